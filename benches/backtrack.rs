@@ -20,7 +20,7 @@ fn bench_backtrack(c: &mut Criterion) {
         .collect::<Vec<_>>()
         .then_ignore(just(';'));
 
-    let xs = five.or(four).repeated().collect::<Vec<_>>();
+    let xs = five.or(four).or_rewinding().repeated().collect::<Vec<_>>();
 
     c.bench_function("backtrack", |b| {
         b.iter(|| {
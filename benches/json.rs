@@ -52,6 +52,12 @@ fn bench_json(c: &mut Criterion) {
         }
     });
 
+    c.bench_function("json_chumsky_zero_copy_try_parse", {
+        use ::chumsky::prelude::*;
+        let json = chumsky_zero_copy::json::<EmptyErr>();
+        move |b| b.iter(|| black_box(json.try_parse(black_box(JSON))).unwrap())
+    });
+
     c.bench_function("json_chumsky_zero_copy_check", {
         use ::chumsky::prelude::*;
         let json = chumsky_zero_copy::json::<EmptyErr>();
@@ -115,6 +115,52 @@ fn bench_or(c: &mut Criterion) {
     });
 }
 
+fn bench_choice_trie(c: &mut Criterion) {
+    const OPS: [&str; 50] = [
+        "+", "-", "*", "/", "%", "^", "!", "&", "|", "&&", "||", "<<", ">>", "+=", "-=", "*=",
+        "/=", "%=", "^=", "&=", "|=", "<<=", ">>=", "=", "==", "!=", ">", "<", ">=", "<=", "@",
+        ".", "..", "..=", "...", ",", ";", ":", "::", "->", "=>", "#", "$", "?", "~", "<-", "<=>",
+        "**", "**=", "//",
+    ];
+
+    let mut by_len = OPS.iter().enumerate().collect::<Vec<_>>();
+    by_len.sort_by_key(|(_, op)| std::cmp::Reverse(op.len()));
+    let ops_choice = choice(
+        by_len
+            .into_iter()
+            .map(|(i, op)| just::<_, &str, extra::Default>(*op).to(i))
+            .collect::<Vec<_>>(),
+    )
+    .padded()
+    .repeated();
+
+    let ops_trie = choice_trie::<_, _, _, &str, extra::Default>(
+        OPS.iter().enumerate().map(|(i, op)| (*op, i)),
+    )
+    .padded()
+    .repeated();
+
+    let input = OPS.join(" ");
+
+    let mut group = c.benchmark_group("choice_trie");
+
+    group.bench_function(BenchmarkId::new("choice::<Vec<_>>", "50 ops"), |b| {
+        b.iter(|| {
+            black_box(ops_choice.parse(black_box(input.as_str())))
+                .into_result()
+                .unwrap();
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("choice_trie", "50 ops"), |b| {
+        b.iter(|| {
+            black_box(ops_trie.parse(black_box(input.as_str())))
+                .into_result()
+                .unwrap();
+        })
+    });
+}
+
 fn bench_group(c: &mut Criterion) {
     let alphabet_group = group((
         just::<_, &str, extra::Default>('A'),
@@ -243,6 +289,40 @@ fn bench_then(c: &mut Criterion) {
     });
 }
 
+fn bench_split_on(c: &mut Criterion) {
+    let separated_by = text::int::<&str, _, extra::Default>(10)
+        .separated_by(just(','))
+        .collect::<Vec<_>>();
+    let split_on =
+        text::split_on(',', text::int::<&str, _, extra::Default>(10)).collect::<Vec<_>>();
+
+    let input = (0..10_000)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let mut group = c.benchmark_group("split_on");
+
+    group.bench_function(
+        BenchmarkId::new("separated_by(just(','))", "10000 ints"),
+        |b| {
+            b.iter(|| {
+                black_box(separated_by.parse(black_box(input.as_str())))
+                    .into_result()
+                    .unwrap();
+            })
+        },
+    );
+
+    group.bench_function(BenchmarkId::new("split_on(',')", "10000 ints"), |b| {
+        b.iter(|| {
+            black_box(split_on.parse(black_box(input.as_str())))
+                .into_result()
+                .unwrap();
+        })
+    });
+}
+
 #[cfg(feature = "regex")]
 fn bench_regex(c: &mut Criterion) {
     let re_foo = regex::<_, _, extra::Default>("foo");
@@ -306,6 +386,6 @@ fn bench_regex(_: &mut Criterion) {}
 criterion_group!(
     name = benches;
     config = utils::make_criterion();
-    targets = bench_choice, bench_or, bench_group, bench_then, bench_regex,
+    targets = bench_choice, bench_or, bench_choice_trie, bench_group, bench_then, bench_split_on, bench_regex,
 );
 criterion_main!(benches);
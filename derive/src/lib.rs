@@ -0,0 +1,270 @@
+//! Derive macros for [chumsky](https://docs.rs/chumsky).
+//!
+//! This crate is an implementation detail of chumsky's `derive` feature. See the documentation of `chumsky::derive`
+//! for how to use it.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Fields, GenericArgument,
+    Ident, LitStr, Path, PathArguments, Type,
+};
+
+/// Derive `chumsky::derive::Grammar` for a struct or enum.
+///
+/// See the documentation of `chumsky::derive` for the attributes that this macro accepts.
+#[proc_macro_derive(Grammar, attributes(grammar))]
+pub fn derive_grammar(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// The literals given to a `#[grammar(...)]` attribute
+#[derive(Default)]
+struct Attrs {
+    token: Option<LitStr>,
+    prefix: Option<LitStr>,
+    suffix: Option<LitStr>,
+}
+
+impl Attrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("grammar")) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("token") {
+                    &mut out.token
+                } else if meta.path.is_ident("prefix") {
+                    &mut out.prefix
+                } else if meta.path.is_ident("suffix") {
+                    &mut out.suffix
+                } else {
+                    return Err(meta.error("expected `token`, `prefix` or `suffix`"));
+                };
+                *slot = Some(meta.value()?.parse()?);
+                Ok(())
+            })?;
+        }
+        Ok(out)
+    }
+
+    // Wrap a parser in this attribute's prefix and suffix, if any
+    fn wrap(&self, parser: TokenStream2) -> TokenStream2 {
+        let parser = match self.prefix.as_ref().map(literal) {
+            Some(prefix) => quote!(#prefix.ignore_then(#parser)),
+            None => parser,
+        };
+        match self.suffix.as_ref().map(literal) {
+            Some(suffix) => quote!(#parser.then_ignore(#suffix)),
+            None => parser,
+        }
+    }
+}
+
+// A literal that ends in an identifier character must not run into a following one, so that `let` doesn't match the
+// start of `letter`
+fn literal(lit: &LitStr) -> TokenStream2 {
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    if lit.value().ends_with(is_ident_char) {
+        quote!(::chumsky::primitive::just(#lit)
+            .then_ignore(
+                ::chumsky::primitive::any()
+                    .filter(|c: &char| c.is_ascii_alphanumeric() || *c == '_')
+                    .not(),
+            )
+            .padded())
+    } else {
+        quote!(::chumsky::primitive::just(#lit).padded())
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "`Grammar` cannot yet be derived for generic types",
+        ));
+    }
+
+    let attrs = Attrs::parse(&input.attrs)?;
+    let mut recursive = false;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let parser = sequence(
+                name,
+                name,
+                quote!(Self),
+                &data.fields,
+                &attrs,
+                &mut recursive,
+            )?;
+            attrs.wrap(parser)
+        }
+        Data::Enum(data) => {
+            if attrs.token.is_some() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "`token` may only be given to unit structs and unit variants",
+                ));
+            }
+            let mut variants = Vec::new();
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                let variant_attrs = Attrs::parse(&variant.attrs)?;
+                let parser = sequence(
+                    ident,
+                    name,
+                    quote!(Self::#ident),
+                    &variant.fields,
+                    &variant_attrs,
+                    &mut recursive,
+                )?;
+                variants.push(variant_attrs.wrap(parser));
+            }
+            let mut variants = variants.into_iter();
+            let first = variants.next().ok_or_else(|| {
+                syn::Error::new(name.span(), "`Grammar` cannot be derived for empty enums")
+            })?;
+            // Variants are tried in order, backtracking between them, as for `choice`
+            let parser =
+                variants.fold(first, |acc, parser| quote!(#acc.or(#parser).or_rewinding()));
+            attrs.wrap(parser)
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                name.span(),
+                "`Grammar` cannot be derived for unions",
+            ))
+        }
+    };
+
+    let body = if recursive {
+        quote!(::chumsky::recursive::recursive(|this| #body).boxed())
+    } else {
+        quote!(#body.boxed())
+    };
+
+    Ok(quote! {
+        impl<'a> ::chumsky::derive::Grammar<'a> for #name {
+            fn parser<E>() -> ::chumsky::Boxed<'a, 'a, &'a str, Self, E>
+            where
+                E: ::chumsky::extra::ParserExtra<'a, &'a str> + 'a,
+            {
+                use ::chumsky::Parser as _;
+                #body
+            }
+        }
+    })
+}
+
+// Parse each of the fields in turn, then build the struct or variant from them
+fn sequence(
+    name: &Ident,
+    self_name: &Ident,
+    ctor: TokenStream2,
+    fields: &Fields,
+    attrs: &Attrs,
+    recursive: &mut bool,
+) -> syn::Result<TokenStream2> {
+    if let Fields::Unit = fields {
+        let token = attrs.token.as_ref().ok_or_else(|| {
+            syn::Error::new(
+                name.span(),
+                "unit structs and unit variants need a `#[grammar(token = \"...\")]` attribute",
+            )
+        })?;
+        let token = literal(token);
+        return Ok(quote!(#token.map(|_| #ctor)));
+    }
+    if let Some(token) = &attrs.token {
+        return Err(syn::Error::new(
+            token.span(),
+            "`token` may only be given to unit structs and unit variants",
+        ));
+    }
+
+    let mut parser = None;
+    let mut pattern = None;
+    let mut bindings = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let field_attrs = Attrs::parse(&field.attrs)?;
+        if let Some(token) = &field_attrs.token {
+            return Err(syn::Error::new(
+                token.span(),
+                "`token` may only be given to unit structs and unit variants",
+            ));
+        }
+        // The prefix and suffix of an optional field are only expected when the field is present
+        let field_parser = match wrapper(&field.ty) {
+            Some((wrapper, inner)) if wrapper == "Option" => {
+                let inner = field_attrs.wrap(field_parser(inner, self_name, recursive));
+                quote!(#inner.or_not())
+            }
+            _ => field_attrs.wrap(field_parser(&field.ty, self_name, recursive)),
+        };
+        let binding = Ident::new(&format!("field{i}"), field.span());
+        (parser, pattern) = match (parser, pattern) {
+            (Some(parser), Some(pattern)) => (
+                Some(quote!(#parser.then(#field_parser))),
+                Some(quote!((#pattern, #binding))),
+            ),
+            _ => (Some(field_parser), Some(quote!(#binding))),
+        };
+        bindings.push((field.ident.as_ref(), binding));
+    }
+
+    let value = if let Fields::Named(_) = fields {
+        let fields = bindings
+            .iter()
+            .map(|(ident, binding)| quote!(#ident: #binding));
+        quote!(#ctor { #(#fields),* })
+    } else {
+        let fields = bindings.iter().map(|(_, binding)| binding);
+        quote!(#ctor(#(#fields),*))
+    };
+    Ok(quote!(#parser.map(|#pattern| #value)))
+}
+
+// `Box`, `Option` and `Vec` are unwrapped here rather than left to their `Grammar` implementations so that any use of
+// `Self` inside them refers to the parser being defined instead of recursing forever
+fn field_parser(ty: &Type, name: &Ident, recursive: &mut bool) -> TokenStream2 {
+    if let Some((wrapper, inner)) = wrapper(ty) {
+        let inner = field_parser(inner, name, recursive);
+        match wrapper.as_str() {
+            "Box" => quote!(#inner.map(::core::convert::Into::into)),
+            "Option" => quote!(#inner.or_not()),
+            _ => quote!(#inner.repeated().collect()),
+        }
+    } else if is_self(ty, name) {
+        *recursive = true;
+        quote!(this.clone())
+    } else {
+        quote!(<#ty as ::chumsky::derive::Grammar<'a>>::parser::<E>())
+    }
+}
+
+fn is_self(ty: &Type, name: &Ident) -> bool {
+    matches!(ty, Type::Path(ty) if ty.qself.is_none() && (ty.path.is_ident("Self") || ty.path.is_ident(name)))
+}
+
+// The name of the wrapper and the `T` in `Box<T>`, `Option<T>` or `Vec<T>`, if the type is one of those
+fn wrapper(ty: &Type) -> Option<(String, &Type)> {
+    let Type::Path(ty) = ty else { return None };
+    let Path { segments, .. } = &ty.path;
+    let last = segments.last()?;
+    let wrapper = last.ident.to_string();
+    if !matches!(wrapper.as_str(), "Box" | "Option" | "Vec") {
+        return None;
+    }
+    match &last.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some((wrapper, inner)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,111 @@
+use chumsky::{derive::Grammar, prelude::*};
+
+#[derive(Grammar, Debug, PartialEq)]
+enum Expr {
+    Num(i64),
+    #[grammar(token = "pi")]
+    Pi,
+    Neg(#[grammar(prefix = "-")] Box<Expr>),
+    #[grammar(prefix = "(", suffix = ")")]
+    Add(Box<Expr>, #[grammar(prefix = "+")] Box<Expr>),
+    #[grammar(prefix = "[", suffix = "]")]
+    Mul(Box<Expr>, #[grammar(prefix = "*")] Box<Expr>),
+}
+
+fn num(n: i64) -> Box<Expr> {
+    Box::new(Expr::Num(n))
+}
+
+fn expr<'a>() -> impl Parser<'a, &'a str, Expr, extra::Err<Rich<'a, char>>> {
+    Expr::parser()
+}
+
+#[test]
+fn enum_variants_are_a_choice() {
+    assert_eq!(expr().parse("42").into_result(), Ok(Expr::Num(42)));
+    assert_eq!(expr().parse(" pi ").into_result(), Ok(Expr::Pi));
+    assert_eq!(
+        expr().parse("(1 + 2)").into_result(),
+        Ok(Expr::Add(num(1), num(2)))
+    );
+    assert!(expr().parse("e").has_errors());
+}
+
+#[test]
+fn literals_end_at_an_identifier_boundary() {
+    assert!(expr().parse("pie").has_errors());
+    assert!(expr().parse("pi_").has_errors());
+    assert_eq!(
+        expr().parse("(pi+1)").into_result(),
+        Ok(Expr::Add(Box::new(Expr::Pi), num(1)))
+    );
+
+    let parser = Let::parser::<extra::Err<Simple<char>>>();
+    assert!(parser.parse("letx = 1;").has_errors());
+    assert!(parser.parse("let x = elsewhere;").has_errors());
+}
+
+#[test]
+fn fields_are_a_sequence() {
+    assert_eq!(
+        expr().parse("[(1 + pi) * -3]").into_result(),
+        Ok(Expr::Mul(
+            Box::new(Expr::Add(num(1), Box::new(Expr::Pi))),
+            num(-3)
+        )),
+    );
+    assert_eq!(
+        expr().parse("-(1+-2)").into_result(),
+        Ok(Expr::Neg(Box::new(Expr::Add(num(1), num(-2))))),
+    );
+    // Every literal is required
+    assert!(expr().parse("(1 + 2").has_errors());
+    assert!(expr().parse("(1 2)").has_errors());
+    assert!(expr().parse("[1 + 2]").has_errors());
+}
+
+#[derive(Grammar, Debug, PartialEq)]
+#[grammar(prefix = "let")]
+struct Let {
+    name: String,
+    #[grammar(prefix = "=")]
+    values: Vec<u8>,
+    #[grammar(prefix = "else")]
+    fallback: Option<Box<Let>>,
+    end: Semi,
+}
+
+#[derive(Grammar, Debug, PartialEq)]
+#[grammar(token = ";")]
+struct Semi;
+
+#[test]
+fn structs_and_wrappers() {
+    let parser = Let::parser::<extra::Err<Simple<char>>>();
+
+    assert_eq!(
+        parser.parse("let x = 1 2 3;").into_result(),
+        Ok(Let {
+            name: "x".to_string(),
+            values: vec![1, 2, 3],
+            fallback: None,
+            end: Semi,
+        }),
+    );
+    assert_eq!(
+        parser.parse("let x = else let y = 4;;").into_result(),
+        Ok(Let {
+            name: "x".to_string(),
+            values: vec![],
+            fallback: Some(Box::new(Let {
+                name: "y".to_string(),
+                values: vec![4],
+                fallback: None,
+                end: Semi,
+            })),
+            end: Semi,
+        }),
+    );
+    // Integers that don't fit produce an error
+    assert!(parser.parse("let x = 256;").has_errors());
+}
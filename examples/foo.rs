@@ -47,7 +47,9 @@ fn parser<'a>() -> impl Parser<'a, &'a str, Expr<'a>> {
         let atom = int
             .or(expr.delimited_by(just('('), just(')')))
             .or(call)
+            // A call starts with an identifier, so backtrack to parse a lone variable
             .or(ident.map(Expr::Var))
+            .or_rewinding()
             .padded();
 
         let op = |c| just(c).padded();
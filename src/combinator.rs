@@ -108,6 +108,11 @@ where
     ) -> IPResult<M, O> {
         self.parser.next_cfg(inp, &mut state.0, &state.1)
     }
+
+    #[inline(always)]
+    fn size_hint<M: Mode>(&self, state: &Self::IterState<M>) -> usize {
+        self.parser.size_hint_cfg(&state.0, &state.1)
+    }
 }
 
 /// See [`ConfigIterParser::try_configure`]
@@ -183,6 +188,11 @@ where
     ) -> IPResult<M, O> {
         self.parser.next_cfg(inp, &mut state.0, &state.1)
     }
+
+    #[inline(always)]
+    fn size_hint<M: Mode>(&self, state: &Self::IterState<M>) -> usize {
+        self.parser.size_hint_cfg(&state.0, &state.1)
+    }
 }
 
 /// See [`Parser::to_slice`]
@@ -247,13 +257,15 @@ where
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-        let before = inp.offset();
+        let before = inp.save();
         self.parser.go::<Emit>(inp).and_then(|out| {
             if (self.filter)(&out) {
                 Ok(M::bind(|| out))
             } else {
-                let err_span = inp.span_since(before);
+                let err_span = inp.span_since(before.offset());
                 inp.add_alt(inp.offset().offset, None, None, err_span);
+                // A rejected output counts as not having matched at all, so leave it unconsumed
+                inp.rewind_input(before);
                 Err(())
             }
         })
@@ -291,6 +303,7 @@ where
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
         let out = self.parser.go::<M>(inp)?;
+        // In `Check` mode, `M::map` discards the output without calling the mapper
         Ok(M::map(out, &self.mapper))
     }
 
@@ -328,6 +341,11 @@ where
             Err(()) => Err(()),
         }
     }
+
+    #[inline(always)]
+    fn size_hint<M: Mode>(&self, state: &Self::IterState<M>) -> usize {
+        self.parser.size_hint(state)
+    }
 }
 
 /// See [`Parser::map_with`].
@@ -402,6 +420,11 @@ where
             Err(()) => Err(()),
         }
     }
+
+    #[inline(always)]
+    fn size_hint<M: Mode>(&self, state: &Self::IterState<M>) -> usize {
+        self.parser.size_hint(state)
+    }
 }
 
 /// See [`Parser::map_group`].
@@ -505,7 +528,7 @@ where
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, I::Span> {
         let before = inp.offset();
-        self.parser.go::<M>(inp)?;
+        self.parser.go::<Check>(inp)?;
         Ok(M::bind(|| inp.span_since(before)))
     }
 
@@ -540,13 +563,14 @@ where
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-        let before = inp.offset();
+        let before = inp.save();
         let out = self.parser.go::<Emit>(inp)?;
-        let span = inp.span_since(before);
+        let span = inp.span_since(before.offset());
         match (self.mapper)(out, span) {
             Ok(out) => Ok(M::bind(|| out)),
             Err(err) => {
                 inp.add_alt_err(inp.offset().offset, err);
+                inp.rewind_input(before);
                 Err(())
             }
         }
@@ -583,12 +607,13 @@ where
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-        let before = inp.offset();
+        let before = inp.save();
         let out = self.parser.go::<Emit>(inp)?;
-        match (self.mapper)(out, &mut MapExtra::new(before, inp)) {
+        match (self.mapper)(out, &mut MapExtra::new(before.offset(), inp)) {
             Ok(out) => Ok(M::bind(|| out)),
             Err(err) => {
                 inp.add_alt_err(inp.offset().offset, err);
+                inp.rewind_input(before);
                 Err(())
             }
         }
@@ -832,6 +857,74 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::memoized_left_recursive`].
+#[cfg(feature = "memoization")]
+#[derive(Copy, Clone)]
+pub struct MemoizedLeftRecursive<A> {
+    pub(crate) parser: A,
+}
+
+#[cfg(feature = "memoization")]
+impl<'a, I, E, A, O> ParserSealed<'a, I, O, E> for MemoizedLeftRecursive<A>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    O: Clone + 'static,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        let key = (before.offset, self as *const _ as *const () as usize);
+
+        // A left-recursive call sees the seed grown so far, or fails if there isn't one yet
+        match inp.seeds.get(&key) {
+            Some(Some((out, end))) => {
+                let out = out
+                    .downcast_ref::<O>()
+                    .expect("left-recursive seed has the wrong type")
+                    .clone();
+                inp.offset = *end;
+                return Ok(M::bind(|| out));
+            }
+            Some(None) => {
+                let err_span = inp.span_since(before.offset());
+                inp.add_alt(key.0, None, None, err_span);
+                return Err(());
+            }
+            None => {}
+        }
+
+        // Grow the seed: reparse with the previous result as the left-recursive call's output until the parse no
+        // longer gets any further. Outputs are needed for seeds, so they're always generated.
+        inp.seeds.insert(key, None);
+        let mut best_end: Option<input::Marker<'a, '_, I>> = None;
+        loop {
+            inp.rewind(before);
+            let Ok(out) = self.parser.go::<Emit>(inp) else {
+                break;
+            };
+            let end = inp.save();
+            if matches!(best_end, Some(best_end) if end.offset <= best_end.offset) {
+                break;
+            }
+            inp.seeds.insert(key, Some((Box::new(out), end.offset)));
+            best_end = Some(end);
+        }
+        let seed = inp.seeds.remove(&key).flatten();
+
+        let (Some(end), Some((out, _))) = (best_end, seed) else {
+            return Err(());
+        };
+        inp.rewind(end);
+        let out = *out
+            .downcast::<O>()
+            .expect("left-recursive seed has the wrong type");
+        Ok(M::bind(|| out))
+    }
+
+    go_extra!(O);
+}
+
 /// See [`Parser::then`].
 pub struct Then<A, B, OA, OB, E> {
     pub(crate) parser_a: A,
@@ -974,11 +1067,15 @@ where
 
         #[cfg(feature = "memoization")]
         let mut memos = HashMap::default();
+        #[cfg(feature = "memoization")]
+        let mut seeds = HashMap::default();
         let res = inp.with_input(
             &inp2,
             |inp| (&self.parser_a).then_ignore(end()).go::<M>(inp),
             #[cfg(feature = "memoization")]
             &mut memos,
+            #[cfg(feature = "memoization")]
+            &mut seeds,
         );
 
         // TODO: Translate secondary error offsets too
@@ -1066,6 +1163,45 @@ where
     }
 }
 
+/// See [`Parser::then_with_span`].
+pub struct ThenWithSpan<A, F, OA> {
+    pub(crate) parser: A,
+    pub(crate) then: F,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OA>,
+}
+
+impl<A: Copy, F: Copy, OA> Copy for ThenWithSpan<A, F, OA> {}
+impl<A: Clone, F: Clone, OA> Clone for ThenWithSpan<A, F, OA> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            then: self.then.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, E, A, F, P, OA, U> ParserSealed<'a, I, U, E> for ThenWithSpan<A, F, OA>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    F: Fn(OA, I::Span) -> P,
+    P: Parser<'a, I, U, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, U> {
+        let before = inp.offset();
+        // The output of the first parser is always needed to build the second
+        let out = self.parser.go::<Emit>(inp)?;
+        let span = inp.span_since(before);
+        (self.then)(out, span).go::<M>(inp)
+    }
+
+    go_extra!(U);
+}
+
 /// See [`Parser::then_with_ctx`].
 pub struct ThenWithCtx<A, B, OA, I, E> {
     pub(crate) parser: A,
@@ -1206,6 +1342,7 @@ pub struct DelimitedBy<A, B, C, OB, OC> {
     pub(crate) parser: A,
     pub(crate) start: B,
     pub(crate) end: C,
+    pub(crate) recover_unclosed: bool,
     #[allow(dead_code)]
     pub(crate) phantom: EmptyPhantom<(OB, OC)>,
 }
@@ -1217,11 +1354,51 @@ impl<A: Clone, B: Clone, C: Clone, OB, OC> Clone for DelimitedBy<A, B, C, OB, OC
             parser: self.parser.clone(),
             start: self.start.clone(),
             end: self.end.clone(),
+            recover_unclosed: self.recover_unclosed,
             phantom: EmptyPhantom::new(),
         }
     }
 }
 
+impl<A, B, C, OB, OC> DelimitedBy<A, B, C, OB, OC> {
+    /// Recover from a missing closing delimiter by reporting an [`Error::unclosed_delimiter`] error at the span of the
+    /// opening delimiter.
+    ///
+    /// If the inner pattern succeeds but the closing delimiter cannot be found, the error is recorded and the output
+    /// of the inner pattern is still produced. Parsing continues from the point at which the closing delimiter was
+    /// expected. Recoveries are reported by [`Parser::parse_with_recoveries`] with the strategy name
+    /// `"recover_unclosed"`.
+    ///
+    /// Delimiters may be made up of several tokens, like `just("begin")`. When delimited patterns are nested, each
+    /// closing delimiter closes the innermost opening delimiter, so an error is always reported at the opening
+    /// delimiter that was left unbalanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let block = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+    ///     .and_is(text::ascii::keyword("end").not())
+    ///     .padded()
+    ///     .repeated()
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just("begin"), just("end"))
+    ///     .recover_unclosed();
+    ///
+    /// assert_eq!(block.parse("begin x y end").into_result(), Ok(vec!["x", "y"]));
+    ///
+    /// let res = block.parse("begin x y");
+    /// assert_eq!(res.output(), Some(&vec!["x", "y"]));
+    /// assert_eq!(res.errors().map(|e| *e.span()).collect::<Vec<_>>(), vec![SimpleSpan::new(0, 5)]);
+    /// ```
+    pub fn recover_unclosed(self) -> Self {
+        Self {
+            recover_unclosed: true,
+            ..self
+        }
+    }
+}
+
 impl<'a, I, E, A, B, C, OA, OB, OC> ParserSealed<'a, I, OA, E> for DelimitedBy<A, B, C, OB, OC>
 where
     I: Input<'a>,
@@ -1232,15 +1409,83 @@ where
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, OA> {
+        if !self.recover_unclosed {
+            self.start.go::<Check>(inp)?;
+            let a = self.parser.go::<M>(inp)?;
+            self.end.go::<Check>(inp)?;
+            return Ok(a);
+        }
+
+        let start = inp.offset();
         self.start.go::<Check>(inp)?;
+        let open_span = inp.span_since(start);
         let a = self.parser.go::<M>(inp)?;
-        self.end.go::<Check>(inp)?;
-        Ok(a)
+        let before = inp.save();
+        match self.end.go::<Check>(inp) {
+            Ok(()) => Ok(a),
+            Err(()) if !inp.can_recover() => Err(()),
+            Err(()) => {
+                inp.rewind(before);
+                inp.errors.alt.take();
+                inp.emit(inp.offset, E::Error::unclosed_delimiter(open_span));
+                inp.record_recovery(before.offset, "recover_unclosed");
+                Ok(a)
+            }
+        }
     }
 
     go_extra!(OA);
 }
 
+/// See [`Parser::delimited_by_spanned`].
+pub struct DelimitedBySpanned<A, B, C, OB, OC> {
+    pub(crate) parser: A,
+    pub(crate) start: B,
+    pub(crate) end: C,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(OB, OC)>,
+}
+
+impl<A: Copy, B: Copy, C: Copy, OB, OC> Copy for DelimitedBySpanned<A, B, C, OB, OC> {}
+impl<A: Clone, B: Clone, C: Clone, OB, OC> Clone for DelimitedBySpanned<A, B, C, OB, OC> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            start: self.start.clone(),
+            end: self.end.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, E, A, B, C, OA, OB, OC> ParserSealed<'a, I, (I::Span, OA, I::Span), E>
+    for DelimitedBySpanned<A, B, C, OB, OC>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+    C: Parser<'a, I, OC, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, (I::Span, OA, I::Span)> {
+        let before = inp.offset();
+        self.start.go::<Check>(inp)?;
+        let open = M::bind(|| inp.span_since(before));
+        let a = self.parser.go::<M>(inp)?;
+        let before = inp.offset();
+        self.end.go::<Check>(inp)?;
+        let close = M::bind(|| inp.span_since(before));
+        Ok(M::combine(
+            M::combine(open, a, |open, a| (open, a)),
+            close,
+            |(open, a), close| (open, a, close),
+        ))
+    }
+
+    go_extra!((I::Span, OA, I::Span));
+}
+
 /// See [`Parser::padded_by`].
 pub struct PaddedBy<A, B, OB> {
     pub(crate) parser: A,
@@ -1281,7 +1526,34 @@ where
 /// See [`Parser::or`].
 #[derive(Copy, Clone)]
 pub struct Or<A, B> {
-    pub(crate) choice: crate::primitive::Choice<(A, B)>,
+    pub(crate) first: A,
+    pub(crate) second: B,
+    pub(crate) rewinding: bool,
+}
+
+impl<A, B> Or<A, B> {
+    /// Try the second parser whenever the first fails, even if the first consumed some input before failing.
+    ///
+    /// By default, [`Parser::or`] is committed to the first parser once it has consumed input. With this flag set,
+    /// parsing always backtracks to where the first parser started instead, as [`choice`](crate::primitive::choice)
+    /// does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::Simple};
+    /// let arrow = just::<_, _, extra::Err<Simple<char>>>('-').then(just('>')).to_slice();
+    ///
+    /// // `-` is consumed by the first parser before it fails, so `or` never tries `just("-")`
+    /// assert!(arrow.or(just("-")).parse("-").has_errors());
+    /// assert_eq!(arrow.or(just("-")).or_rewinding().parse("-").into_result(), Ok("-"));
+    /// ```
+    pub fn or_rewinding(self) -> Self {
+        Self {
+            rewinding: true,
+            ..self
+        }
+    }
 }
 
 impl<'a, I, O, E, A, B> ParserSealed<'a, I, O, E> for Or<A, B>
@@ -1293,7 +1565,16 @@ where
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-        self.choice.go::<M>(inp)
+        let before = inp.save();
+        match self.first.go::<M>(inp) {
+            Ok(out) => Ok(out),
+            // Committed choice: the first parser consumed input before failing, so don't try the second
+            Err(()) if !self.rewinding && inp.offset() != before.offset() => Err(()),
+            Err(()) => {
+                inp.rewind(before);
+                self.second.go::<M>(inp)
+            }
+        }
     }
 
     go_extra!(O);
@@ -1327,25 +1608,63 @@ impl RepeatedCfg {
     }
 }
 
+/// A function that builds the error produced by [`Repeated::at_least_with`], [`Repeated::at_most_with`] or
+/// [`SeparatedBy::at_least_with`].
+///
+/// This is implemented for any `Fn(I::Span) -> E::Error`, and for `()`, which leaves the default error in place.
+pub trait CountError<'a, I: Input<'a>, E: ParserExtra<'a, I>> {
+    #[doc(hidden)]
+    const CUSTOM: bool;
+
+    #[doc(hidden)]
+    fn make_error(&self, span: I::Span) -> Option<E::Error>;
+}
+
+impl<'a, I: Input<'a>, E: ParserExtra<'a, I>> CountError<'a, I, E> for () {
+    const CUSTOM: bool = false;
+
+    #[inline(always)]
+    fn make_error(&self, _span: I::Span) -> Option<E::Error> {
+        None
+    }
+}
+
+impl<'a, I: Input<'a>, E: ParserExtra<'a, I>, F: Fn(I::Span) -> E::Error> CountError<'a, I, E>
+    for F
+{
+    const CUSTOM: bool = true;
+
+    #[inline(always)]
+    fn make_error(&self, span: I::Span) -> Option<E::Error> {
+        Some(self(span))
+    }
+}
+
 /// See [`Parser::repeated`].
-pub struct Repeated<A, OA, I, E> {
+pub struct Repeated<A, OA, I, E, L = (), U = ()> {
     pub(crate) parser: A,
     pub(crate) at_least: usize,
     // Slightly evil: Should be `Option<usize>`, but we encode `!0` as 'no cap' because it's so large
     pub(crate) at_most: u64,
+    pub(crate) reserved: usize,
+    pub(crate) too_few: L,
+    pub(crate) too_many: U,
     #[cfg(debug_assertions)]
     pub(crate) location: Location<'static>,
     #[allow(dead_code)]
     pub(crate) phantom: EmptyPhantom<(OA, E, I)>,
 }
 
-impl<A: Copy, OA, I, E> Copy for Repeated<A, OA, I, E> {}
-impl<A: Clone, OA, I, E> Clone for Repeated<A, OA, I, E> {
+impl<A: Copy, OA, I, E, L: Copy, U: Copy> Copy for Repeated<A, OA, I, E, L, U> {}
+impl<A: Clone, OA, I, E, L: Clone, U: Clone> Clone for Repeated<A, OA, I, E, L, U> {
     fn clone(&self) -> Self {
         Self {
             parser: self.parser.clone(),
             at_least: self.at_least,
             at_most: self.at_most,
+            reserved: self.reserved,
+            too_few: self.too_few.clone(),
+            too_many: self.too_many.clone(),
             #[cfg(debug_assertions)]
             location: self.location,
             phantom: EmptyPhantom::new(),
@@ -1353,7 +1672,7 @@ impl<A: Clone, OA, I, E> Clone for Repeated<A, OA, I, E> {
     }
 }
 
-impl<'a, A, OA, I, E> Repeated<A, OA, I, E>
+impl<'a, A, OA, I, E, L, U> Repeated<A, OA, I, E, L, U>
 where
     A: Parser<'a, I, OA, E>,
     I: Input<'a>,
@@ -1364,6 +1683,50 @@ where
         Self { at_least, ..self }
     }
 
+    /// Require that the pattern appear at least a minimum number of times, using `too_few` to build the error
+    /// produced when it does not.
+    ///
+    /// `too_few` is given the span at which another instance of the pattern was required. This is useful for
+    /// producing diagnostics specific to your language instead of a generic 'expected ...' error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let params = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+    ///     .then_ignore(just(',').or_not())
+    ///     .repeated()
+    ///     .at_least_with(1, |span| Rich::custom(span, "a function needs at least one parameter"))
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just('('), just(')'));
+    /// let func = text::ascii::keyword("fn")
+    ///     .padded()
+    ///     .ignore_then(text::ascii::ident())
+    ///     .then(params);
+    ///
+    /// assert_eq!(func.parse("fn f(x,y)").into_result(), Ok(("f", vec!["x", "y"])));
+    /// assert_eq!(
+    ///     func.parse("fn f()").into_result(),
+    ///     Err(vec![Rich::custom((5..5).into(), "a function needs at least one parameter")]),
+    /// );
+    /// ```
+    pub fn at_least_with<F>(self, at_least: usize, too_few: F) -> Repeated<A, OA, I, E, F, U>
+    where
+        F: Fn(I::Span) -> E::Error,
+    {
+        Repeated {
+            parser: self.parser,
+            at_least,
+            at_most: self.at_most,
+            reserved: self.reserved,
+            too_few,
+            too_many: self.too_many,
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Require that the pattern appear at most a maximum number of times.
     pub fn at_most(self, at_most: usize) -> Self {
         Self {
@@ -1372,6 +1735,46 @@ where
         }
     }
 
+    /// Require that the pattern appear at most a maximum number of times, using `too_many` to build the error
+    /// produced when it appears more often.
+    ///
+    /// Unlike [`Repeated::at_most`], which simply stops after the maximum and leaves any further instances of the
+    /// pattern to whatever comes next, this checks whether the pattern would match again and fails if it does.
+    /// `too_many` is given the span of the excess instances.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let area_code = any::<_, extra::Err<Rich<char>>>()
+    ///     .filter(char::is_ascii_digit)
+    ///     .repeated()
+    ///     .at_most_with(3, |span| Rich::custom(span, "an area code has at most 3 digits"))
+    ///     .to_slice();
+    ///
+    /// assert_eq!(area_code.parse("012").into_result(), Ok("012"));
+    /// assert_eq!(
+    ///     area_code.parse("01234").into_result(),
+    ///     Err(vec![Rich::custom((3..5).into(), "an area code has at most 3 digits")]),
+    /// );
+    /// ```
+    pub fn at_most_with<F>(self, at_most: usize, too_many: F) -> Repeated<A, OA, I, E, L, F>
+    where
+        F: Fn(I::Span) -> E::Error,
+    {
+        Repeated {
+            parser: self.parser,
+            at_least: self.at_least,
+            at_most: at_most as u64,
+            reserved: self.reserved,
+            too_few: self.too_few,
+            too_many,
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Require that the pattern appear exactly the given number of times.
     ///
     /// ```
@@ -1418,54 +1821,217 @@ where
             ..self
         }
     }
-}
 
-impl<'a, I, E, A, OA> ParserSealed<'a, I, (), E> for Repeated<A, OA, I, E>
-where
-    I: Input<'a>,
-    E: ParserExtra<'a, I>,
-    A: Parser<'a, I, OA, E>,
-{
-    #[inline(always)]
-    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
-        if self.at_most == !0 && self.at_least == 0 {
-            loop {
-                let before = inp.save();
-                match self.parser.go::<Check>(inp) {
-                    Ok(()) => {}
-                    Err(()) => {
-                        // TODO: Helper for this? Rewind does this? (seconds one may be bad for other cases)
-                        inp.errors.alt = None;
-                        inp.rewind(before);
-                        break Ok(M::bind(|| ()));
-                    }
-                }
-                #[cfg(debug_assertions)]
-                debug_assert!(
-                    before.offset() != inp.offset(),
-                    "found Repeated combinator making no progress at {}",
-                    self.location,
-                );
-            }
-        } else {
-            let mut state = self.make_iter::<Check>(inp)?;
-            loop {
-                #[cfg(debug_assertions)]
-                let before = inp.offset();
-                match self.next::<Check>(inp, &mut state) {
-                    Ok(Some(())) => {}
-                    Ok(None) => break Ok(M::bind(|| ())),
-                    // TODO: Technically we should be rewinding here: as-is, this is invalid since errorring parsers
-                    // are permitted to leave input state unspecified. Really, unwinding should occur *here* and not in
-                    // `next`.
-                    Err(()) => break Err(()),
-                }
-                #[cfg(debug_assertions)]
-                debug_assert!(
-                    before != inp.offset(),
-                    "found Repeated combinator making no progress at {}",
-                    self.location,
-                );
+    /// Reserve space for `n` instances of the pattern when collecting them, such as with [`IterParser::collect`].
+    ///
+    /// This is only a hint, and does not change which inputs are accepted. Without it, space is reserved for the
+    /// minimum number of instances (see [`Repeated::at_least`] and [`Repeated::exactly`]), up to a limit of
+    /// 1024 instances so that a large count taken from the input cannot cause a large allocation up front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let words = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .padded()
+    ///     .repeated()
+    ///     .reserve(64)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let out = words.parse("the quick brown fox").into_result().unwrap();
+    /// assert_eq!(out, ["the", "quick", "brown", "fox"]);
+    /// assert!(out.capacity() >= 64);
+    /// ```
+    pub fn reserve(self, n: usize) -> Self {
+        Self {
+            reserved: n,
+            ..self
+        }
+    }
+
+    // The number of further instances to reserve space for, given how many have been parsed already
+    fn remaining_reserve(&self, count: usize, at_least: usize) -> usize {
+        const MAX_RESERVED: usize = 1024;
+        self.reserved
+            .max(at_least.min(MAX_RESERVED))
+            .saturating_sub(count)
+    }
+
+    /// Allow whitespace before each instance of the pattern, so that instances may be separated by whitespace.
+    ///
+    /// Whitespace following the last instance is not consumed, leaving it to whatever parser comes next. This
+    /// matters in grammars where whitespace can be significant to a following token. To also consume whitespace after
+    /// the last instance, use [`Parser::padded`] on the pattern itself instead, as in `pattern.padded().repeated()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let nums = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .repeated()
+    ///     .allow_whitespace()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(nums.parse("1 2\n 3").into_result(), Ok(vec!["1", "2", "3"]));
+    ///
+    /// // Whitespace after the last number is left for the terminator
+    /// let stmt = nums.then_ignore(just(" ;"));
+    /// assert_eq!(stmt.parse("1 2 ;").into_result(), Ok(vec!["1", "2"]));
+    /// ```
+    pub fn allow_whitespace(self) -> Repeated<crate::text::PaddedBefore<A>, OA, I, E, L, U> {
+        Repeated {
+            parser: crate::text::PaddedBefore {
+                parser: self.parser,
+            },
+            at_least: self.at_least,
+            at_most: self.at_most,
+            reserved: self.reserved,
+            too_few: self.too_few,
+            too_many: self.too_many,
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    #[inline(always)]
+    fn next_within<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        count: &mut usize,
+        at_least: usize,
+        at_most: u64,
+    ) -> IPResult<M, OA>
+    where
+        L: CountError<'a, I, E>,
+        U: CountError<'a, I, E>,
+    {
+        if *count as u64 >= at_most {
+            return if U::CUSTOM {
+                self.check_too_many(inp).map(|()| None)
+            } else {
+                Ok(None)
+            };
+        }
+
+        inp.step()?;
+        let before = inp.save();
+        // A custom error replaces the errors of a required instance, so set aside errors from earlier attempts that
+        // it must not replace
+        let custom = L::CUSTOM && *count < at_least;
+        let alt = if custom { inp.errors.alt.take() } else { None };
+        let res = self.parser.go::<M>(inp);
+        let consumed = inp.offset() != before.offset();
+        if custom && res.is_err() && !consumed {
+            inp.errors.alt = None;
+            if let Some(err) = self.too_few.make_error(inp.span_since(before.offset())) {
+                inp.add_alt_err(before.offset, err);
+            }
+        }
+        if let Some(alt) = alt {
+            inp.add_alt_err(alt.pos, alt.err);
+        }
+        match res {
+            Ok(item) => {
+                *count += 1;
+                Ok(Some(item))
+            }
+            Err(()) => {
+                inp.rewind(before);
+                if *count >= at_least {
+                    Ok(None)
+                } else {
+                    Err(())
+                }
+            }
+        }
+    }
+
+    // Having reached the maximum, see how many more instances of the pattern follow
+    fn check_too_many(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<Check, ()>
+    where
+        U: CountError<'a, I, E>,
+    {
+        let before = inp.save();
+        let alt = inp.errors.alt.take();
+        let mut end = None;
+        loop {
+            let item_start = inp.offset();
+            if self.parser.go::<Check>(inp).is_err() || inp.offset() == item_start {
+                break;
+            }
+            end = Some(inp.offset());
+        }
+        inp.rewind(before);
+        inp.errors.alt = alt;
+
+        match end {
+            Some(end) => {
+                if let Some(err) = self.too_many.make_error(inp.span(before.offset()..end)) {
+                    inp.add_alt_err(before.offset, err);
+                }
+                Err(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, I, E, A, OA, L, U> ParserSealed<'a, I, (), E> for Repeated<A, OA, I, E, L, U>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    L: CountError<'a, I, E>,
+    U: CountError<'a, I, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
+        if self.at_most == !0 && self.at_least == 0 {
+            loop {
+                inp.step()?;
+                let before = inp.save();
+                match self.parser.go::<Check>(inp) {
+                    Ok(()) => {}
+                    Err(()) => {
+                        // TODO: Helper for this? Rewind does this? (seconds one may be bad for other cases)
+                        inp.errors.alt = None;
+                        inp.rewind(before);
+                        break Ok(M::bind(|| ()));
+                    }
+                }
+                if before.offset() == inp.offset() {
+                    #[cfg(debug_assertions)]
+                    panic!(
+                        "found Repeated combinator making no progress at {}",
+                        self.location,
+                    );
+                    // The pattern would match forever, so stop rather than hanging
+                    #[cfg(not(debug_assertions))]
+                    break Ok(M::bind(|| ()));
+                }
+            }
+        } else {
+            let mut state = self.make_iter::<Check>(inp)?;
+            loop {
+                let before = inp.offset();
+                match self.next::<Check>(inp, &mut state) {
+                    Ok(Some(())) => {}
+                    Ok(None) => break Ok(M::bind(|| ())),
+                    // TODO: Technically we should be rewinding here: as-is, this is invalid since errorring parsers
+                    // are permitted to leave input state unspecified. Really, unwinding should occur *here* and not in
+                    // `next`.
+                    Err(()) => break Err(()),
+                }
+                if before == inp.offset() {
+                    #[cfg(debug_assertions)]
+                    panic!(
+                        "found Repeated combinator making no progress at {}",
+                        self.location,
+                    );
+                    #[cfg(not(debug_assertions))]
+                    break Ok(M::bind(|| ()));
+                }
             }
         }
     }
@@ -1473,11 +2039,13 @@ where
     go_extra!(());
 }
 
-impl<'a, A, O, I, E> IterParserSealed<'a, I, O, E> for Repeated<A, O, I, E>
+impl<'a, A, O, I, E, L, U> IterParserSealed<'a, I, O, E> for Repeated<A, O, I, E, L, U>
 where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
     A: Parser<'a, I, O, E>,
+    L: CountError<'a, I, E>,
+    U: CountError<'a, I, E>,
 {
     type IterState<M: Mode> = usize;
 
@@ -1495,33 +2063,22 @@ where
         inp: &mut InputRef<'a, '_, I, E>,
         count: &mut Self::IterState<M>,
     ) -> IPResult<M, O> {
-        if *count as u64 >= self.at_most {
-            return Ok(None);
-        }
+        self.next_within::<M>(inp, count, self.at_least, self.at_most)
+    }
 
-        let before = inp.save();
-        match self.parser.go::<M>(inp) {
-            Ok(item) => {
-                *count += 1;
-                Ok(Some(item))
-            }
-            Err(()) => {
-                inp.rewind(before);
-                if *count >= self.at_least {
-                    Ok(None)
-                } else {
-                    Err(())
-                }
-            }
-        }
+    #[inline(always)]
+    fn size_hint<M: Mode>(&self, count: &Self::IterState<M>) -> usize {
+        self.remaining_reserve(*count, self.at_least)
     }
 }
 
-impl<'a, A, O, I, E> ConfigIterParserSealed<'a, I, O, E> for Repeated<A, O, I, E>
+impl<'a, A, O, I, E, L, U> ConfigIterParserSealed<'a, I, O, E> for Repeated<A, O, I, E, L, U>
 where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
     A: Parser<'a, I, O, E>,
+    L: CountError<'a, I, E>,
+    U: CountError<'a, I, E>,
 {
     type Config = RepeatedCfg;
 
@@ -1535,35 +2092,120 @@ where
         let at_most = cfg.at_most.map(|x| x as u64).unwrap_or(self.at_most);
         let at_least = cfg.at_least.unwrap_or(self.at_least);
 
-        if *count as u64 >= at_most {
-            return Ok(None);
+        self.next_within::<M>(inp, count, at_least, at_most)
+    }
+
+    #[inline(always)]
+    fn size_hint_cfg<M: Mode>(&self, count: &Self::IterState<M>, cfg: &Self::Config) -> usize {
+        self.remaining_reserve(*count, cfg.at_least.unwrap_or(self.at_least))
+    }
+}
+
+/// See [`Parser::repeated_until`].
+pub struct RepeatedUntil<A, T, OA, OT, I, E> {
+    pub(crate) parser: A,
+    pub(crate) terminator: T,
+    #[cfg(debug_assertions)]
+    pub(crate) location: Location<'static>,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(OA, OT, E, I)>,
+}
+
+impl<A: Copy, T: Copy, OA, OT, I, E> Copy for RepeatedUntil<A, T, OA, OT, I, E> {}
+impl<A: Clone, T: Clone, OA, OT, I, E> Clone for RepeatedUntil<A, T, OA, OT, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            terminator: self.terminator.clone(),
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
         }
+    }
+}
 
+impl<'a, I, E, A, T, OA, OT> ParserSealed<'a, I, (), E> for RepeatedUntil<A, T, OA, OT, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    T: Parser<'a, I, OT, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
+        self.make_iter::<Check>(inp)?;
+        loop {
+            let before = inp.offset();
+            match self.next::<Check>(inp, &mut ()) {
+                Ok(Some(())) => {}
+                Ok(None) => break Ok(M::bind(|| ())),
+                Err(()) => break Err(()),
+            }
+            if before == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
+                    "found RepeatedUntil combinator making no progress at {}",
+                    self.location,
+                );
+                #[cfg(not(debug_assertions))]
+                break Ok(M::bind(|| ()));
+            }
+        }
+    }
+
+    go_extra!(());
+}
+
+impl<'a, I, E, A, T, OA, OT> IterParserSealed<'a, I, OA, E> for RepeatedUntil<A, T, OA, OT, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    T: Parser<'a, I, OT, E>,
+{
+    type IterState<M: Mode> = ();
+
+    #[inline(always)]
+    fn make_iter<M: Mode>(
+        &self,
+        _inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<Emit, Self::IterState<M>> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn next<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _state: &mut Self::IterState<M>,
+    ) -> IPResult<M, OA> {
+        inp.step()?;
+        // The terminator is only looked ahead at, never consumed
         let before = inp.save();
+        let terminated = self.terminator.go::<Check>(inp).is_ok();
+        inp.rewind(before);
+        if terminated {
+            return Ok(None);
+        }
+
         match self.parser.go::<M>(inp) {
-            Ok(item) => {
-                *count += 1;
-                Ok(Some(item))
-            }
+            Ok(item) => Ok(Some(item)),
             Err(()) => {
                 inp.rewind(before);
-                if *count >= at_least {
-                    Ok(None)
-                } else {
-                    Err(())
-                }
+                Err(())
             }
         }
     }
 }
 
 /// See [`Parser::separated_by`].
-pub struct SeparatedBy<A, B, OA, OB, I, E> {
+pub struct SeparatedBy<A, B, OA, OB, I, E, L = ()> {
     pub(crate) parser: A,
     pub(crate) separator: B,
     pub(crate) at_least: usize,
     // Slightly evil: Should be `Option<usize>`, but we encode `!0` as 'no cap' because it's so large
     pub(crate) at_most: u64,
+    pub(crate) too_few: L,
     pub(crate) allow_leading: bool,
     pub(crate) allow_trailing: bool,
     #[cfg(debug_assertions)]
@@ -1572,14 +2214,15 @@ pub struct SeparatedBy<A, B, OA, OB, I, E> {
     pub(crate) phantom: EmptyPhantom<(OA, OB, E, I)>,
 }
 
-impl<A: Copy, B: Copy, OA, OB, I, E> Copy for SeparatedBy<A, B, OA, OB, I, E> {}
-impl<A: Clone, B: Clone, OA, OB, I, E> Clone for SeparatedBy<A, B, OA, OB, I, E> {
+impl<A: Copy, B: Copy, OA, OB, I, E, L: Copy> Copy for SeparatedBy<A, B, OA, OB, I, E, L> {}
+impl<A: Clone, B: Clone, OA, OB, I, E, L: Clone> Clone for SeparatedBy<A, B, OA, OB, I, E, L> {
     fn clone(&self) -> Self {
         Self {
             parser: self.parser.clone(),
             separator: self.separator.clone(),
             at_least: self.at_least,
             at_most: self.at_most,
+            too_few: self.too_few.clone(),
             allow_leading: self.allow_leading,
             allow_trailing: self.allow_trailing,
             #[cfg(debug_assertions)]
@@ -1589,7 +2232,7 @@ impl<A: Clone, B: Clone, OA, OB, I, E> Clone for SeparatedBy<A, B, OA, OB, I, E>
     }
 }
 
-impl<'a, A, B, OA, OB, I, E> SeparatedBy<A, B, OA, OB, I, E>
+impl<'a, A, B, OA, OB, I, E, L> SeparatedBy<A, B, OA, OB, I, E, L>
 where
     A: Parser<'a, I, OA, E>,
     B: Parser<'a, I, OB, E>,
@@ -1613,6 +2256,47 @@ where
         Self { at_least, ..self }
     }
 
+    /// Require that the pattern appear at least a minimum number of times, using `too_few` to build the error
+    /// produced when it does not.
+    ///
+    /// `too_few` is given the span at which another separator or element was required. Combined with
+    /// [`SeparatedBy::at_most`], this is useful for grammars that expect an exact number of elements, such as
+    /// `between x and y`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let bounds = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+    ///     .separated_by(text::ascii::keyword("and").padded())
+    ///     .at_least_with(2, |span| Rich::custom(span, "expected two values separated by `and`"))
+    ///     .at_most(2)
+    ///     .collect_exactly::<[_; 2]>();
+    ///
+    /// assert_eq!(bounds.parse("x and y").into_result(), Ok(["x", "y"]));
+    /// assert_eq!(
+    ///     bounds.parse("x").into_result(),
+    ///     Err(vec![Rich::custom((1..1).into(), "expected two values separated by `and`")]),
+    /// );
+    /// ```
+    pub fn at_least_with<F>(self, at_least: usize, too_few: F) -> SeparatedBy<A, B, OA, OB, I, E, F>
+    where
+        F: Fn(I::Span) -> E::Error,
+    {
+        SeparatedBy {
+            parser: self.parser,
+            separator: self.separator,
+            at_least,
+            at_most: self.at_most,
+            too_few,
+            allow_leading: self.allow_leading,
+            allow_trailing: self.allow_trailing,
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Require that the pattern appear at most a maximum number of times.
     ///
     /// ```
@@ -1720,20 +2404,429 @@ where
     /// ```
     pub fn allow_trailing(self) -> Self {
         Self {
-            allow_trailing: true,
-            ..self
+            allow_trailing: true,
+            ..self
+        }
+    }
+
+    /// Allow items to be missing between separators, producing [`None`] in their place.
+    ///
+    /// This is useful for formats with optional fields, such as CSV. A separator at the start or end of the input is
+    /// treated as being next to a missing item, and an input without any items or separators is parsed as a single
+    /// missing item.
+    ///
+    /// The output type of this iterable parser is `Option<OA>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let fields = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .separated_by(just(','))
+    ///     .allow_empty()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(fields.parse("a,,c").into_result(), Ok(vec![Some("a"), None, Some("c")]));
+    /// assert_eq!(fields.parse(",b").into_result(), Ok(vec![None, Some("b")]));
+    /// assert_eq!(fields.parse("a,").into_result(), Ok(vec![Some("a"), None]));
+    /// ```
+    pub fn allow_empty(self) -> SeparatedBy<OrNot<A>, B, Option<OA>, OB, I, E, L> {
+        SeparatedBy {
+            parser: OrNot {
+                parser: self.parser,
+            },
+            separator: self.separator,
+            at_least: self.at_least,
+            at_most: self.at_most,
+            too_few: self.too_few,
+            allow_leading: self.allow_leading,
+            allow_trailing: self.allow_trailing,
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Allow the separator before the final element to be a different pattern, as in `a, b, and c`.
+    ///
+    /// Between each pair of elements, `last_separator` is tried before the usual separator, and the list ends with the
+    /// element that follows it. If that element cannot be parsed, the usual separator is tried instead. The usual
+    /// separator may still appear before the final element, so `a, b, c` is also accepted.
+    ///
+    /// Other configuration, such as [`SeparatedBy::at_least`], should be applied before calling this method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let names = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .separated_by(just(", "))
+    ///     .last_separator(just(", and ").or(just(" and ")))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(names.parse("a, b, and c").into_result(), Ok(vec!["a", "b", "c"]));
+    /// assert_eq!(names.parse("a and b").into_result(), Ok(vec!["a", "b"]));
+    /// assert_eq!(names.parse("a, b, c").into_result(), Ok(vec!["a", "b", "c"]));
+    /// // Nothing may follow the final element
+    /// assert!(names.parse("a and b, c").has_errors());
+    /// ```
+    pub fn last_separator<C, OC>(
+        self,
+        last_separator: C,
+    ) -> SeparatedByLast<A, B, C, OA, OB, OC, I, E, L>
+    where
+        C: Parser<'a, I, OC, E>,
+    {
+        SeparatedByLast {
+            inner: self,
+            last_separator,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Collect the elements into a [`Vec`], each paired with the span of the input it was parsed from.
+    ///
+    /// This is useful when later validation needs to point at specific elements, such as when reporting duplicate
+    /// entries in a list literal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let items = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .separated_by(just(", "))
+    ///     .collect_vec_with_spans();
+    ///
+    /// assert_eq!(
+    ///     items.parse("x, yz").into_result(),
+    ///     Ok(vec![("x", SimpleSpan::from(0..1)), ("yz", SimpleSpan::from(3..5))]),
+    /// );
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn collect_vec_with_spans(
+        self,
+    ) -> Collect<
+        SeparatedBy<
+            MapWith<A, OA, fn(OA, &mut MapExtra<'a, '_, I, E>) -> (OA, I::Span)>,
+            B,
+            (OA, I::Span),
+            OB,
+            I,
+            E,
+            L,
+        >,
+        (OA, I::Span),
+        Vec<(OA, I::Span)>,
+    >
+    where
+        L: CountError<'a, I, E>,
+    {
+        SeparatedBy {
+            parser: MapWith {
+                parser: self.parser,
+                mapper: (|out, e| (out, e.span())) as fn(_, &mut MapExtra<'a, '_, I, E>) -> _,
+                phantom: EmptyPhantom::new(),
+            },
+            separator: self.separator,
+            at_least: self.at_least,
+            at_most: self.at_most,
+            too_few: self.too_few,
+            allow_leading: self.allow_leading,
+            allow_trailing: self.allow_trailing,
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+        .collect()
+    }
+
+    /// Collect the elements into a [`Vec`], along with whether a trailing separator followed the last of them.
+    ///
+    /// This is only useful together with [`SeparatedBy::allow_trailing`], and allows code that needs to preserve the
+    /// original formatting of the input (such as a code formatter) to tell `(a, b)` and `(a, b,)` apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let items = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .allow_trailing()
+    ///     .collect_with_trailing()
+    ///     .delimited_by(just('('), just(')'));
+    ///
+    /// assert_eq!(items.parse("(a, b)").into_result(), Ok((vec!["a", "b"], false)));
+    /// assert_eq!(items.parse("(a, b,)").into_result(), Ok((vec!["a", "b"], true)));
+    /// ```
+    pub fn collect_with_trailing(self) -> CollectWithTrailing<A, B, OA, OB, I, E, L>
+    where
+        L: CountError<'a, I, E>,
+    {
+        CollectWithTrailing { inner: self }
+    }
+}
+
+impl<'a, I, E, A, B, OA, OB, L> IterParserSealed<'a, I, OA, E>
+    for SeparatedBy<A, B, OA, OB, I, E, L>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+    L: CountError<'a, I, E>,
+{
+    type IterState<M: Mode> = usize
+    where
+        I: 'a;
+
+    #[inline(always)]
+    fn make_iter<M: Mode>(
+        &self,
+        _inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<Emit, Self::IterState<M>> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn next<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        state: &mut Self::IterState<M>,
+    ) -> IPResult<M, OA> {
+        if *state as u64 >= self.at_most {
+            return Ok(None);
+        }
+
+        inp.step()?;
+        // A custom error replaces the errors of a required instance, so set aside errors from earlier attempts that
+        // it must not replace
+        let custom = L::CUSTOM && *state < self.at_least;
+        let alt = if custom { inp.errors.alt.take() } else { None };
+        let res = self.next_within::<M>(inp, state, custom);
+        if let Some(alt) = alt {
+            inp.add_alt_err(alt.pos, alt.err);
+        }
+        res
+    }
+}
+
+impl<'a, I, E, A, B, OA, OB, L> SeparatedBy<A, B, OA, OB, I, E, L>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+    L: CountError<'a, I, E>,
+{
+    #[inline(always)]
+    fn next_within<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        state: &mut usize,
+        custom: bool,
+    ) -> IPResult<M, OA> {
+        let before_separator = inp.save();
+        if *state == 0 && self.allow_leading {
+            if self.separator.go::<Check>(inp).is_err() {
+                inp.rewind(before_separator);
+            }
+        } else if *state > 0 {
+            match self.separator.go::<Check>(inp) {
+                Ok(()) => {
+                    // Do nothing
+                }
+                Err(()) if *state < self.at_least => {
+                    let missing = custom && inp.offset() == before_separator.offset();
+                    inp.rewind(before_separator);
+                    if missing {
+                        self.too_few(inp, before_separator);
+                    }
+                    return Err(());
+                }
+                Err(()) => {
+                    inp.rewind(before_separator);
+                    return Ok(None);
+                }
+            }
+        }
+
+        let before_item = inp.save();
+        match self.parser.go::<M>(inp) {
+            Ok(item) => {
+                *state += 1;
+                Ok(Some(item))
+            }
+            Err(()) if *state < self.at_least => {
+                // We have errored before we have reached the count,
+                // and therefore should return this error, as we are
+                // still expecting items
+                let missing = custom && inp.offset() == before_item.offset();
+                inp.rewind(before_separator);
+                if missing {
+                    self.too_few(inp, before_separator);
+                }
+                Err(())
+            }
+            Err(()) => {
+                // We are not expecting any more items, so it is okay
+                // for it to fail.
+
+                // though if we don't allow trailing, we shouldn't have
+                // consumed the separator, so we need to rewind it.
+                if self.allow_trailing {
+                    inp.rewind(before_item);
+                } else {
+                    inp.rewind(before_separator);
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    // Replace the errors of a missing separator or element, which failed without consuming input, with the custom
+    // error for too few elements
+    fn too_few<'parse>(
+        &self,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        before: input::Marker<'a, 'parse, I>,
+    ) {
+        inp.errors.alt = None;
+        if let Some(err) = self.too_few.make_error(inp.span_since(before.offset())) {
+            inp.add_alt_err(before.offset, err);
+        }
+    }
+}
+
+impl<'a, I, E, A, B, OA, OB, L> ParserSealed<'a, I, (), E> for SeparatedBy<A, B, OA, OB, I, E, L>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+    L: CountError<'a, I, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
+        let mut state = self.make_iter::<Check>(inp)?;
+        loop {
+            let before = inp.offset();
+            match self.next::<Check>(inp, &mut state) {
+                Ok(Some(())) => {}
+                Ok(None) => break Ok(M::bind(|| ())),
+                // TODO: Technically we should be rewinding here: as-is, this is invalid since errorring parsers
+                // are permitted to leave input state unspecified. Really, unwinding should occur *here* and not in
+                // `next`.
+                Err(()) => break Err(()),
+            }
+            if before == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
+                    "found SeparatedBy combinator making no progress at {}",
+                    self.location,
+                );
+                #[cfg(not(debug_assertions))]
+                break Ok(M::bind(|| ()));
+            }
+        }
+    }
+
+    go_extra!(());
+}
+
+/// See [`SeparatedBy::collect_with_trailing`].
+pub struct CollectWithTrailing<A, B, OA, OB, I, E, L = ()> {
+    pub(crate) inner: SeparatedBy<A, B, OA, OB, I, E, L>,
+}
+
+impl<A: Copy, B: Copy, OA, OB, I, E, L: Copy> Copy for CollectWithTrailing<A, B, OA, OB, I, E, L> {}
+impl<A: Clone, B: Clone, OA, OB, I, E, L: Clone> Clone
+    for CollectWithTrailing<A, B, OA, OB, I, E, L>
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, I, E, A, B, OA, OB, L> ParserSealed<'a, I, (Vec<OA>, bool), E>
+    for CollectWithTrailing<A, B, OA, OB, I, E, L>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+    L: CountError<'a, I, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, (Vec<OA>, bool)> {
+        let mut output = M::bind::<Vec<OA>, _>(Vec::new);
+        let mut state = self.inner.make_iter::<M>(inp)?;
+        loop {
+            let before = inp.offset();
+            match self.inner.next::<M>(inp, &mut state) {
+                Ok(Some(item)) => {
+                    M::combine_mut(&mut output, item, |output, item| output.push(item))
+                }
+                Ok(None) => {
+                    // The only thing that can be consumed by the final attempt is a trailing separator
+                    let trailing = state > 0 && before != inp.offset();
+                    break Ok(M::map(output, |output| (output, trailing)));
+                }
+                Err(()) => break Err(()),
+            }
+            if state > 1 && before == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
+                    "found SeparatedBy combinator making no progress at {}",
+                    self.inner.location,
+                );
+                #[cfg(not(debug_assertions))]
+                break Ok(M::map(output, |output| (output, false)));
+            }
+        }
+    }
+
+    go_extra!((Vec<OA>, bool));
+}
+
+/// See [`SeparatedBy::last_separator`].
+pub struct SeparatedByLast<A, B, C, OA, OB, OC, I, E, L = ()> {
+    pub(crate) inner: SeparatedBy<A, B, OA, OB, I, E, L>,
+    pub(crate) last_separator: C,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OC>,
+}
+
+impl<A: Copy, B: Copy, C: Copy, OA, OB, OC, I, E, L: Copy> Copy
+    for SeparatedByLast<A, B, C, OA, OB, OC, I, E, L>
+{
+}
+impl<A: Clone, B: Clone, C: Clone, OA, OB, OC, I, E, L: Clone> Clone
+    for SeparatedByLast<A, B, C, OA, OB, OC, I, E, L>
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            last_separator: self.last_separator.clone(),
+            phantom: EmptyPhantom::new(),
         }
     }
 }
 
-impl<'a, I, E, A, B, OA, OB> IterParserSealed<'a, I, OA, E> for SeparatedBy<A, B, OA, OB, I, E>
+impl<'a, I, E, A, B, C, OA, OB, OC, L> IterParserSealed<'a, I, OA, E>
+    for SeparatedByLast<A, B, C, OA, OB, OC, I, E, L>
 where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
     A: Parser<'a, I, OA, E>,
     B: Parser<'a, I, OB, E>,
+    C: Parser<'a, I, OC, E>,
+    L: CountError<'a, I, E>,
 {
-    type IterState<M: Mode> = usize
+    // The number of elements so far, and whether the last separator has been seen
+    type IterState<M: Mode> = (usize, bool)
     where
         I: 'a;
 
@@ -1742,103 +2835,130 @@ where
         &self,
         _inp: &mut InputRef<'a, '_, I, E>,
     ) -> PResult<Emit, Self::IterState<M>> {
-        Ok(0)
+        Ok((0, false))
     }
 
     #[inline(always)]
     fn next<M: Mode>(
         &self,
         inp: &mut InputRef<'a, '_, I, E>,
-        state: &mut Self::IterState<M>,
+        (count, finished): &mut Self::IterState<M>,
     ) -> IPResult<M, OA> {
-        if *state as u64 >= self.at_most {
+        if *finished {
             return Ok(None);
         }
 
-        let before_separator = inp.save();
-        if *state == 0 && self.allow_leading {
-            if self.separator.go::<Check>(inp).is_err() {
-                inp.rewind(before_separator);
-            }
-        } else if *state > 0 {
-            match self.separator.go::<Check>(inp) {
-                Ok(()) => {
-                    // Do nothing
-                }
-                Err(()) if *state < self.at_least => {
-                    inp.rewind(before_separator);
-                    return Err(());
-                }
-                Err(()) => {
-                    inp.rewind(before_separator);
-                    return Ok(None);
+        // The last separator must be followed by an element that brings the count up to the minimum
+        if *count > 0 && (*count as u64) < self.inner.at_most && *count + 1 >= self.inner.at_least {
+            let before_separator = inp.save();
+            if self.last_separator.go::<Check>(inp).is_ok() {
+                if let Ok(item) = self.inner.parser.go::<M>(inp) {
+                    *count += 1;
+                    *finished = true;
+                    return Ok(Some(item));
                 }
             }
+            inp.rewind(before_separator);
         }
 
-        let before_item = inp.save();
-        match self.parser.go::<M>(inp) {
-            Ok(item) => {
-                *state += 1;
-                Ok(Some(item))
-            }
-            Err(()) if *state < self.at_least => {
-                // We have errored before we have reached the count,
-                // and therefore should return this error, as we are
-                // still expecting items
-                inp.rewind(before_separator);
-                Err(())
-            }
-            Err(()) => {
-                // We are not expecting any more items, so it is okay
-                // for it to fail.
-
-                // though if we don't allow trailing, we shouldn't have
-                // consumed the separator, so we need to rewind it.
-                if self.allow_trailing {
-                    inp.rewind(before_item);
-                } else {
-                    inp.rewind(before_separator);
-                }
-                Ok(None)
-            }
-        }
+        self.inner.next::<M>(inp, count)
     }
 }
 
-impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, (), E> for SeparatedBy<A, B, OA, OB, I, E>
+impl<'a, I, E, A, B, C, OA, OB, OC, L> ParserSealed<'a, I, (), E>
+    for SeparatedByLast<A, B, C, OA, OB, OC, I, E, L>
 where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
     A: Parser<'a, I, OA, E>,
     B: Parser<'a, I, OB, E>,
+    C: Parser<'a, I, OC, E>,
+    L: CountError<'a, I, E>,
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
         let mut state = self.make_iter::<Check>(inp)?;
         loop {
-            #[cfg(debug_assertions)]
             let before = inp.offset();
             match self.next::<Check>(inp, &mut state) {
                 Ok(Some(())) => {}
                 Ok(None) => break Ok(M::bind(|| ())),
-                // TODO: Technically we should be rewinding here: as-is, this is invalid since errorring parsers
-                // are permitted to leave input state unspecified. Really, unwinding should occur *here* and not in
-                // `next`.
                 Err(()) => break Err(()),
             }
-            #[cfg(debug_assertions)]
-            debug_assert!(
-                before != inp.offset(),
-                "found SeparatedBy combinator making no progress at {}",
-                self.location,
-            );
+            if before == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
+                    "found SeparatedBy combinator making no progress at {}",
+                    self.inner.location,
+                );
+                #[cfg(not(debug_assertions))]
+                break Ok(M::bind(|| ()));
+            }
         }
     }
 
     go_extra!(());
 }
 
+/// See [`Parser::infix_flat`].
+#[derive(Copy, Clone)]
+pub struct InfixFlat<A, B> {
+    pub(crate) parser: A,
+    pub(crate) operator: B,
+    #[cfg(debug_assertions)]
+    pub(crate) location: Location<'static>,
+}
+
+impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, (Vec<OA>, Vec<OB>), E> for InfixFlat<A, B>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, (Vec<OA>, Vec<OB>)> {
+        let mut operands = M::map(self.parser.go::<M>(inp)?, |operand| vec![operand]);
+        let mut operators = M::bind(Vec::new);
+        loop {
+            let before = inp.save();
+            let (operator, operand) = match self.operator.go::<M>(inp) {
+                Ok(operator) => match self.parser.go::<M>(inp) {
+                    Ok(operand) => (operator, operand),
+                    Err(()) => {
+                        inp.rewind(before);
+                        break;
+                    }
+                },
+                Err(()) => {
+                    inp.rewind(before);
+                    break;
+                }
+            };
+            M::combine_mut(&mut operators, operator, |operators, operator| {
+                operators.push(operator)
+            });
+            M::combine_mut(&mut operands, operand, |operands, operand| {
+                operands.push(operand)
+            });
+            if before.offset() == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
+                    "found InfixFlat combinator making no progress at {}",
+                    self.location,
+                );
+                #[cfg(not(debug_assertions))]
+                break;
+            }
+        }
+        Ok(M::combine(operands, operators, |operands, operators| {
+            (operands, operators)
+        }))
+    }
+
+    go_extra!((Vec<OA>, Vec<OB>));
+}
+
 /// See [`IterParser::enumerate`].
 pub struct Enumerate<A, O> {
     pub(crate) parser: A,
@@ -1887,6 +3007,11 @@ where
         state.0 += 1;
         Ok(out)
     }
+
+    #[inline(always)]
+    fn size_hint<M: Mode>(&self, state: &Self::IterState<M>) -> usize {
+        self.parser.size_hint(&state.1)
+    }
 }
 
 /// See [`IterParser::collect`].
@@ -1919,12 +3044,10 @@ where
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, C> {
-        let mut output = M::bind::<C, _>(|| C::default());
         let mut iter_state = self.parser.make_iter::<M>(inp)?;
-        #[cfg(debug_assertions)]
-        let mut i = 0;
+        let mut output = M::bind::<C, _>(|| C::with_capacity(self.parser.size_hint(&iter_state)));
+        let mut first = true;
         loop {
-            #[cfg(debug_assertions)]
             let before = inp.offset();
             match self.parser.next::<M>(inp, &mut iter_state) {
                 Ok(Some(out)) => {
@@ -1935,16 +3058,167 @@ where
             }
             // We only check after the second iteration because that's when we *must* have consumed both item
             // and separator.
-            #[cfg(debug_assertions)]
             if !A::NONCONSUMPTION_IS_OK {
-                if i >= 1 {
-                    debug_assert!(
-                        before != inp.offset(),
+                if !first && before == inp.offset() {
+                    #[cfg(debug_assertions)]
+                    panic!(
                         "found Collect combinator making no progress at {}",
                         self.location,
                     );
+                    #[cfg(not(debug_assertions))]
+                    break Ok(output);
+                }
+                first = false;
+            }
+        }
+    }
+
+    go_extra!(C);
+}
+
+/// See [`IterParser::for_each`].
+pub struct ForEach<A, O, F> {
+    pub(crate) parser: A,
+    pub(crate) f: F,
+    #[cfg(debug_assertions)]
+    pub(crate) location: Location<'static>,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<O>,
+}
+
+impl<A: Copy, O, F: Copy> Copy for ForEach<A, O, F> {}
+impl<A: Clone, O, F: Clone> Clone for ForEach<A, O, F> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            f: self.f.clone(),
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, O, E, A, F> ParserSealed<'a, I, (), E> for ForEach<A, O, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: IterParser<'a, I, O, E>,
+    F: Fn(O),
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
+        // Items are always generated, even when our own output isn't needed, since `f` is called for its side effects
+        let mut iter_state = self.parser.make_iter::<Emit>(inp)?;
+        let mut first = true;
+        loop {
+            let before = inp.offset();
+            match self.parser.next::<Emit>(inp, &mut iter_state) {
+                Ok(Some(out)) => (self.f)(out),
+                Ok(None) => break Ok(M::bind(|| ())),
+                Err(()) => break Err(()),
+            }
+            if !A::NONCONSUMPTION_IS_OK {
+                if !first && before == inp.offset() {
+                    #[cfg(debug_assertions)]
+                    panic!(
+                        "found ForEach combinator making no progress at {}",
+                        self.location,
+                    );
+                    #[cfg(not(debug_assertions))]
+                    break Ok(M::bind(|| ()));
+                }
+                first = false;
+            }
+        }
+    }
+
+    go_extra!(());
+}
+
+/// What [`IterParser::collect_map`] should do when a key appears more than once.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OnDuplicate {
+    /// Keep the first value for the key and emit an [`Error::duplicate_key`] error at the span of the repeated key.
+    /// Since the error doesn't stop parsing, the rest of the map is still parsed as normal.
+    Error,
+    /// Keep the first value for the key, ignoring any later ones.
+    KeepFirst,
+    /// Keep the last value for the key, replacing any earlier ones.
+    KeepLast,
+}
+
+/// See [`IterParser::collect_map`].
+pub struct CollectMap<A, K, V, C> {
+    pub(crate) parser: A,
+    pub(crate) on_duplicate: OnDuplicate,
+    #[cfg(debug_assertions)]
+    pub(crate) location: Location<'static>,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(K, V, C)>,
+}
+
+impl<A: Copy, K, V, C> Copy for CollectMap<A, K, V, C> {}
+impl<A: Clone, K, V, C> Clone for CollectMap<A, K, V, C> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            on_duplicate: self.on_duplicate,
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, E, A, K, V, C> ParserSealed<'a, I, C, E> for CollectMap<A, K, V, C>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: IterParser<'a, I, ((K, I::Span), V), E>,
+    C: MapContainer<K, V>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, C> {
+        // Keys are needed to detect duplicates, so we always generate the entries
+        let mut map = C::default();
+        let mut iter_state = self.parser.make_iter::<Emit>(inp)?;
+        let mut first = true;
+        loop {
+            let before = inp.offset();
+            match self.parser.next::<Emit>(inp, &mut iter_state) {
+                Ok(Some(((key, span), value))) => {
+                    if map.contains_key(&key) {
+                        match self.on_duplicate {
+                            OnDuplicate::Error if !inp.can_recover() => {
+                                inp.add_alt_err(inp.offset, E::Error::duplicate_key(span));
+                                break Err(());
+                            }
+                            OnDuplicate::Error => {
+                                inp.emit(inp.offset, E::Error::duplicate_key(span))
+                            }
+                            OnDuplicate::KeepFirst => {}
+                            OnDuplicate::KeepLast => map.insert(key, value),
+                        }
+                    } else {
+                        map.insert(key, value);
+                    }
+                }
+                Ok(None) => break Ok(M::bind(|| map)),
+                Err(()) => break Err(()),
+            }
+            // As with `Collect`, only the second iteration onwards must have consumed both item and separator
+            if !A::NONCONSUMPTION_IS_OK {
+                if !first && before == inp.offset() {
+                    #[cfg(debug_assertions)]
+                    panic!(
+                        "found CollectMap combinator making no progress at {}",
+                        self.location,
+                    );
+                    #[cfg(not(debug_assertions))]
+                    break Ok(M::bind(|| map));
                 }
-                i += 1;
+                first = false;
             }
         }
     }
@@ -2010,6 +3284,69 @@ where
     go_extra!(C);
 }
 
+/// See [`IterParser::collect_non_empty`]
+pub struct CollectNonEmpty<A, O> {
+    pub(crate) parser: A,
+    #[cfg(debug_assertions)]
+    pub(crate) location: Location<'static>,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<O>,
+}
+
+impl<A: Copy, O> Copy for CollectNonEmpty<A, O> {}
+impl<A: Clone, O> Clone for CollectNonEmpty<A, O> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, (O, Vec<O>), E> for CollectNonEmpty<A, O>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: IterParser<'a, I, O, E>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, (O, Vec<O>)> {
+        let before = inp.offset();
+        let mut iter_state = self.parser.make_iter::<M>(inp)?;
+        let head = match self.parser.next::<M>(inp, &mut iter_state)? {
+            Some(head) => head,
+            None => {
+                let found = inp.peek_maybe();
+                inp.add_alt(inp.offset, None, found, inp.span_since(before));
+                return Err(());
+            }
+        };
+        let mut tail = M::bind(Vec::new);
+        loop {
+            let item_start = inp.offset();
+            match self.parser.next::<M>(inp, &mut iter_state)? {
+                Some(out) => M::combine_mut(&mut tail, out, |tail, out| tail.push(out)),
+                None => break,
+            }
+            // The head was the first iteration, so every element of the tail must make progress (see `Collect`)
+            if !A::NONCONSUMPTION_IS_OK && item_start == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
+                    "found CollectNonEmpty combinator making no progress at {}",
+                    self.location,
+                );
+                #[cfg(not(debug_assertions))]
+                break;
+            }
+        }
+        Ok(M::combine(head, tail, |head, tail| (head, tail)))
+    }
+
+    go_extra!((O, Vec<O>));
+}
+
 /// See [`Parser::or_not`].
 #[derive(Copy, Clone)]
 pub struct OrNot<A> {
@@ -2065,17 +3402,74 @@ where
 
         let before = inp.save();
         match self.parser.go::<M>(inp) {
-            Ok(item) => {
-                *finished = true;
-                Ok(Some(item))
-            }
+            Ok(item) => {
+                *finished = true;
+                Ok(Some(item))
+            }
+            Err(()) => {
+                inp.rewind(before);
+                *finished = true;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// A value produced by [`Parser::or_value`] or [`Parser::or_default`] when the pattern is absent.
+///
+/// Any [`Clone`] value is a fallback that is cloned each time, and a `fn() -> O` is a fallback that is called each
+/// time.
+pub trait Fallback<O> {
+    #[doc(hidden)]
+    fn fallback(&self) -> O;
+}
+
+impl<O: Clone> Fallback<O> for O {
+    #[inline(always)]
+    fn fallback(&self) -> O {
+        self.clone()
+    }
+}
+
+impl<O> Fallback<O> for fn() -> O {
+    #[inline(always)]
+    fn fallback(&self) -> O {
+        self()
+    }
+}
+
+/// See [`Parser::or_value`].
+#[derive(Copy, Clone)]
+pub struct OrValue<A, F> {
+    pub(crate) parser: A,
+    pub(crate) fallback: F,
+}
+
+/// See [`Parser::or_default`].
+pub type OrDefault<A, O> = OrValue<A, fn() -> O>;
+
+impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for OrValue<A, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Fallback<O>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        match self.parser.go::<M>(inp) {
+            Ok(out) => Ok(out),
+            // Committed choice: only fall back if the pattern failed without consuming anything
+            Err(()) if inp.offset() != before.offset() => Err(()),
             Err(()) => {
                 inp.rewind(before);
-                *finished = true;
-                Ok(None)
+                Ok(M::bind(|| self.fallback.fallback()))
             }
         }
     }
+
+    go_extra!(O);
 }
 
 /// See [`Parser::not`].
@@ -2117,6 +3511,7 @@ where
             Ok(()) => {
                 let (at, found) = inp.next_inner();
                 inp.add_alt(at, None, found.map(|f| f.into()), result_span);
+                inp.rewind(before);
                 Err(())
             }
             Err(()) => Ok(M::bind(|| ())),
@@ -2302,7 +3697,6 @@ where
         let mut a_out = M::bind(|| Vec::new());
         let mut iter_state = self.parser_a.make_iter::<M>(inp)?;
         loop {
-            #[cfg(debug_assertions)]
             let before = inp.offset();
             match self.parser_a.next::<M>(inp, &mut iter_state) {
                 Ok(Some(out)) => {
@@ -2311,13 +3705,14 @@ where
                 Ok(None) => break,
                 Err(()) => return Err(()),
             }
-            #[cfg(debug_assertions)]
-            if !A::NONCONSUMPTION_IS_OK {
-                debug_assert!(
-                    before != inp.offset(),
+            if !A::NONCONSUMPTION_IS_OK && before == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
                     "found Foldr combinator making no progress at {}",
                     self.location,
                 );
+                #[cfg(not(debug_assertions))]
+                break;
             }
         }
 
@@ -2380,13 +3775,14 @@ where
                 Ok(None) => break,
                 Err(()) => return Err(()),
             }
-            #[cfg(debug_assertions)]
-            if !A::NONCONSUMPTION_IS_OK {
-                debug_assert!(
-                    before != inp.offset(),
+            if !A::NONCONSUMPTION_IS_OK && before == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
                     "found FoldrWithState combinator making no progress at {}",
                     self.location,
                 );
+                #[cfg(not(debug_assertions))]
+                break;
             }
         }
 
@@ -2443,7 +3839,6 @@ where
         let mut out = self.parser_a.go::<M>(inp)?;
         let mut iter_state = self.parser_b.make_iter::<M>(inp)?;
         loop {
-            #[cfg(debug_assertions)]
             let before = inp.offset();
             match self.parser_b.next::<M>(inp, &mut iter_state) {
                 Ok(Some(b_out)) => {
@@ -2452,13 +3847,14 @@ where
                 Ok(None) => break Ok(out),
                 Err(()) => break Err(()),
             }
-            #[cfg(debug_assertions)]
-            if !B::NONCONSUMPTION_IS_OK {
-                debug_assert!(
-                    before != inp.offset(),
+            if !B::NONCONSUMPTION_IS_OK && before == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
                     "found Foldl combinator making no progress at {}",
                     self.location,
                 );
+                #[cfg(not(debug_assertions))]
+                break Ok(out);
             }
         }
     }
@@ -2508,7 +3904,6 @@ where
         let mut out = self.parser_a.go::<M>(inp)?;
         let mut iter_state = self.parser_b.make_iter::<M>(inp)?;
         loop {
-            #[cfg(debug_assertions)]
             let before = inp.offset();
             match self.parser_b.next::<M>(inp, &mut iter_state) {
                 Ok(Some(b_out)) => {
@@ -2519,13 +3914,100 @@ where
                 Ok(None) => break Ok(out),
                 Err(()) => break Err(()),
             }
-            #[cfg(debug_assertions)]
-            if !B::NONCONSUMPTION_IS_OK {
-                debug_assert!(
-                    before != inp.offset(),
+            if !B::NONCONSUMPTION_IS_OK && before == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
                     "found FoldlWithState combinator making no progress at {}",
                     self.location,
                 );
+                #[cfg(not(debug_assertions))]
+                break Ok(out);
+            }
+        }
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::foldl_with_span`].
+pub struct FoldlWithSpan<F, A, Op, B, OOp, OB, E> {
+    pub(crate) parser_a: A,
+    pub(crate) operator: Op,
+    pub(crate) parser_b: B,
+    pub(crate) folder: F,
+    #[cfg(debug_assertions)]
+    pub(crate) location: Location<'static>,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(OOp, OB, E)>,
+}
+
+impl<F: Copy, A: Copy, Op: Copy, B: Copy, OOp, OB, E> Copy
+    for FoldlWithSpan<F, A, Op, B, OOp, OB, E>
+{
+}
+impl<F: Clone, A: Clone, Op: Clone, B: Clone, OOp, OB, E> Clone
+    for FoldlWithSpan<F, A, Op, B, OOp, OB, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            parser_a: self.parser_a.clone(),
+            operator: self.operator.clone(),
+            parser_b: self.parser_b.clone(),
+            folder: self.folder.clone(),
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, F, A, Op, B, O, OOp, OB, E> ParserSealed<'a, I, O, E>
+    for FoldlWithSpan<F, A, Op, B, OOp, OB, E>
+where
+    I: Input<'a>,
+    A: Parser<'a, I, O, E>,
+    Op: Parser<'a, I, OOp, E>,
+    B: Parser<'a, I, OB, E>,
+    E: ParserExtra<'a, I>,
+    F: Fn(O, OOp, I::Span, OB) -> O,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O>
+    where
+        Self: Sized,
+    {
+        let mut out = self.parser_a.go::<M>(inp)?;
+        loop {
+            let before = inp.save();
+            let op = match self.operator.go::<M>(inp) {
+                Ok(op) => op,
+                Err(()) => {
+                    inp.rewind(before);
+                    break Ok(out);
+                }
+            };
+            let op_span = inp.span_since(before.offset());
+            let b_out = match self.parser_b.go::<M>(inp) {
+                Ok(b_out) => b_out,
+                Err(()) => {
+                    // The operator and its operand are parsed as a pair, so backtrack to before the operator
+                    inp.rewind(before);
+                    break Ok(out);
+                }
+            };
+            out = M::combine(
+                out,
+                M::combine(op, b_out, |op, b| (op, b)),
+                |out, (op, b)| (self.folder)(out, op, op_span, b),
+            );
+            if before.offset() == inp.offset() {
+                #[cfg(debug_assertions)]
+                panic!(
+                    "found FoldlWithSpan combinator making no progress at {}",
+                    self.location,
+                );
+                #[cfg(not(debug_assertions))]
+                break Ok(out);
             }
         }
     }
@@ -2561,6 +4043,107 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::observe`].
+#[derive(Copy, Clone)]
+pub struct Observe<A, F> {
+    pub(crate) parser: A,
+    pub(crate) observer: F,
+}
+
+impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for Observe<A, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Fn(usize, bool),
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.offset().offset.into();
+        let res = self.parser.go::<M>(inp);
+        let after = inp.offset().offset.into();
+        (self.observer)(after.saturating_sub(before), res.is_ok());
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::with_max_depth`].
+#[derive(Copy, Clone)]
+pub struct WithMaxDepth<A> {
+    pub(crate) parser: A,
+    pub(crate) max_depth: usize,
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for WithMaxDepth<A>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let old_max = inp.depth.max;
+        let max = inp.depth.current.saturating_add(self.max_depth);
+        // An enclosing limit still applies if it is tighter than this one
+        inp.depth.max = Some(old_max.map_or(max, |old_max| old_max.min(max)));
+        let res = self.parser.go::<M>(inp);
+        inp.depth.max = old_max;
+        if res.is_err() {
+            // Other parsers may have failed at the same position as the one that reached the limit, but the limit is
+            // the actual reason that parsing failed there
+            if let (Some(alt), Some(at)) = (&inp.errors.alt, inp.depth.exceeded) {
+                if alt.pos.into() == at.into() {
+                    let before = inp.save();
+                    inp.offset = at;
+                    let err = inp.nesting_too_deep_err();
+                    inp.rewind(before);
+                    inp.errors.alt = Some(Located::at(at, err));
+                }
+            }
+        }
+        res
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::debug`].
+#[derive(Copy, Clone)]
+pub struct DebugParser<A, F> {
+    pub(crate) parser: A,
+    pub(crate) label: &'static str,
+    pub(crate) sink: F,
+}
+
+impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for DebugParser<A, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Fn(fmt::Arguments),
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before: usize = inp.offset.into();
+        (self.sink)(format_args!("{}: entered at {}", self.label, before));
+        let res = self.parser.go::<M>(inp);
+        match res {
+            Ok(_) => (self.sink)(format_args!(
+                "{}: succeeded, consuming {}..{}",
+                self.label,
+                before,
+                inp.offset.into(),
+            )),
+            Err(()) => (self.sink)(format_args!("{}: failed at {}", self.label, before)),
+        }
+        res
+    }
+
+    go_extra!(O);
+}
+
 /// See [`Parser::map_err`].
 #[derive(Copy, Clone)]
 pub struct MapErr<A, F> {
@@ -2594,6 +4177,43 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::flat_map_err`].
+#[derive(Copy, Clone)]
+pub struct FlatMapErr<A, F> {
+    pub(crate) parser: A,
+    pub(crate) mapper: F,
+}
+
+impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for FlatMapErr<A, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    F: Fn(E::Error) -> (E::Error, Vec<E::Error>),
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let res = self.parser.go::<M>(inp);
+
+        if res.is_err() {
+            let mut e = inp.errors.alt.take().expect("error but no alt?");
+            let (err, others) = (self.mapper)(e.err);
+            e.err = err;
+            for err in others {
+                if !inp.can_recover() {
+                    break;
+                }
+                inp.emit(e.pos, err);
+            }
+            inp.errors.alt = Some(e);
+        }
+
+        res
+    }
+
+    go_extra!(O);
+}
+
 // /// See [`Parser::map_err_with_span`].
 // #[derive(Copy, Clone)]
 // pub struct MapErrWithSpan<A, F> {
@@ -2757,6 +4377,137 @@ where
 mod tests {
     use crate::prelude::*;
 
+    #[test]
+    fn repeated_at_least_with() {
+        let parser = just::<_, _, extra::Err<Rich<char>>>('x')
+            .repeated()
+            .at_least_with(1, |span| Rich::custom(span, "expected at least one x"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(parser.parse("xx").into_result(), Ok(vec!['x', 'x']));
+        assert_eq!(
+            parser.parse("").into_result(),
+            Err(vec![Rich::custom((0..0).into(), "expected at least one x")]),
+        );
+        assert_eq!(
+            parser.check("").into_result(),
+            Err(vec![Rich::custom((0..0).into(), "expected at least one x")]),
+        );
+
+        // The minimum can still be changed at parse time
+        let parser = just::<_, _, extra::Err<Rich<char>>>('x')
+            .repeated()
+            .at_least_with(1, |span| Rich::custom(span, "too few"))
+            .configure(|cfg, _| cfg.at_least(3))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parser.parse("xx").into_result(),
+            Err(vec![Rich::custom((2..2).into(), "too few")]),
+        );
+
+        // An element that fails after consuming input keeps its own error
+        let parser = just::<_, _, extra::Err<Rich<char>>>('a')
+            .then(just('b'))
+            .repeated()
+            .at_least_with(1, |span| Rich::custom(span, "too few"))
+            .collect::<Vec<_>>();
+        let errs = parser.parse("ac").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(1, 2));
+        assert_eq!(errs[0].found(), Some(&'c'));
+
+        // ...as does an earlier attempt that got further
+        let parser = just::<_, _, extra::Err<Rich<char>>>("xzzq")
+            .ignored()
+            .or(just('x').ignored())
+            .or_rewinding()
+            .then(
+                just('y')
+                    .repeated()
+                    .at_least_with(1, |span| Rich::custom(span, "too few")),
+            );
+        let errs = parser.parse("xzzw").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 4));
+        assert_eq!(errs[0].found(), Some(&'w'));
+    }
+
+    #[test]
+    fn repeated_at_most_with() {
+        let parser = just::<_, _, extra::Err<Rich<char>>>('x')
+            .repeated()
+            .at_least_with(1, |span| Rich::custom(span, "too few"))
+            .at_most_with(2, |span| Rich::custom(span, "too many"))
+            .collect::<Vec<_>>()
+            .then_ignore(just('y'));
+
+        assert_eq!(parser.parse("xxy").into_result(), Ok(vec!['x', 'x']));
+        assert_eq!(
+            parser.parse("y").into_result(),
+            Err(vec![Rich::custom((0..0).into(), "too few")]),
+        );
+        assert_eq!(
+            parser.parse("xxxxy").into_result(),
+            Err(vec![Rich::custom((2..4).into(), "too many")]),
+        );
+    }
+
+    #[test]
+    fn repeated_until() {
+        let stmts = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+            .then_ignore(just(';'))
+            .padded()
+            .repeated_until(just('}'))
+            .collect::<Vec<_>>();
+
+        // The terminator is not consumed
+        let rest = stmts.then(any().repeated().to_slice());
+        assert_eq!(
+            rest.parse("a; b;}c").into_result(),
+            Ok((vec!["a", "b"], "}c"))
+        );
+        // A terminator at the start yields no elements
+        assert_eq!(rest.parse("}").into_result(), Ok((vec![], "}")));
+
+        let block = just('{').ignore_then(stmts).then_ignore(just('}'));
+        assert_eq!(
+            block.parse("{ x; y; z; }").into_result(),
+            Ok(vec!["x", "y", "z"])
+        );
+        // Elements are required until the terminator is found
+        assert!(block.parse("{ x; y }").has_errors());
+        assert!(block.parse("{ x;").has_errors());
+    }
+
+    #[test]
+    fn repeated_allow_whitespace() {
+        let nums = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .from_str::<u32>()
+            .unwrapped()
+            .repeated()
+            .allow_whitespace()
+            .collect::<Vec<_>>();
+
+        assert_eq!(nums.parse("1 2 3").into_result(), Ok(vec![1, 2, 3]));
+        assert_eq!(nums.parse("  1\t2").into_result(), Ok(vec![1, 2]));
+        assert_eq!(nums.parse("").into_result(), Ok(vec![]));
+        // Trailing whitespace isn't part of the repetition
+        assert!(nums.parse("1 2 ").has_errors());
+
+        // ...so it remains available to a following token that needs it
+        let line = nums.then_ignore(just(" \n"));
+        assert_eq!(line.parse("1 2 \n").into_result(), Ok(vec![1, 2]));
+
+        // Other configuration is kept
+        let pair = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .repeated()
+            .exactly(2)
+            .allow_whitespace()
+            .collect::<Vec<_>>();
+        assert_eq!(pair.parse("1 2").into_result(), Ok(vec!["1", "2"]));
+        assert!(pair.parse("1").has_errors());
+    }
+
     #[test]
     fn separated_by_at_least() {
         let parser = just::<_, _, extra::Default>('-')
@@ -2819,6 +4570,143 @@ mod tests {
         assert!(parser.parse("-,-,").has_errors());
     }
 
+    #[test]
+    fn separated_by_collect_with_trailing() {
+        let parser = just::<_, _, extra::Default>('a')
+            .or(just('b'))
+            .separated_by(just(','))
+            .allow_leading()
+            .allow_trailing()
+            .collect_with_trailing();
+
+        assert_eq!(
+            parser.parse("a,b").into_result(),
+            Ok((vec!['a', 'b'], false))
+        );
+        assert_eq!(
+            parser.parse("a,b,").into_result(),
+            Ok((vec!['a', 'b'], true))
+        );
+        assert_eq!(parser.parse(",a").into_result(), Ok((vec!['a'], false)));
+        // A lone separator is leading, not trailing
+        assert_eq!(parser.parse(",").into_result(), Ok((vec![], false)));
+        assert_eq!(parser.parse("").into_result(), Ok((vec![], false)));
+        assert!(parser.parse("a,,").has_errors());
+
+        // Without `allow_trailing`, there is never a trailing separator
+        let parser = just::<_, _, extra::Default>('a')
+            .separated_by(just(','))
+            .collect_with_trailing();
+        assert_eq!(
+            parser.parse("a,a").into_result(),
+            Ok((vec!['a', 'a'], false))
+        );
+        assert!(parser.parse("a,").has_errors());
+    }
+
+    #[test]
+    fn separated_by_at_least_with() {
+        let between = text::ascii::keyword::<_, _, _, extra::Err<Rich<char>>>("between")
+            .padded()
+            .ignore_then(
+                text::ascii::ident()
+                    .separated_by(text::ascii::keyword("and").padded())
+                    .at_least_with(2, |span| {
+                        Rich::custom(span, "expected two values separated by `and`")
+                    })
+                    .at_most(2)
+                    .collect_exactly::<[_; 2]>(),
+            );
+
+        assert_eq!(
+            between.parse("between X and Y").into_result(),
+            Ok(["X", "Y"])
+        );
+        assert_eq!(
+            between.parse("between X").into_result(),
+            Err(vec![Rich::custom(
+                (9..9).into(),
+                "expected two values separated by `and`"
+            )]),
+        );
+        // A missing element after the separator also produces the custom error
+        assert_eq!(
+            between.parse("between X and").into_result(),
+            Err(vec![Rich::custom(
+                (9..9).into(),
+                "expected two values separated by `and`"
+            )]),
+        );
+
+        // An element that fails after consuming input keeps its own error
+        let pair = just::<_, _, extra::Err<Rich<char>>>('a')
+            .then(just('b'))
+            .separated_by(just(','))
+            .at_least_with(2, |span| Rich::custom(span, "too few"))
+            .collect::<Vec<_>>();
+        let errs = pair.parse("ab,ac").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(4, 5));
+        assert_eq!(errs[0].found(), Some(&'c'));
+    }
+
+    #[test]
+    fn separated_by_last_separator() {
+        let names = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+            .separated_by(just(',').padded())
+            .last_separator(
+                just(',')
+                    .or_not()
+                    .padded()
+                    .then(text::ascii::keyword("and"))
+                    .padded(),
+            )
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names.parse("a, b, and c").into_result(),
+            Ok(vec!["a", "b", "c"])
+        );
+        assert_eq!(
+            names.parse("a, b and c").into_result(),
+            Ok(vec!["a", "b", "c"])
+        );
+        assert_eq!(
+            names.parse("a, b, c").into_result(),
+            Ok(vec!["a", "b", "c"])
+        );
+        assert_eq!(names.parse("a and b").into_result(), Ok(vec!["a", "b"]));
+        assert_eq!(names.parse("a").into_result(), Ok(vec!["a"]));
+        assert_eq!(names.parse("").into_result(), Ok(vec![]));
+        // The last separator must come last
+        assert!(names.parse("a and b, c").has_errors());
+        assert!(names.parse("a and b and c").has_errors());
+
+        // The element after the last separator must bring the count up to the minimum
+        let at_least = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+            .separated_by(just(", "))
+            .at_least(3)
+            .last_separator(just(" and "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            at_least.parse("a, b and c").into_result(),
+            Ok(vec!["a", "b", "c"])
+        );
+        assert!(at_least.parse("a and b, c").has_errors());
+
+        // ...and may not go beyond the maximum
+        let at_most = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+            .separated_by(just(", "))
+            .at_most(2)
+            .last_separator(just(" and "))
+            .collect::<Vec<_>>()
+            .then_ignore(just(" and c"));
+        assert_eq!(
+            at_most.parse("a and b and c").into_result(),
+            Ok(vec!["a", "b"])
+        );
+    }
+
     #[test]
     fn separated_by_leaves_last_separator() {
         let parser = just::<_, _, extra::Default>('-')
@@ -92,6 +92,18 @@ impl Container<char> for String {
     }
 }
 
+impl<'a> Container<&'a str> for String {
+    fn push(&mut self, item: &'a str) {
+        (*self).push_str(item)
+    }
+}
+
+impl Container<String> for String {
+    fn push(&mut self, item: String) {
+        (*self).push_str(&item)
+    }
+}
+
 impl<K: Eq + Hash, V> Container<(K, V)> for HashMap<K, V> {
     fn with_capacity(n: usize) -> Self {
         Self::with_capacity(n)
@@ -142,6 +154,43 @@ impl<T: Ord> Container<T> for alloc::collections::BTreeSet<T> {
     }
 }
 
+/// A utility trait for maps that can be constructed from a series of key-value pairs, used by
+/// [`IterParser::collect_map`].
+pub trait MapContainer<K, V>: Default {
+    /// Whether the map already has a value for the given key.
+    fn contains_key(&self, key: &K) -> bool;
+    /// Set the value for the given key, replacing any existing value.
+    fn insert(&mut self, key: K, value: V);
+}
+
+impl<K: Eq + Hash, V> MapContainer<K, V> for HashMap<K, V> {
+    fn contains_key(&self, key: &K) -> bool {
+        (*self).contains_key(key)
+    }
+    fn insert(&mut self, key: K, value: V) {
+        (*self).insert(key, value);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V> MapContainer<K, V> for std::collections::HashMap<K, V> {
+    fn contains_key(&self, key: &K) -> bool {
+        (*self).contains_key(key)
+    }
+    fn insert(&mut self, key: K, value: V) {
+        (*self).insert(key, value);
+    }
+}
+
+impl<K: Ord, V> MapContainer<K, V> for alloc::collections::BTreeMap<K, V> {
+    fn contains_key(&self, key: &K) -> bool {
+        (*self).contains_key(key)
+    }
+    fn insert(&mut self, key: K, value: V) {
+        (*self).insert(key, value);
+    }
+}
+
 /// A utility trait for types that hold a specific constant number of output values.
 ///
 /// # Safety
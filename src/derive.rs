@@ -0,0 +1,126 @@
+//! Parsers derived from type definitions.
+//!
+//! *“Then you should say what you mean,” the March Hare went on.*
+//!
+//! Many grammars mirror the shape of the syntax tree that they produce: a struct is made of its fields, parsed one
+//! after the other, and an enum is one of its variants. `#[derive(Grammar)]` writes the parser for such types for
+//! you, implementing the [`Grammar`] trait.
+//!
+//! - The fields of a struct or enum variant are parsed in order, like [`Parser::then`].
+//!
+//! - The variants of an enum are tried in order, backtracking to try the next whenever one fails, like [`choice`] or
+//!   [`Or::or_rewinding`](crate::combinator::Or::or_rewinding).
+//!
+//! - Fields of type `Self` (usually behind a [`Box`]) recurse into the parser being defined. Other fields are parsed
+//!   with their own [`Grammar`] implementation. [`Box<T>`], [`Option<T>`] and [`Vec<T>`] fields parse `T`, optionally
+//!   and repeatedly respectively.
+//!
+//! Literal text is given with the `#[grammar(...)]` attribute:
+//!
+//! - `#[grammar(token = "...")]` on a unit struct or unit variant gives the text that it is parsed from.
+//!
+//! - `#[grammar(prefix = "...")]` and `#[grammar(suffix = "...")]` on a struct, variant, or field give text that must
+//!   appear before or after it. On an [`Option`] field, they are only expected when the field is present.
+//!
+//! Literals, as well as the built-in implementations of [`Grammar`], accept whitespace around them, as if wrapped in
+//! [`Parser::padded`]. A literal that ends in an identifier character, such as `let`, must not be directly followed by
+//! another one, so it won't match the start of `letter`.
+//!
+//! This is a first cut: only `&str` inputs and non-generic types are supported, and there is no support for operator
+//! precedence. Types may refer to themselves, but not to each other in a cycle, since each derived parser creates the
+//! parsers of its fields. Expressions with infix operators can be written fully parenthesized, or parsed with a hand-written
+//! parser that uses the derived ones for everything else.
+//!
+//! # Example
+//!
+//! ```
+//! use chumsky::{prelude::*, derive::Grammar};
+//!
+//! #[derive(Grammar, Debug, PartialEq)]
+//! enum Expr {
+//!     Num(u64),
+//!     #[grammar(token = "x")]
+//!     Var,
+//!     Neg(#[grammar(prefix = "-")] Box<Expr>),
+//!     #[grammar(prefix = "(", suffix = ")")]
+//!     Add(Box<Expr>, #[grammar(prefix = "+")] Box<Expr>),
+//! }
+//!
+//! let expr = Expr::parser::<extra::Err<Rich<char>>>();
+//!
+//! assert_eq!(
+//!     expr.parse("(1 + -x)").into_result(),
+//!     Ok(Expr::Add(Box::new(Expr::Num(1)), Box::new(Expr::Neg(Box::new(Expr::Var))))),
+//! );
+//! assert!(expr.parse("1 + x").has_errors());
+//! ```
+
+use super::*;
+
+pub use chumsky_derive::Grammar;
+
+/// A type that can be parsed from a `&str`, usually implemented with `#[derive(Grammar)]`.
+///
+/// See the [module-level documentation](self) for more information.
+pub trait Grammar<'a>: Sized {
+    /// Create a parser for this type.
+    fn parser<E: ParserExtra<'a, &'a str> + 'a>() -> Boxed<'a, 'a, &'a str, Self, E>;
+}
+
+macro_rules! impl_grammar_for_unsigned {
+    ($($T:ty),*) => {$(
+        impl<'a> Grammar<'a> for $T {
+            /// Parses a decimal integer, producing an [`Error::invalid_literal`] error if it does not fit.
+            fn parser<E: ParserExtra<'a, &'a str> + 'a>() -> Boxed<'a, 'a, &'a str, Self, E> {
+                Parser::boxed(text::int(10).from_str_or_error().padded())
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_grammar_for_signed {
+    ($($T:ty),*) => {$(
+        impl<'a> Grammar<'a> for $T {
+            /// Parses a decimal integer with an optional leading `-`, producing an [`Error::invalid_literal`] error if
+            /// it does not fit.
+            fn parser<E: ParserExtra<'a, &'a str> + 'a>() -> Boxed<'a, 'a, &'a str, Self, E> {
+                Parser::boxed(
+                    just('-')
+                        .or_not()
+                        .then(text::int(10))
+                        .to_slice()
+                        .from_str_or_error()
+                        .padded(),
+                )
+            }
+        }
+    )*};
+}
+
+impl_grammar_for_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_grammar_for_signed!(i8, i16, i32, i64, i128, isize);
+
+impl<'a> Grammar<'a> for String {
+    /// Parses an ASCII identifier (see [`text::ascii::ident`]).
+    fn parser<E: ParserExtra<'a, &'a str> + 'a>() -> Boxed<'a, 'a, &'a str, Self, E> {
+        Parser::boxed(text::ascii::ident().map(String::from).padded())
+    }
+}
+
+impl<'a, T: Grammar<'a> + 'a> Grammar<'a> for Box<T> {
+    fn parser<E: ParserExtra<'a, &'a str> + 'a>() -> Boxed<'a, 'a, &'a str, Self, E> {
+        Parser::boxed(T::parser().map(Box::new))
+    }
+}
+
+impl<'a, T: Grammar<'a> + 'a> Grammar<'a> for Option<T> {
+    fn parser<E: ParserExtra<'a, &'a str> + 'a>() -> Boxed<'a, 'a, &'a str, Self, E> {
+        Parser::boxed(T::parser().or_not())
+    }
+}
+
+impl<'a, T: Grammar<'a> + 'a> Grammar<'a> for Vec<T> {
+    fn parser<E: ParserExtra<'a, &'a str> + 'a>() -> Boxed<'a, 'a, &'a str, Self, E> {
+        Parser::boxed(T::parser().repeated().collect())
+    }
+}
@@ -45,6 +45,12 @@ use alloc::string::ToString;
 ///         }
 ///     }
 ///
+///     fn add_expected<Iter: IntoIterator<Item = Option<MaybeRef<'a, char>>>>(&mut self, new: Iter) {
+///         if let Self::ExpectedFound { expected, .. } = self {
+///             expected.extend(new.into_iter().map(|e| e.as_deref().copied()));
+///         }
+///     }
+///
 ///     fn merge(mut self, mut other: Self) -> Self {
 ///         if let (Self::ExpectedFound { expected, .. }, Self::ExpectedFound { expected: expected_other, .. }) = (
 ///             &mut self,
@@ -85,6 +91,19 @@ pub trait Error<'a, I: Input<'a>>: Sized {
         self
     }
 
+    /// Add more expected inputs to this error, as produced by other patterns that failed at the same location.
+    ///
+    /// This is a hook for error types that can grow their expected inputs in place, such as in their own
+    /// [`Error::merge_expected_found`]. Chumsky's combinators never call it directly, so implementing [`Error::merge`]
+    /// is enough to combine the inputs expected by each alternative. By default, this does nothing.
+    #[inline(always)]
+    fn add_expected<E: IntoIterator<Item = Option<MaybeRef<'a, I::Token>>>>(
+        &mut self,
+        expected: E,
+    ) {
+        #![allow(unused_variables)]
+    }
+
     /// Fast path for `a.merge(Error::expected_found(...))` that may incur less overhead by, for example, reusing allocations.
     #[inline(always)]
     fn merge_expected_found<E: IntoIterator<Item = Option<MaybeRef<'a, I::Token>>>>(
@@ -106,6 +125,80 @@ pub trait Error<'a, I: Input<'a>>: Sized {
     ) -> Self {
         Self::expected_found(expected, found, span)
     }
+
+    /// Create a new error indicating that recursive parsers were nested more deeply than the limit given to
+    /// [`Parser::with_max_depth`](crate::Parser::with_max_depth).
+    ///
+    /// `found` is the input at which the limit was reached. By default, this is reported like any other unexpected
+    /// input.
+    #[inline(always)]
+    fn nesting_too_deep(found: Option<MaybeRef<'a, I::Token>>, span: I::Span) -> Self {
+        Self::expected_found(None, found, span)
+    }
+
+    /// Create a new error indicating that parsing took more steps than the budget given to
+    /// [`Parser::parse_with_budget`](crate::Parser::parse_with_budget).
+    ///
+    /// `span` is the span of the input at which the budget ran out. By default, this is reported as an error with no
+    /// expected or found input.
+    #[inline(always)]
+    fn budget_exceeded(span: I::Span) -> Self {
+        Self::expected_found(None, None, span)
+    }
+
+    /// Create a new error indicating that the input itself failed, cutting it short. For example,
+    /// [`BufReadInput`](crate::input::BufReadInput) fails when its reader produces an IO error.
+    ///
+    /// `span` is the span of the input at which the failure happened, and `reason` describes it. By default, this is
+    /// reported as an error with no expected or found input.
+    #[inline(always)]
+    fn input_failure(span: I::Span, reason: &str) -> Self {
+        #![allow(unused_variables)]
+        Self::expected_found(None, None, span)
+    }
+
+    /// Create a new error indicating that a key appeared more than once in a map being built by
+    /// [`IterParser::collect_map`](crate::IterParser::collect_map) with
+    /// [`OnDuplicate::Error`](crate::combinator::OnDuplicate::Error).
+    ///
+    /// `span` is the span of the repeated key. By default, this is reported as an error with no expected or found
+    /// input.
+    #[inline(always)]
+    fn duplicate_key(span: I::Span) -> Self {
+        Self::expected_found(None, None, span)
+    }
+
+    /// Create a new error indicating that a literal was parsed successfully but could not be converted into a value
+    /// by [`Parser::from_str_or_error`](crate::Parser::from_str_or_error), such as an integer literal that is too
+    /// large for its type.
+    ///
+    /// `reason` is the error produced by the conversion and `span` is the span of the literal. By default, this is
+    /// reported as an error with no expected or found input.
+    #[inline(always)]
+    fn invalid_literal<R: fmt::Display>(reason: R, span: I::Span) -> Self {
+        #![allow(unused_variables)]
+        Self::expected_found(None, None, span)
+    }
+
+    /// Create a new error indicating that an integer had a leading zero, as reported by
+    /// [`text::int_no_leading_zero`](crate::text::int_no_leading_zero).
+    ///
+    /// `span` is the span of the leading zero. By default, this is reported as an error with no expected or found
+    /// input.
+    #[inline(always)]
+    fn leading_zero(span: I::Span) -> Self {
+        Self::expected_found(None, None, span)
+    }
+
+    /// Create a new error indicating that a closing delimiter was missing, as reported by
+    /// [`DelimitedBy::recover_unclosed`](crate::combinator::DelimitedBy::recover_unclosed).
+    ///
+    /// `span` is the span of the opening delimiter that was left unclosed. By default, this is reported as an error
+    /// with no expected or found input.
+    #[inline(always)]
+    fn unclosed_delimiter(span: I::Span) -> Self {
+        Self::expected_found(None, None, span)
+    }
 }
 
 /// A ZST error type that tracks only whether a parse error occurred at all. This type is for when
@@ -647,6 +740,23 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
     }
 }
 
+impl<'a, T, S, L> From<Simple<'a, T, S>> for Rich<'a, T, S, L> {
+    /// Convert a [`Simple`] error into a [`Rich`] error with the same span and found token.
+    ///
+    /// [`Simple`] does not track what was expected, so the resulting error expects nothing in particular.
+    fn from(err: Simple<'a, T, S>) -> Self {
+        Rich {
+            span: err.span,
+            reason: Box::new(RichReason::ExpectedFound {
+                expected: Vec::new(),
+                found: err.found,
+            }),
+            #[cfg(feature = "label")]
+            context: Vec::new(),
+        }
+    }
+}
+
 impl<'a, I: Input<'a>, L> Error<'a, I> for Rich<'a, I::Token, I::Span, L>
 where
     I::Token: PartialEq,
@@ -694,15 +804,8 @@ where
         _span: I::Span,
     ) -> Self {
         match &mut *self.reason {
-            RichReason::ExpectedFound { expected, found: _ } => {
-                for new_expected in new_expected {
-                    let new_expected = new_expected
-                        .map(RichPattern::Token)
-                        .unwrap_or(RichPattern::EndOfInput);
-                    if !expected[..].contains(&new_expected) {
-                        expected.push(new_expected);
-                    }
-                }
+            RichReason::ExpectedFound { .. } => {
+                <Self as Error<'a, I>>::add_expected(&mut self, new_expected)
             }
             RichReason::Many(m) => m.push(RichReason::ExpectedFound {
                 expected: new_expected
@@ -735,6 +838,24 @@ where
         self
     }
 
+    #[inline]
+    fn add_expected<E: IntoIterator<Item = Option<MaybeRef<'a, I::Token>>>>(
+        &mut self,
+        new_expected: E,
+    ) {
+        // Custom errors have no expected inputs to add to
+        if let RichReason::ExpectedFound { expected, .. } = &mut *self.reason {
+            for new_expected in new_expected {
+                let new_expected = new_expected
+                    .map(RichPattern::Token)
+                    .unwrap_or(RichPattern::EndOfInput);
+                if !expected[..].contains(&new_expected) {
+                    expected.push(new_expected);
+                }
+            }
+        }
+    }
+
     #[inline]
     fn replace_expected_found<E: IntoIterator<Item = Option<MaybeRef<'a, I::Token>>>>(
         mut self,
@@ -769,6 +890,41 @@ where
         self.context.clear();
         self
     }
+
+    #[inline]
+    fn nesting_too_deep(_found: Option<MaybeRef<'a, I::Token>>, span: I::Span) -> Self {
+        Self::custom(span, "nesting too deep")
+    }
+
+    #[inline]
+    fn budget_exceeded(span: I::Span) -> Self {
+        Self::custom(span, "parser step budget exceeded")
+    }
+
+    #[inline]
+    fn input_failure(span: I::Span, reason: &str) -> Self {
+        Self::custom(span, reason)
+    }
+
+    #[inline]
+    fn duplicate_key(span: I::Span) -> Self {
+        Self::custom(span, "duplicate key")
+    }
+
+    #[inline]
+    fn invalid_literal<R: fmt::Display>(reason: R, span: I::Span) -> Self {
+        Self::custom(span, reason)
+    }
+
+    #[inline]
+    fn leading_zero(span: I::Span) -> Self {
+        Self::custom(span, "leading zeros are not allowed")
+    }
+
+    #[inline]
+    fn unclosed_delimiter(span: I::Span) -> Self {
+        Self::custom(span, "unclosed delimiter")
+    }
 }
 
 #[cfg(feature = "label")]
@@ -61,6 +61,51 @@
 //! assert!(make_parser().parse(b"!").has_errors());
 //! assert!(make_parser().parse(b"").has_errors());
 //! ```
+//!
+//! # Writing combinators
+//!
+//! Chumsky's own parsers are implemented via an internal `go` method that is generic over a parsing mode. That method,
+//! and the types it uses, are deliberately kept private so that they can keep changing. Extension parsers don't need
+//! them: [`InputRef::parse`] and [`InputRef::check`] run any other parser against the input, and
+//! [`InputRef::save`] and [`InputRef::rewind`] allow backtracking. This is enough to write combinators that take other
+//! parsers as arguments.
+//!
+//! ```
+//! use chumsky::{prelude::*, input::InputRef, extension::v1::{ExtParser, Ext}};
+//!
+//! // A combinator that parses a pattern twice, producing both outputs.
+//! pub struct Twice_<A>(A);
+//!
+//! impl<'a, I, O, E, A> ExtParser<'a, I, (O, O), E> for Twice_<A>
+//! where
+//!     I: Input<'a>,
+//!     E: extra::ParserExtra<'a, I>,
+//!     A: Parser<'a, I, O, E>,
+//! {
+//!     fn parse(&self, inp: &mut InputRef<'a, '_, I, E>) -> Result<(O, O), E::Error> {
+//!         let a = inp.parse(&self.0)?;
+//!         let b = inp.parse(&self.0)?;
+//!         Ok((a, b))
+//!     }
+//!
+//!     // Avoid generating outputs that will never be used
+//!     fn check(&self, inp: &mut InputRef<'a, '_, I, E>) -> Result<(), E::Error> {
+//!         inp.check(&self.0)?;
+//!         inp.check(&self.0)
+//!     }
+//! }
+//!
+//! pub type Twice<A> = Ext<Twice_<A>>;
+//!
+//! pub fn twice<A>(parser: A) -> Twice<A> {
+//!     Ext(Twice_(parser))
+//! }
+//!
+//! let digits = twice(any::<_, extra::Default>().filter(char::is_ascii_digit));
+//!
+//! assert_eq!(digits.parse("42").into_result(), Ok(('4', '2')));
+//! assert!(digits.parse("4").has_errors());
+//! ```
 
 use super::*;
 
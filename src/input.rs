@@ -5,7 +5,7 @@
 //! [`Input`] is the primary trait used to feed input data into a chumsky parser. You can create them in a number of
 //! ways: from strings, slices, arrays, etc.
 
-pub use crate::stream::{BoxedExactSizeStream, BoxedStream, Stream};
+pub use crate::stream::{BoxedExactSizeStream, BoxedStream, CachedIterInput, Stream};
 
 use super::*;
 #[cfg(feature = "memoization")]
@@ -13,7 +13,8 @@ use hashbrown::HashMap;
 #[cfg(feature = "std")]
 use std::{
     cell::RefCell,
-    io::{BufReader, Read, Seek},
+    collections::VecDeque,
+    io::{self, BufRead, BufReader, ErrorKind, Read, Seek},
 };
 
 /// A trait for types that represents a stream of input tokens. Unlike [`Iterator`], this type
@@ -26,8 +27,10 @@ use std::{
 ///
 /// Some common input types, and which traits they implement are:
 /// - `&str`: [`SliceInput`], [`StrInput`], [`ValueInput`], [`ExactSizeInput`]
+/// - `&String`: the same as `&str`
 /// - `&[T]`: [`SliceInput`], [`ValueInput`], [`BorrowInput`], [`ExactSizeInput`]
 /// - `Stream<I>`: [`ValueInput`], [`ExactSizeInput`] if `I: ExactSizeIterator`
+/// - `CachedIterInput<I>`: [`ValueInput`], [`ExactSizeInput`] if `I: ExactSizeIterator`
 ///
 /// This trait is sealed and so cannot be implemented by other crates because it has an unstable API. This may
 /// eventually change. For now, if you wish to use a type that chumsky does not know about as an input, consider using
@@ -76,6 +79,25 @@ pub trait Input<'a>: Sealed + 'a {
     #[doc(hidden)]
     fn prev(offs: Self::Offset) -> Self::Offset;
 
+    // Get the offset of the token that ends at the given offset, or `None` at the start of the input. Inputs with
+    // tokens that span more than one offset, like the characters of a `&str`, must override this.
+    //
+    // # Safety
+    //
+    // As with `Input::next_maybe`, the offset must have been generated by `Input::start` or `Input::next_maybe`.
+    #[doc(hidden)]
+    unsafe fn prev_token_offset(&self, offs: Self::Offset) -> Option<Self::Offset> {
+        (offs > self.start()).then(|| Self::prev(offs))
+    }
+
+    // A failure of the input itself that cut it short, such as an IO error, and the offset at which it happened. This
+    // is reported as an error once parsing has finished, and the parse produces no output, since the parser may have
+    // mistaken the failure for the end of the input.
+    #[doc(hidden)]
+    fn failure(&self) -> Option<(Self::Offset, String)> {
+        None
+    }
+
     /// Split an input that produces tokens of type `(T, S)` into one that produces tokens of type `T` and spans of
     /// type `S`.
     ///
@@ -122,6 +144,35 @@ pub trait Input<'a>: Sealed + 'a {
         }
     }
 
+    /// Shift the spans generated by this input by a base offset.
+    ///
+    /// This is useful when parsing a snippet extracted from a larger source, such as the body of a macro, so that the
+    /// spans of outputs and errors refer to the original source rather than to the start of the snippet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let src = "let x = 5 + ;";
+    /// let snippet = &src[8..];
+    ///
+    /// let sum = text::int::<_, _, extra::Err<Rich<char>>>(10)
+    ///     .padded()
+    ///     .separated_by(just('+'))
+    ///     .at_least(1)
+    ///     .collect::<Vec<_>>()
+    ///     .then_ignore(just(';'));
+    ///
+    /// let errs = sum.parse(snippet.with_offset(8)).into_errors();
+    /// assert_eq!(errs[0].span(), &SimpleSpan::new(12, 13));
+    /// ```
+    fn with_offset(self, base: usize) -> WithOffset<Self>
+    where
+        Self: Sized,
+    {
+        WithOffset { input: self, base }
+    }
+
     /// Map the spans output for this input to a different output span.
     ///
     /// This is useful if you wish to include extra context that applies to all spans emitted during a parse, such as
@@ -137,6 +188,56 @@ pub trait Input<'a>: Sealed + 'a {
             phantom: PhantomData,
         }
     }
+
+    /// Map the tokens produced by this input to a different token type, lazily as they are read.
+    ///
+    /// This is useful for reusing a parser written against one token representation with input in another, such as
+    /// raw tokens from a lexer, without first collecting the mapped tokens into a new buffer. Spans are unchanged.
+    ///
+    /// As the mapped tokens are produced on demand, the resulting input does not implement [`BorrowInput`]. Slices
+    /// taken from it are those of the original input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Tok {
+    ///     Num(u64),
+    ///     Plus,
+    /// }
+    ///
+    /// struct RawTok(&'static str);
+    ///
+    /// impl From<&RawTok> for Tok {
+    ///     fn from(raw: &RawTok) -> Self {
+    ///         match raw.0 {
+    ///             "+" => Tok::Plus,
+    ///             s => Tok::Num(s.parse().unwrap()),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let num = select! { Tok::Num(x) => x };
+    /// let sum = num.foldl(
+    ///     just::<_, _, extra::Err<Simple<Tok>>>(Tok::Plus).ignore_then(num).repeated(),
+    ///     |a, b| a + b,
+    /// );
+    ///
+    /// let raw = [RawTok("1"), RawTok("+"), RawTok("20")];
+    /// assert_eq!(sum.parse((&raw[..]).map_token(|t| Tok::from(t))).into_result(), Ok(21));
+    /// ```
+    fn map_token<T, F>(self, map_fn: F) -> MappedToken<T, Self, F>
+    where
+        Self: Sized,
+        F: Fn(&Self::Token) -> T,
+    {
+        MappedToken {
+            input: self,
+            map_fn,
+            phantom: PhantomData,
+        }
+    }
 }
 
 /// Implement by inputs that have a known size (including spans)
@@ -222,6 +323,13 @@ impl<'a> Input<'a> for &'a str {
     fn prev(offs: Self::Offset) -> Self::Offset {
         offs.saturating_sub(1)
     }
+
+    #[inline]
+    unsafe fn prev_token_offset(&self, offs: Self::Offset) -> Option<Self::Offset> {
+        // SAFETY: Offsets are always at character boundaries
+        let c = unsafe { self.get_unchecked(..offs) }.chars().next_back()?;
+        Some(offs - c.len_utf8())
+    }
 }
 
 impl<'a> ExactSizeInput<'a> for &'a str {
@@ -271,6 +379,79 @@ impl<'a> SliceInput<'a> for &'a str {
     }
 }
 
+// `&String` is parsed exactly like the `&str` it dereferences to, so that owned strings can be passed straight to
+// `Parser::parse` without converting them first, even when the input type of the parser has not yet been inferred
+impl Sealed for &String {}
+impl<'a> Input<'a> for &'a String {
+    type Offset = usize;
+    type Token = char;
+    type Span = SimpleSpan<usize>;
+
+    #[inline]
+    fn start(&self) -> Self::Offset {
+        0
+    }
+
+    type TokenMaybe = char;
+
+    #[inline(always)]
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        self.next(offset)
+    }
+
+    #[inline(always)]
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        range.into()
+    }
+
+    #[inline(always)]
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        offs.saturating_sub(1)
+    }
+
+    #[inline(always)]
+    unsafe fn prev_token_offset(&self, offs: Self::Offset) -> Option<Self::Offset> {
+        // SAFETY: The offsets of a `&String` are those of the `&str` that it dereferences to
+        unsafe { self.as_str().prev_token_offset(offs) }
+    }
+}
+
+impl<'a> ExactSizeInput<'a> for &'a String {
+    #[inline(always)]
+    unsafe fn span_from(&self, range: RangeFrom<Self::Offset>) -> Self::Span {
+        (range.start..self.len()).into()
+    }
+}
+
+impl<'a> ValueInput<'a> for &'a String {
+    #[inline(always)]
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        // SAFETY: The offsets of a `&String` are those of the `&str` that it dereferences to
+        unsafe { self.as_str().next(offset) }
+    }
+}
+
+impl<'a> StrInput<'a, char> for &'a String {}
+
+impl<'a> SliceInput<'a> for &'a String {
+    type Slice = &'a str;
+
+    #[inline(always)]
+    fn full_slice(&self) -> Self::Slice {
+        self.as_str()
+    }
+
+    #[inline(always)]
+    fn slice(&self, range: Range<Self::Offset>) -> Self::Slice {
+        &self[range]
+    }
+
+    #[inline(always)]
+    fn slice_from(&self, from: RangeFrom<Self::Offset>) -> Self::Slice {
+        &self[from]
+    }
+}
+
 impl<'a, T> Sealed for &'a [T] {}
 impl<'a, T> Input<'a> for &'a [T] {
     type Offset = usize;
@@ -437,6 +618,58 @@ pub struct SpannedInput<T, S, I> {
     phantom: PhantomData<T>,
 }
 
+impl<T, S, I> SpannedInput<T, S, I> {
+    /// Create a new input from one that produces tokens of type `(T, S)` and an 'End Of Input' (EoI) span, like
+    /// [`Input::spanned`].
+    ///
+    /// Errors found at the end of the input, such as a missing token, are reported at the EoI span.
+    pub fn new(input: I, eoi: S) -> Self {
+        Self {
+            input,
+            eoi,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An input made up of a slice of tokens paired with their spans, as commonly produced by a separate lexing phase.
+///
+/// See [`SpannedInput::new`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::TokenInput};
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Tok {
+///     Num(u64),
+///     Plus,
+/// }
+///
+/// fn lex(src: &str) -> Vec<(Tok, SimpleSpan)> {
+///     src.char_indices()
+///         .filter(|(_, c)| !c.is_whitespace())
+///         .map(|(i, c)| {
+///             let tok = match c {
+///                 '+' => Tok::Plus,
+///                 c => Tok::Num(c.to_digit(10).unwrap().into()),
+///             };
+///             (tok, SimpleSpan::new(i, i + 1))
+///         })
+///         .collect()
+/// }
+///
+/// let num = select! { Tok::Num(x) => x };
+/// let plus = just::<_, _, extra::Err<Rich<Tok>>>(Tok::Plus);
+/// let sum = num.foldl(plus.ignore_then(num).repeated(), |a, b| a + b);
+///
+/// let src = "1 + 2 + 3";
+/// let tokens = lex(src);
+/// let eoi = SimpleSpan::new(src.len(), src.len());
+/// assert_eq!(sum.parse(TokenInput::new(&tokens, eoi)).into_result(), Ok(6));
+/// ```
+pub type TokenInput<'a, T, S> = SpannedInput<T, S, &'a [(T, S)]>;
+
 /// Utility type required to allow [`SpannedInput`] to implement [`Input`].
 #[doc(hidden)]
 pub struct SpannedTokenMaybe<'a, I: Input<'a>, T, S>(I::TokenMaybe, PhantomData<(T, S)>);
@@ -486,13 +719,12 @@ where
 
     #[inline(always)]
     unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
-        let start = self
-            .input
-            .next_maybe(range.start)
-            .1
-            .or_else(|| self.input.next_maybe(self.input.start()).1)
-            // TODO: Should EOI actually be 'full input'?
-            .map_or(self.eoi.start(), |tok| tok.borrow().1.start());
+        let start = match self.input.next_maybe(range.start).1 {
+            Some(tok) => tok.borrow().1.start(),
+            // Spans that start at the end of the input, such as that of an error caused by a missing token, can only
+            // be the EoI span
+            None => return self.eoi.clone(),
+        };
         let end = self
             .input
             .next_maybe(I::prev(range.end))
@@ -505,6 +737,11 @@ where
     fn prev(offs: Self::Offset) -> Self::Offset {
         I::prev(offs)
     }
+
+    #[inline(always)]
+    unsafe fn prev_token_offset(&self, offs: Self::Offset) -> Option<Self::Offset> {
+        self.input.prev_token_offset(offs)
+    }
 }
 
 impl<'a, T, S, I> ExactSizeInput<'a> for SpannedInput<T, S, I>
@@ -619,6 +856,11 @@ where
     fn prev(offs: Self::Offset) -> Self::Offset {
         I::prev(offs)
     }
+
+    #[inline(always)]
+    unsafe fn prev_token_offset(&self, offs: Self::Offset) -> Option<Self::Offset> {
+        self.input.prev_token_offset(offs)
+    }
 }
 
 impl<'a, S, I: Input<'a>> ExactSizeInput<'a> for WithContext<S, I>
@@ -696,6 +938,221 @@ where
 {
 }
 
+/// An input wrapper that shifts the spans it generates by a base offset. See [`Input::with_offset`].
+#[derive(Copy, Clone)]
+pub struct WithOffset<I> {
+    input: I,
+    base: usize,
+}
+
+impl<I> WithOffset<I> {
+    #[inline(always)]
+    fn rebase<S: Span<Offset = usize>>(&self, span: S) -> S {
+        S::new(
+            span.context(),
+            span.start() + self.base..span.end() + self.base,
+        )
+    }
+}
+
+impl<I> Sealed for WithOffset<I> {}
+impl<'a, I> Input<'a> for WithOffset<I>
+where
+    I: Input<'a>,
+    I::Span: Span<Offset = usize>,
+{
+    type Offset = I::Offset;
+    type Token = I::Token;
+    type Span = I::Span;
+
+    #[inline(always)]
+    fn start(&self) -> Self::Offset {
+        self.input.start()
+    }
+
+    type TokenMaybe = I::TokenMaybe;
+
+    #[inline(always)]
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        self.input.next_maybe(offset)
+    }
+
+    #[inline(always)]
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        self.rebase(self.input.span(range))
+    }
+
+    #[inline(always)]
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        I::prev(offs)
+    }
+
+    #[inline(always)]
+    unsafe fn prev_token_offset(&self, offs: Self::Offset) -> Option<Self::Offset> {
+        self.input.prev_token_offset(offs)
+    }
+}
+
+impl<'a, I> ExactSizeInput<'a> for WithOffset<I>
+where
+    I: ExactSizeInput<'a>,
+    I::Span: Span<Offset = usize>,
+{
+    #[inline(always)]
+    unsafe fn span_from(&self, range: RangeFrom<Self::Offset>) -> Self::Span {
+        self.rebase(self.input.span_from(range))
+    }
+}
+
+impl<'a, I> ValueInput<'a> for WithOffset<I>
+where
+    I: ValueInput<'a>,
+    I::Span: Span<Offset = usize>,
+{
+    #[inline(always)]
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        self.input.next(offset)
+    }
+}
+
+impl<'a, I> BorrowInput<'a> for WithOffset<I>
+where
+    I: BorrowInput<'a>,
+    I::Span: Span<Offset = usize>,
+{
+    #[inline(always)]
+    unsafe fn next_ref(&self, offset: Self::Offset) -> (Self::Offset, Option<&'a Self::Token>) {
+        self.input.next_ref(offset)
+    }
+}
+
+impl<'a, I> SliceInput<'a> for WithOffset<I>
+where
+    I: SliceInput<'a>,
+    I::Span: Span<Offset = usize>,
+{
+    type Slice = I::Slice;
+
+    #[inline(always)]
+    fn full_slice(&self) -> Self::Slice {
+        <I as SliceInput>::full_slice(&self.input)
+    }
+
+    #[inline(always)]
+    fn slice(&self, range: Range<Self::Offset>) -> Self::Slice {
+        <I as SliceInput>::slice(&self.input, range)
+    }
+
+    #[inline(always)]
+    fn slice_from(&self, from: RangeFrom<Self::Offset>) -> Self::Slice {
+        <I as SliceInput>::slice_from(&self.input, from)
+    }
+}
+
+impl<'a, C, I> StrInput<'a, C> for WithOffset<I>
+where
+    I: StrInput<'a, C>,
+    I::Span: Span<Offset = usize>,
+    C: Char,
+{
+}
+
+/// An input wrapper that maps the tokens of an input to a different token type. See [`Input::map_token`].
+#[derive(Copy, Clone)]
+pub struct MappedToken<T, I, F> {
+    input: I,
+    map_fn: F,
+    phantom: PhantomData<T>,
+}
+
+impl<T, I, F> Sealed for MappedToken<T, I, F> {}
+impl<'a, T, I, F> Input<'a> for MappedToken<T, I, F>
+where
+    I: Input<'a>,
+    T: 'a,
+    F: Fn(&I::Token) -> T + 'a,
+{
+    type Offset = I::Offset;
+    type Token = T;
+    type Span = I::Span;
+
+    #[inline(always)]
+    fn start(&self) -> Self::Offset {
+        self.input.start()
+    }
+
+    type TokenMaybe = T;
+
+    #[inline(always)]
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        let (offset, tok) = self.input.next_maybe(offset);
+        (offset, tok.map(|tok| (self.map_fn)(tok.borrow())))
+    }
+
+    #[inline(always)]
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        self.input.span(range)
+    }
+
+    #[inline(always)]
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        I::prev(offs)
+    }
+
+    #[inline(always)]
+    unsafe fn prev_token_offset(&self, offs: Self::Offset) -> Option<Self::Offset> {
+        self.input.prev_token_offset(offs)
+    }
+}
+
+impl<'a, T, I, F> ExactSizeInput<'a> for MappedToken<T, I, F>
+where
+    I: ExactSizeInput<'a>,
+    T: 'a,
+    F: Fn(&I::Token) -> T + 'a,
+{
+    #[inline(always)]
+    unsafe fn span_from(&self, range: RangeFrom<Self::Offset>) -> Self::Span {
+        self.input.span_from(range)
+    }
+}
+
+impl<'a, T, I, F> ValueInput<'a> for MappedToken<T, I, F>
+where
+    I: Input<'a>,
+    T: 'a,
+    F: Fn(&I::Token) -> T + 'a,
+{
+    #[inline(always)]
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        self.next_maybe(offset)
+    }
+}
+
+impl<'a, T, I, F> SliceInput<'a> for MappedToken<T, I, F>
+where
+    I: SliceInput<'a>,
+    T: 'a,
+    F: Fn(&I::Token) -> T + 'a,
+{
+    type Slice = I::Slice;
+
+    #[inline(always)]
+    fn full_slice(&self) -> Self::Slice {
+        <I as SliceInput>::full_slice(&self.input)
+    }
+
+    #[inline(always)]
+    fn slice(&self, range: Range<Self::Offset>) -> Self::Slice {
+        <I as SliceInput>::slice(&self.input, range)
+    }
+
+    #[inline(always)]
+    fn slice_from(&self, from: RangeFrom<Self::Offset>) -> Self::Slice {
+        <I as SliceInput>::slice_from(&self.input, from)
+    }
+}
+
 /// An input wrapper that maps the span type of your input
 /// into your custom span [`Input::map_span`].
 #[derive(Copy, Clone)]
@@ -739,6 +1196,11 @@ where
     fn prev(offs: Self::Offset) -> Self::Offset {
         I::prev(offs)
     }
+
+    #[inline(always)]
+    unsafe fn prev_token_offset(&self, offs: Self::Offset) -> Option<Self::Offset> {
+        self.input.prev_token_offset(offs)
+    }
 }
 
 impl<'a, S, I: Input<'a>, F: 'a> ExactSizeInput<'a> for MappedSpan<S, I, F>
@@ -896,12 +1358,328 @@ impl<'a, R: Read + Seek + 'a> ValueInput<'a> for IoInput<R> {
     }
 }
 
+#[cfg(feature = "std")]
+struct BufReadInner<R> {
+    reader: R,
+    // The most recently read bytes, the first of which is at offset `start`
+    window: VecDeque<u8>,
+    start: usize,
+    capacity: usize,
+    eof: bool,
+    // The first IO error encountered, if it has not been taken yet
+    error: Option<io::Error>,
+    failure: Option<(usize, String)>,
+}
+
+#[cfg(feature = "std")]
+impl<R> BufReadInner<R> {
+    fn fail(&mut self, offset: usize, reason: String) {
+        self.failure.get_or_insert((offset, reason));
+    }
+}
+
+/// Input type which supports readers that cannot seek, such as sockets, pipes or [`std::io::Stdin`].
+///
+/// Only the last `window` bytes read are kept, so parsers may only backtrack that far behind the furthest point
+/// of the input that has been read. Most grammars look ahead by no more than a handful of tokens, allowing the
+/// window to remain small even when the input is very large. Use [`IoInput`] instead if the reader is seekable.
+///
+/// An IO error ends the input and is reported as a parse error at the offset where it happened (see
+/// [`Error::input_failure`]). The error itself can be retrieved with [`BufReadInput::take_io_error`], for which the
+/// input can be parsed by reference. A parser that attempts to rewind to an offset that has already left the window
+/// also produces a parse error, instead of the tokens that were there. Either way, the parse produces no output.
+///
+/// Only available with the `std` feature
+#[cfg(feature = "std")]
+pub struct BufReadInput<R>(RefCell<BufReadInner<R>>);
+
+#[cfg(feature = "std")]
+impl<R: BufRead> BufReadInput<R> {
+    /// Create a new `BufReadInput` from a buffered reader, keeping the last `window` bytes for rewinding.
+    ///
+    /// A window of at least one byte is always kept.
+    pub fn new(reader: R, window: usize) -> BufReadInput<R> {
+        BufReadInput(RefCell::new(BufReadInner {
+            reader,
+            window: VecDeque::new(),
+            start: 0,
+            capacity: window.max(1),
+            eof: false,
+            error: None,
+            failure: None,
+        }))
+    }
+
+    /// Take the IO error that ended the input, if there was one.
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, input::BufReadInput};
+    /// # use std::io::{self, BufReader, ErrorKind, Read};
+    /// // A reader that fails after producing some bytes
+    /// let reader = BufReader::new(b"ab".chain(Failing));
+    /// # struct Failing;
+    /// # impl Read for Failing {
+    /// #     fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+    /// #         Err(io::Error::new(ErrorKind::ConnectionReset, "connection reset"))
+    /// #     }
+    /// # }
+    /// let input = BufReadInput::new(reader, 16);
+    ///
+    /// let word = any::<_, extra::Err<Rich<u8>>>().repeated().collect::<Vec<_>>();
+    /// assert!(word.parse(&input).has_errors());
+    /// assert_eq!(input.take_io_error().map(|e| e.kind()), Some(ErrorKind::ConnectionReset));
+    /// ```
+    pub fn take_io_error(&self) -> Option<io::Error> {
+        self.0.borrow_mut().error.take()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> Sealed for BufReadInput<R> {}
+#[cfg(feature = "std")]
+impl<'a, R: BufRead + 'a> Input<'a> for BufReadInput<R> {
+    type Offset = usize;
+    type Token = u8;
+    type Span = SimpleSpan;
+
+    fn start(&self) -> Self::Offset {
+        0
+    }
+
+    type TokenMaybe = u8;
+
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        Self::next(self, offset)
+    }
+
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        SimpleSpan::from(range)
+    }
+
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        offs.saturating_sub(1)
+    }
+
+    fn failure(&self) -> Option<(Self::Offset, String)> {
+        self.0.borrow().failure.clone()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, R: BufRead + 'a> ValueInput<'a> for BufReadInput<R> {
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        let inner = &mut *self.0.borrow_mut();
+
+        if offset < inner.start {
+            let reason = format!(
+                "cannot rewind to offset {} because only the last {} bytes read are kept (offset {} onwards), \
+                consider using a larger window",
+                offset, inner.capacity, inner.start,
+            );
+            inner.fail(offset, reason);
+            return (offset, None);
+        }
+
+        while !inner.eof && offset >= inner.start + inner.window.len() {
+            let buf = match inner.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    let at = inner.start + inner.window.len();
+                    inner.fail(at, format!("failed to read input: {err}"));
+                    inner.error = Some(err);
+                    inner.eof = true;
+                    break;
+                }
+            };
+            if buf.is_empty() {
+                inner.eof = true;
+                break;
+            }
+            // Only take what's needed to reach `offset`, so that the window never skips past it
+            let n = buf.len().min(offset + 1 - inner.start - inner.window.len());
+            inner.window.extend(&buf[..n]);
+            inner.reader.consume(n);
+
+            let excess = inner.window.len().saturating_sub(inner.capacity);
+            inner.window.drain(..excess);
+            inner.start += excess;
+        }
+
+        match inner.window.get(offset - inner.start) {
+            Some(tok) => (offset + 1, Some(*tok)),
+            None => (offset, None),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> Sealed for &BufReadInput<R> {}
+#[cfg(feature = "std")]
+impl<'a, R: BufRead + 'a> Input<'a> for &'a BufReadInput<R> {
+    type Offset = usize;
+    type Token = u8;
+    type Span = SimpleSpan;
+
+    fn start(&self) -> Self::Offset {
+        0
+    }
+
+    type TokenMaybe = u8;
+
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        BufReadInput::next(*self, offset)
+    }
+
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        SimpleSpan::from(range)
+    }
+
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        offs.saturating_sub(1)
+    }
+
+    fn failure(&self) -> Option<(Self::Offset, String)> {
+        BufReadInput::failure(*self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, R: BufRead + 'a> ValueInput<'a> for &'a BufReadInput<R> {
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        BufReadInput::next(*self, offset)
+    }
+}
+
+/// Input type which splits a [`&str`] into grapheme clusters, so that each token is a user-perceived character.
+///
+/// Unlike [`&str`] input, which produces a token for every [`char`], a grapheme cluster made up of several code points
+/// (such as an emoji with a skin tone modifier, a flag, or a letter followed by combining accents) is a single token of
+/// type `&str`. Extended grapheme clusters are used, as described in
+/// [Unicode Standard Annex #29](https://www.unicode.org/reports/tr29/). Offsets and spans are in bytes, as for
+/// [`&str`], and slices of the input are [`&str`]s.
+///
+/// Only available with the `unicode-segmentation` feature
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::GraphemeInput};
+/// let thumb = just::<_, _, extra::Err<Simple<&str>>>("👍🏽");
+///
+/// // With `&str` input, this would be two tokens
+/// assert_eq!(thumb.parse(GraphemeInput::new("👍🏽")).into_result(), Ok("👍🏽"));
+/// assert!(thumb.parse(GraphemeInput::new("👍")).has_errors());
+/// ```
+#[cfg(feature = "unicode-segmentation")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GraphemeInput<'a>(&'a str);
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> GraphemeInput<'a> {
+    /// Create a new `GraphemeInput` from a string.
+    pub fn new(input: &'a str) -> GraphemeInput<'a> {
+        GraphemeInput(input)
+    }
+}
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> Sealed for GraphemeInput<'a> {}
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> Input<'a> for GraphemeInput<'a> {
+    type Offset = usize;
+    type Token = &'a str;
+    type Span = SimpleSpan<usize>;
+
+    #[inline]
+    fn start(&self) -> Self::Offset {
+        0
+    }
+
+    type TokenMaybe = &'a str;
+
+    #[inline(always)]
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        self.next(offset)
+    }
+
+    #[inline(always)]
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        range.into()
+    }
+
+    #[inline(always)]
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        offs.saturating_sub(1)
+    }
+
+    #[inline]
+    unsafe fn prev_token_offset(&self, offs: Self::Offset) -> Option<Self::Offset> {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        // SAFETY: We only ever return offsets that are at the boundary of a grapheme cluster, and so of a character
+        let g = unsafe { self.0.get_unchecked(..offs) }
+            .graphemes(true)
+            .next_back()?;
+        Some(offs - g.len())
+    }
+}
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> ExactSizeInput<'a> for GraphemeInput<'a> {
+    #[inline(always)]
+    unsafe fn span_from(&self, range: RangeFrom<Self::Offset>) -> Self::Span {
+        (range.start..self.0.len()).into()
+    }
+}
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> ValueInput<'a> for GraphemeInput<'a> {
+    #[inline]
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        // SAFETY: We only ever return offsets that are at the boundary of a grapheme cluster, and so of a character
+        let rest = unsafe { self.0.get_unchecked(offset..) };
+        match rest.graphemes(true).next() {
+            Some(g) => (offset + g.len(), Some(g)),
+            None => (offset, None),
+        }
+    }
+}
+
+#[cfg(feature = "unicode-segmentation")]
+impl<'a> SliceInput<'a> for GraphemeInput<'a> {
+    type Slice = &'a str;
+
+    #[inline(always)]
+    fn full_slice(&self) -> Self::Slice {
+        self.0
+    }
+
+    #[inline(always)]
+    fn slice(&self, range: Range<Self::Offset>) -> Self::Slice {
+        &self.0[range]
+    }
+
+    #[inline(always)]
+    fn slice_from(&self, from: RangeFrom<Self::Offset>) -> Self::Slice {
+        &self.0[from]
+    }
+}
+
 /// Represents a location in an input that can be rewound to.
 ///
-/// Markers can be created with [`InputRef::save`] and rewound to with [`InputRef::rewind`].
+/// Markers can be created with [`InputRef::save`] and rewound to with [`InputRef::rewind`]. Custom parsers that need
+/// to backtrack should prefer [`Checkpoint`], which wraps a marker.
+///
+/// A marker is opaque and only valid for the parse that created it: the `'parse` lifetime is invariant, so the
+/// compiler rejects any attempt to keep a marker beyond that parse or to use it with another [`InputRef`].
 pub struct Marker<'a, 'parse, I: Input<'a>> {
     pub(crate) offset: I::Offset,
     pub(crate) err_count: usize,
+    pub(crate) recovery_count: usize,
+    pub(crate) depth_exceeded: Option<I::Offset>,
     phantom: PhantomData<fn(&'parse ()) -> &'parse ()>, // Invariance
 }
 
@@ -923,6 +1701,37 @@ impl<'a, 'parse, I: Input<'a>> Clone for Marker<'a, 'parse, I> {
     }
 }
 
+/// A point in a parse that a parser can return to, for implementing backtracking in custom parsers (see
+/// [`custom`](crate::primitive::custom)).
+///
+/// Checkpoints are created with [`InputRef::checkpoint`] and returned to with [`InputRef::rewind_to`]. The following
+/// invariants hold:
+///
+/// - A checkpoint is only valid within the parse that created it. Its `'parse` lifetime is invariant, so the compiler
+///   rejects any attempt to keep a checkpoint beyond that parse or to use it with another [`InputRef`].
+/// - Returning to a checkpoint restores the input position, and discards any errors emitted and recoveries performed
+///   since it was created. It does not undo changes made to the parser state (see [`InputRef::state`]).
+/// - A checkpoint may be returned to any number of times, including after parsing has moved backwards past it, so a
+///   checkpoint taken after a successful attempt can be used to skip ahead to the end of that attempt later.
+pub struct Checkpoint<'a, 'parse, I: Input<'a>> {
+    marker: Marker<'a, 'parse, I>,
+}
+
+impl<'a, 'parse, I: Input<'a>> Checkpoint<'a, 'parse, I> {
+    /// Get the [`Offset`] that this checkpoint corresponds to.
+    pub fn offset(self) -> Offset<'a, 'parse, I> {
+        self.marker.offset()
+    }
+}
+
+impl<'a, 'parse, I: Input<'a>> Copy for Checkpoint<'a, 'parse, I> {}
+impl<'a, 'parse, I: Input<'a>> Clone for Checkpoint<'a, 'parse, I> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
 /// Represents a location in an input.
 ///
 /// If you to rewind to an old input location, see [`Marker`].
@@ -960,6 +1769,11 @@ impl<'a, 'parse, I: Input<'a>> Ord for Offset<'a, 'parse, I> {
 pub(crate) struct Errors<T, E> {
     pub(crate) alt: Option<Located<T, E>>,
     pub(crate) secondary: Vec<Located<T, E>>,
+    /// The offset range covered by each successful recovery, along with the name of the strategy that performed it.
+    pub(crate) recoveries: Vec<(Range<T>, &'static str)>,
+    /// Set by [`Parser::parse_with_max_errors`]. Once this many secondary errors exist, recovery is no longer
+    /// attempted.
+    pub(crate) max: Option<usize>,
 }
 
 impl<T, E> Errors<T, E> {
@@ -970,11 +1784,52 @@ impl<T, E> Errors<T, E> {
     }
 }
 
+/// How deeply recursive parsers are currently nested, and how deeply they may be nested.
+pub(crate) struct Depth<T> {
+    pub(crate) current: usize,
+    /// Set by [`Parser::with_max_depth`]. `None` means that nesting is unlimited.
+    pub(crate) max: Option<usize>,
+    /// The furthest offset at which the limit has been reached, outside of any attempts that were since rewound.
+    pub(crate) exceeded: Option<T>,
+    /// How many times the limit has been reached, so callers can tell whether it was reached during an attempt.
+    pub(crate) times_exceeded: usize,
+}
+
+impl<T> Default for Depth<T> {
+    fn default() -> Self {
+        Self {
+            current: 0,
+            max: None,
+            exceeded: None,
+            times_exceeded: 0,
+        }
+    }
+}
+
+/// How many more steps the parser may take before giving up, set by [`Parser::parse_with_budget`].
+pub(crate) struct Budget<T> {
+    /// `None` means that the number of steps is unlimited.
+    pub(crate) remaining: Option<usize>,
+    /// The offset at which the budget ran out.
+    pub(crate) exhausted: Option<T>,
+}
+
+impl<T> Default for Budget<T> {
+    fn default() -> Self {
+        Self {
+            remaining: None,
+            exhausted: None,
+        }
+    }
+}
+
 impl<T, E> Default for Errors<T, E> {
     fn default() -> Self {
         Self {
             alt: None,
             secondary: Vec::new(),
+            recoveries: Vec::new(),
+            max: None,
         }
     }
 }
@@ -986,8 +1841,12 @@ pub(crate) struct InputOwn<'a, 's, I: Input<'a>, E: ParserExtra<'a, I>> {
     pub(crate) errors: Errors<I::Offset, E::Error>,
     pub(crate) state: MaybeMut<'s, E::State>,
     pub(crate) ctx: E::Context,
+    pub(crate) depth: Depth<I::Offset>,
+    pub(crate) budget: Budget<I::Offset>,
     #[cfg(feature = "memoization")]
     pub(crate) memos: HashMap<(I::Offset, usize), Option<Located<I::Offset, E::Error>>>,
+    #[cfg(feature = "memoization")]
+    pub(crate) seeds: HashMap<(I::Offset, usize), Seed<I::Offset>>,
 }
 
 impl<'a, 's, I, E> InputOwn<'a, 's, I, E>
@@ -1006,8 +1865,12 @@ where
             errors: Errors::default(),
             state: MaybeMut::Val(E::State::default()),
             ctx: E::Context::default(),
+            depth: Depth::default(),
+            budget: Budget::default(),
             #[cfg(feature = "memoization")]
             memos: HashMap::default(),
+            #[cfg(feature = "memoization")]
+            seeds: HashMap::default(),
         }
     }
 
@@ -1020,8 +1883,12 @@ where
             errors: Errors::default(),
             state: MaybeMut::Ref(state),
             ctx: E::Context::default(),
+            depth: Depth::default(),
+            budget: Budget::default(),
             #[cfg(feature = "memoization")]
             memos: HashMap::default(),
+            #[cfg(feature = "memoization")]
+            seeds: HashMap::default(),
         }
     }
 
@@ -1032,8 +1899,12 @@ where
             errors: &mut self.errors,
             state: &mut self.state,
             ctx: &self.ctx,
+            depth: &mut self.depth,
+            budget: &mut self.budget,
             #[cfg(feature = "memoization")]
             memos: &mut self.memos,
+            #[cfg(feature = "memoization")]
+            seeds: &mut self.seeds,
         }
     }
 
@@ -1048,20 +1919,61 @@ where
             errors: &mut self.errors,
             state: &mut self.state,
             ctx: &self.ctx,
+            depth: &mut self.depth,
+            budget: &mut self.budget,
             #[cfg(feature = "memoization")]
             memos: &mut self.memos,
+            #[cfg(feature = "memoization")]
+            seeds: &mut self.seeds,
         }
     }
 
+    // An error for the failure of the input itself, if it failed (see `Input::failure`)
+    fn input_failure(&self) -> Option<E::Error> {
+        let (at, reason) = self.input.failure()?;
+        // SAFETY: The failure offset was generated by this input
+        let span = unsafe { self.input.span(at..at) };
+        Some(E::Error::input_failure(span, &reason))
+    }
+
     pub(crate) fn into_errs(self) -> Vec<E::Error> {
+        let failure = self.input_failure();
         self.errors
             .secondary
             .into_iter()
             .map(|err| err.err)
+            .chain(failure)
             .collect()
     }
+
+    pub(crate) fn into_errs_and_recoveries(self) -> (Vec<E::Error>, Vec<Recovery<I::Span>>) {
+        let failure = self.input_failure();
+        let recoveries = self
+            .errors
+            .recoveries
+            .into_iter()
+            .map(|(range, strategy)| Recovery {
+                // SAFETY: Recovery offsets were generated by this input
+                span: unsafe { self.input.span(range) },
+                strategy,
+            })
+            .collect();
+        let errs = self
+            .errors
+            .secondary
+            .into_iter()
+            .map(|err| err.err)
+            .chain(failure)
+            .collect();
+        (errs, recoveries)
+    }
 }
 
+// The seed of a left-recursive parser that is being grown (see `combinator::MemoizedLeftRecursive`): `None` until
+// the first successful parse, then the best output so far (type-erased) and the offset at which it ends
+#[cfg(feature = "memoization")]
+pub(crate) type Seed<Off> = Option<(Box<dyn core::any::Any>, Off)>;
+
 /// Internal type representing an input as well as all the necessary context for parsing.
 pub struct InputRef<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> {
     pub(crate) offset: I::Offset,
@@ -1069,8 +1981,12 @@ pub struct InputRef<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> {
     pub(crate) errors: &'parse mut Errors<I::Offset, E::Error>,
     pub(crate) state: &'parse mut E::State,
     pub(crate) ctx: &'parse E::Context,
+    pub(crate) depth: &'parse mut Depth<I::Offset>,
+    pub(crate) budget: &'parse mut Budget<I::Offset>,
     #[cfg(feature = "memoization")]
     pub(crate) memos: &'parse mut HashMap<(I::Offset, usize), Option<Located<I::Offset, E::Error>>>,
+    #[cfg(feature = "memoization")]
+    pub(crate) seeds: &'parse mut HashMap<(I::Offset, usize), Seed<I::Offset>>,
 }
 
 impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E> {
@@ -1090,8 +2006,12 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
             state: self.state,
             ctx: new_ctx,
             errors: self.errors,
+            depth: self.depth,
+            budget: self.budget,
             #[cfg(feature = "memoization")]
             memos: self.memos,
+            #[cfg(feature = "memoization")]
+            seeds: self.seeds,
         };
         let res = f(&mut new_inp);
         self.offset = new_inp.offset;
@@ -1114,8 +2034,12 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
             state: new_state,
             ctx: self.ctx,
             errors: self.errors,
+            depth: self.depth,
+            budget: self.budget,
             #[cfg(feature = "memoization")]
             memos: self.memos,
+            #[cfg(feature = "memoization")]
+            seeds: self.seeds,
         };
         let res = f(&mut new_inp);
         self.offset = new_inp.offset;
@@ -1131,6 +2055,10 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
             (I::Offset, usize),
             Option<Located<I::Offset, E::Error>>,
         >,
+        #[cfg(feature = "memoization")] seeds: &'sub_parse mut HashMap<
+            (I::Offset, usize),
+            Seed<I::Offset>,
+        >,
     ) -> O
     where
         'parse: 'sub_parse,
@@ -1141,8 +2069,12 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
             state: self.state,
             ctx: self.ctx,
             errors: self.errors,
+            depth: self.depth,
+            budget: self.budget,
             #[cfg(feature = "memoization")]
             memos,
+            #[cfg(feature = "memoization")]
+            seeds,
         };
         f(&mut new_inp)
     }
@@ -1166,6 +2098,8 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
         Marker {
             offset: self.offset,
             err_count: self.errors.secondary.len(),
+            recovery_count: self.errors.recoveries.len(),
+            depth_exceeded: self.depth.exceeded,
             phantom: PhantomData,
         }
     }
@@ -1173,9 +2107,39 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
     /// Reset the parse state to that represented by the given [`Marker`].
     ///
     /// You can create a marker with which to perform rewinding using [`InputRef::save`].
+    ///
+    /// Rewinding restores the input position and discards any errors emitted and recoveries performed since the
+    /// marker was saved. It does not undo changes made to the parser state (see [`InputRef::state`]).
     #[inline(always)]
     pub fn rewind(&mut self, marker: Marker<'a, 'parse, I>) {
         self.errors.secondary.truncate(marker.err_count);
+        self.errors.recoveries.truncate(marker.recovery_count);
+        self.depth.exceeded = marker.depth_exceeded;
+        self.offset = marker.offset;
+    }
+
+    /// Create a [`Checkpoint`] at the current point in the parse.
+    ///
+    /// You can return to this point later with [`InputRef::rewind_to`].
+    #[inline(always)]
+    pub fn checkpoint(&self) -> Checkpoint<'a, 'parse, I> {
+        Checkpoint {
+            marker: self.save(),
+        }
+    }
+
+    /// Return to the point in the parse at which the given [`Checkpoint`] was created.
+    ///
+    /// See [`Checkpoint`] for what this does and doesn't undo.
+    #[inline(always)]
+    pub fn rewind_to(&mut self, checkpoint: Checkpoint<'a, 'parse, I>) {
+        self.rewind(checkpoint.marker);
+    }
+
+    /// Move the input position back to that of the given [`Marker`], keeping any errors emitted and recoveries
+    /// performed since it was saved.
+    #[inline(always)]
+    pub(crate) fn rewind_input(&mut self, marker: Marker<'a, 'parse, I>) {
         self.offset = marker.offset;
     }
 
@@ -1455,6 +2419,19 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
         self.errors.secondary.push(Located::at(pos, error));
     }
 
+    /// Whether error recovery may emit another error without exceeding the limit set by
+    /// [`Parser::parse_with_max_errors`]. Recovery combinators, and anything else that emits an error without failing,
+    /// should fail instead when it may not.
+    #[inline]
+    pub(crate) fn can_recover(&self) -> bool {
+        !matches!(self.errors.max, Some(max) if self.errors.secondary.len() >= max)
+    }
+
+    #[inline]
+    pub(crate) fn record_recovery(&mut self, start: I::Offset, strategy: &'static str) {
+        self.errors.recoveries.push((start..self.offset, strategy));
+    }
+
     #[inline]
     pub(crate) fn add_alt<Exp: IntoIterator<Item = Option<MaybeRef<'a, I::Token>>>>(
         &mut self,
@@ -1478,6 +2455,77 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
         });
     }
 
+    /// Run `f` one level of nesting deeper, failing instead if that would exceed the limit set by
+    /// [`Parser::with_max_depth`].
+    #[inline(always)]
+    pub(crate) fn nested<O>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<O, ()>,
+    ) -> Result<O, ()> {
+        self.step()?;
+        if matches!(self.depth.max, Some(max) if self.depth.current >= max) {
+            let at = self.offset;
+            match self.depth.exceeded {
+                Some(exceeded) if exceeded.into() >= at.into() => {}
+                _ => self.depth.exceeded = Some(at),
+            }
+            self.depth.times_exceeded += 1;
+            let err = self.nesting_too_deep_err();
+            self.add_alt_err(at, err);
+            return Err(());
+        }
+
+        self.depth.current += 1;
+        let res = f(self);
+        self.depth.current -= 1;
+        res
+    }
+
+    /// Take one step of the budget set by [`Parser::parse_with_budget`], failing if it has run out.
+    ///
+    /// This is called by parsers that may loop or recurse, so that the total amount of work is bounded.
+    #[inline(always)]
+    pub(crate) fn step(&mut self) -> Result<(), ()> {
+        match &mut self.budget.remaining {
+            None => Ok(()),
+            Some(0) => {
+                let at = self.offset;
+                if self.budget.exhausted.is_none() {
+                    self.budget.exhausted = Some(at);
+                }
+                let err = self.budget_exceeded_err();
+                self.add_alt_err(at, err);
+                Err(())
+            }
+            Some(remaining) => {
+                *remaining -= 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Generate a [`Error::budget_exceeded`] error for the token at the current offset.
+    #[inline]
+    pub(crate) fn budget_exceeded_err(&mut self) -> E::Error {
+        let before = self.save();
+        let start = self.offset();
+        self.next_maybe_inner();
+        let err_span = self.span_since(start);
+        self.rewind(before);
+        E::Error::budget_exceeded(err_span)
+    }
+
+    /// Generate a [`Error::nesting_too_deep`] error for the token at the current offset.
+    #[inline]
+    pub(crate) fn nesting_too_deep_err(&mut self) -> E::Error {
+        let before = self.save();
+        let start = self.offset();
+        let found = self.next_maybe_inner().1.map(|f| f.into());
+        let err_span = self.span_since(start);
+        self.rewind(before);
+        E::Error::nesting_too_deep(found, err_span)
+    }
+
     #[inline]
     pub(crate) fn add_alt_err(&mut self, at: I::Offset, err: E::Error) {
         // Prioritize errors
@@ -90,6 +90,8 @@ mod blanket;
 pub mod cache;
 pub mod combinator;
 pub mod container;
+#[cfg(feature = "derive")]
+pub mod derive;
 #[cfg(feature = "either")]
 mod either;
 pub mod error;
@@ -130,14 +132,15 @@ pub mod prelude {
         extra,
         input::Input,
         primitive::{
-            any, any_ref, choice, custom, empty, end, group, just, map_ctx, none_of, one_of, todo,
+            any, any_ref, chainl1, chainr1, choice, choice_longest, choice_trie, custom, empty,
+            end, group, just, map_ctx, none_of, one_of, range, todo,
         },
-        recovery::{nested_delimiters, skip_then_retry_until, skip_until, via_parser},
+        recovery::{insert, nested_delimiters, skip_then_retry_until, skip_until, via_parser},
         recursive::{recursive, Recursive},
         span::{SimpleSpan, Span as _},
-        text, Boxed, ConfigIterParser, ConfigParser, IterParser, ParseResult, Parser,
+        text, Boxed, BoxedLocal, ConfigIterParser, ConfigParser, IterParser, ParseResult, Parser,
     };
-    pub use crate::{select, select_ref};
+    pub use crate::{select, select_ref, seq_keep};
 }
 
 use crate::input::InputOwn;
@@ -171,15 +174,15 @@ use self::{
         BorrowInput, Emitter, ExactSizeInput, InputRef, MapExtra, SliceInput, StrInput, ValueInput,
     },
     prelude::*,
-    primitive::Any,
+    primitive::{Any, Choice, End},
     private::{
         Check, ConfigIterParserSealed, ConfigParserSealed, Emit, IPResult, IterParserSealed,
         Located, MaybeUninitExt, Mode, PResult, ParserSealed, Sealed,
     },
-    recovery::{RecoverWith, Strategy},
+    recovery::{RecoverWith, Recovery, Strategy, ThenRecover, ThenSkipGarbageUntil},
     span::Span,
     text::*,
-    util::{MaybeMut, MaybeRef},
+    util::{MaybeMut, MaybeRef, Nested},
 };
 #[cfg(all(feature = "extension", doc))]
 use self::{extension::v1::*, primitive::custom, stream::Stream};
@@ -272,6 +275,44 @@ impl<T, E> ParseResult<T, E> {
         self.output.as_ref()
     }
 
+    /// Transform the output of this result, if it exists, leaving the errors untouched.
+    ///
+    /// Unlike mapping the output of [`ParseResult::into_result`], this keeps any output produced alongside
+    /// recovered-from errors.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> ParseResult<U, E> {
+        ParseResult {
+            output: self.output.map(f),
+            errs: self.errs,
+        }
+    }
+
+    /// Transform each of the errors of this result, leaving the output untouched.
+    ///
+    /// This is useful for converting errors into another type at the boundary of a parser, such as when a parser
+    /// that produces [`Simple`] errors is used by code that expects [`Rich`] errors (see the [`From`] implementation
+    /// on [`Rich`]), or by code with an error type of its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let a = just::<_, _, extra::Err<Simple<char>>>('a');
+    ///
+    /// let errs = a
+    ///     .parse("x")
+    ///     .map_errors(Rich::<char>::from)
+    ///     .into_errors();
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(0..1));
+    /// assert_eq!(errs[0].found(), Some(&'x'));
+    /// ```
+    #[doc(alias = "convert_errors")]
+    pub fn map_errors<U, F: FnMut(E) -> U>(self, f: F) -> ParseResult<T, U> {
+        ParseResult {
+            output: self.output,
+            errs: self.errs.into_iter().map(f).collect(),
+        }
+    }
+
     /// Get an iterator over the parse errors for this result. The iterator will produce no items if there were no
     /// errors.
     pub fn errors(&self) -> impl ExactSizeIterator<Item = &E> + DoubleEndedIterator {
@@ -372,7 +413,8 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// If you want to include non-default state, use [`Parser::parse_with_state`] instead.
     ///
     /// Although the signature of this function looks complicated, it's simpler than you think! You can pass a
-    /// [`&[T]`], a [`&str`], [`Stream`], or anything implementing [`Input`] to it.
+    /// [`&[T]`], a [`&str`], [`Stream`], or anything implementing [`Input`] to it. A [`String`] may be parsed by passing
+    /// a reference to it, which behaves exactly like the [`&str`] it dereferences to.
     fn parse(&self, input: I) -> ParseResult<O, E::Error>
     where
         I: Input<'a>,
@@ -399,9 +441,318 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         let mut inp = own.as_ref_start();
         let res = self.then_ignore(end()).go::<Emit>(&mut inp);
         let alt = inp.errors.alt.take();
+        let failed = own.input.failure().is_some();
+        let mut errs = own.into_errs();
+        let out = match res {
+            Ok(out) if !failed => Some(out),
+            Ok(_) => None,
+            Err(()) => {
+                errs.push(alt.expect("error but no alt?").err);
+                None
+            }
+        };
+        ParseResult::new(out, errs)
+    }
+
+    /// Parse a stream of tokens like [`Parser::parse`], additionally yielding a list of the places at which error
+    /// recovery (see [`Parser::recover_with`]) occurred.
+    ///
+    /// This is useful for tooling, such as editors, that want to distinguish regions of input that were recovered from
+    /// plain errors. Recoveries are listed in the order in which they completed, so an inner recovery appears before
+    /// any outer recovery that contains it. Recoveries performed on a path that was later backtracked away from are
+    /// not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let item = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .recover_with(skip_then_retry_until(any().ignored(), end()));
+    /// let items = item.separated_by(just(',')).collect::<Vec<_>>();
+    ///
+    /// let (res, recoveries) = items.parse_with_recoveries("1,x2,3");
+    /// assert_eq!(res.output(), Some(&vec!["1", "2", "3"]));
+    /// assert_eq!(res.errors().len(), 1);
+    /// assert_eq!(recoveries.len(), 1);
+    /// assert_eq!(recoveries[0].span(), &SimpleSpan::new(2, 4));
+    /// assert_eq!(recoveries[0].strategy(), "skip_then_retry_until");
+    /// ```
+    fn parse_with_recoveries(&self, input: I) -> (ParseResult<O, E::Error>, Vec<Recovery<I::Span>>)
+    where
+        I: Input<'a>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        let mut state = E::State::default();
+        let mut own = InputOwn::new_state(input, &mut state);
+        let mut inp = own.as_ref_start();
+        let res = self.then_ignore(end()).go::<Emit>(&mut inp);
+        let alt = inp.errors.alt.take();
+        let failed = own.input.failure().is_some();
+        let (mut errs, recoveries) = own.into_errs_and_recoveries();
+        let out = match res {
+            Ok(out) if !failed => Some(out),
+            Ok(_) => None,
+            Err(()) => {
+                errs.push(alt.expect("error but no alt?").err);
+                None
+            }
+        };
+        (ParseResult::new(out, errs), recoveries)
+    }
+
+    /// Parse a stream of tokens like [`Parser::parse`], but give up on error recovery once `max_errors` errors have
+    /// been produced, additionally yielding whether any errors were left out of the result.
+    ///
+    /// Recovery can produce an error for every malformed item in a large input, most of which are rarely useful.
+    /// Once the limit is reached, recovery combinators (see [`Parser::recover_with`] and
+    /// [`Parser::then_skip_garbage_until`]) fail instead of recovering, as do other parsers that report errors without
+    /// failing (such as [`IterParser::collect_map`] with [`OnDuplicate::Error`](combinator::OnDuplicate::Error)), so
+    /// parsing bails out early. The result contains
+    /// at most `max_errors` errors (a limit of zero is treated as one, so that a failed parse always has an error),
+    /// and usually no output if the limit was reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let item = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .recover_with(via_parser(text::ascii::ident()));
+    /// let items = item.separated_by(just(',')).collect::<Vec<_>>();
+    ///
+    /// let (res, truncated) = items.parse_with_max_errors("1,a,b,c,5", 2);
+    /// assert_eq!(res.errors().len(), 2);
+    /// assert!(truncated);
+    ///
+    /// let (res, truncated) = items.parse_with_max_errors("1,a,b,c,5", 3);
+    /// assert_eq!(res.output(), Some(&vec!["1", "a", "b", "c", "5"]));
+    /// assert_eq!(res.errors().len(), 3);
+    /// assert!(!truncated);
+    /// ```
+    fn parse_with_max_errors(&self, input: I, max_errors: usize) -> (ParseResult<O, E::Error>, bool)
+    where
+        I: Input<'a>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        let max_errors = max_errors.max(1);
+        let mut state = E::State::default();
+        let mut own = InputOwn::new_state(input, &mut state);
+        own.errors.max = Some(max_errors);
+        let mut inp = own.as_ref_start();
+        let res = self.then_ignore(end()).go::<Emit>(&mut inp);
+        let alt = inp.errors.alt.take();
+        let failed = own.input.failure().is_some();
+        let mut errs = own.into_errs();
+        let out = match res {
+            Ok(out) if !failed => Some(out),
+            Ok(_) => None,
+            Err(()) => {
+                errs.push(alt.expect("error but no alt?").err);
+                None
+            }
+        };
+        let truncated = errs.len() > max_errors;
+        errs.truncate(max_errors);
+        (ParseResult::new(out, errs), truncated)
+    }
+
+    /// Parse a stream of tokens like [`Parser::parse`], but give up once the parser has taken more than `steps` steps.
+    ///
+    /// Grammars that backtrack heavily can take a very long time on some inputs, which is a concern when parsing
+    /// untrusted input. A step is taken by each iteration of a repeating parser (such as [`Parser::repeated`] and
+    /// [`Parser::separated_by`]) and each time a recursive parser (see [`recursive()`](recursive::recursive)) or an
+    /// operator parsed by [`Parser::pratt`] is entered, so the total amount of work done is proportional to the budget.
+    /// If the budget runs out, parsing fails with the single error created by
+    /// [`Error::budget_exceeded`](error::Error::budget_exceeded) and no output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let items = one_of::<_, _, extra::Err<Rich<char>>>('0'..='9')
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(items.parse_with_budget("1,2,3", 10).into_result(), Ok(vec!['1', '2', '3']));
+    ///
+    /// let errs = items.parse_with_budget("1,2,3,4,5", 3).into_errors();
+    /// assert_eq!(errs.len(), 1);
+    /// assert_eq!(errs[0].to_string(), "parser step budget exceeded");
+    /// assert_eq!(errs[0].span(), &SimpleSpan::new(5, 6));
+    /// ```
+    fn parse_with_budget(&self, input: I, steps: usize) -> ParseResult<O, E::Error>
+    where
+        I: Input<'a>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        let mut state = E::State::default();
+        let mut own = InputOwn::new_state(input, &mut state);
+        own.budget.remaining = Some(steps);
+        let mut inp = own.as_ref_start();
+        let res = self.then_ignore(end()).go::<Emit>(&mut inp);
+        // Whatever happened after the budget ran out, it is the reason that parsing stopped
+        if let Some(at) = inp.budget.exhausted {
+            inp.offset = at;
+            let err = inp.budget_exceeded_err();
+            return ParseResult::new(None, vec![err]);
+        }
+        let alt = inp.errors.alt.take();
+        let failed = own.input.failure().is_some();
+        let mut errs = own.into_errs();
+        let out = match res {
+            Ok(out) if !failed => Some(out),
+            Ok(_) => None,
+            Err(()) => {
+                errs.push(alt.expect("error but no alt?").err);
+                None
+            }
+        };
+        ParseResult::new(out, errs)
+    }
+
+    /// Parse a stream of tokens like [`Parser::parse`], for parsers that always produce an output by recovering from
+    /// every error, yielding the output and any errors encountered along the way.
+    ///
+    /// This is useful for tools such as editors that need a syntax tree (containing error nodes, such as those
+    /// produced by [`via_parser`]) for even the most malformed input. The parser must be total: recovery (see
+    /// [`Parser::recover_with`]) has to cover every way in which the input can be malformed, including trailing input
+    /// that the parser does not consume.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parser fails without recovering, as there is then no output to return. Use [`Parser::parse`] for
+    /// parsers that may fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Item {
+    ///     Num(u64),
+    ///     Error,
+    /// }
+    ///
+    /// let item = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .from_str()
+    ///     .unwrapped()
+    ///     .map(Item::Num)
+    ///     .recover_with(via_parser(none_of(",").repeated().at_least(1).to(Item::Error)));
+    /// let items = item.separated_by(just(',')).collect::<Vec<_>>();
+    ///
+    /// let (out, errs) = items.parse_recovery("1,x,3");
+    /// assert_eq!(out, vec![Item::Num(1), Item::Error, Item::Num(3)]);
+    /// assert_eq!(errs.len(), 1);
+    /// ```
+    #[track_caller]
+    fn parse_recovery(&self, input: I) -> (O, Vec<E::Error>)
+    where
+        I: Input<'a>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        match self.parse(input).into_output_errors() {
+            (Some(out), errs) => (out, errs),
+            (None, errs) => panic!(
+                "parser failed without recovering from an error ({} errors were produced), so no output was \
+                 available",
+                errs.len(),
+            ),
+        }
+    }
+
+    /// Parse a stream of tokens without error recovery, yielding either the output or the errors that were
+    /// encountered.
+    ///
+    /// Recovery combinators (see [`Parser::recover_with`]) fail instead of recovering, so parsing stops at the first
+    /// error rather than spending time trying to continue past it. For parsers that do not recover from errors, the
+    /// result is the same as that of `parse(input).into_result()`.
+    ///
+    /// If parsing failed, then there will *always* be at least one item in the error `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let ints = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .recover_with(via_parser(text::ascii::ident()))
+    ///     .separated_by(just(','))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(ints.try_parse("1,2,3"), Ok(vec!["1", "2", "3"]));
+    /// // `parse` recovers from the bad item, but `try_parse` gives up at it
+    /// assert_eq!(ints.parse("1,x,3").into_output(), Some(vec!["1", "x", "3"]));
+    /// assert_eq!(ints.try_parse("1,x,3").unwrap_err().len(), 1);
+    /// ```
+    fn try_parse(&self, input: I) -> Result<O, Vec<E::Error>>
+    where
+        I: Input<'a>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        let mut state = E::State::default();
+        let mut own = InputOwn::new_state(input, &mut state);
+        // Recovery is only attempted while more errors may be emitted
+        own.errors.max = Some(0);
+        let mut inp = own.as_ref_start();
+        let res = self.then_ignore(end()).go::<Emit>(&mut inp);
+        let alt = inp.errors.alt.take();
+        let mut errs = own.into_errs();
+        match res {
+            Ok(out) if errs.is_empty() => Ok(out),
+            Ok(_) => Err(errs),
+            Err(()) => {
+                errs.push(alt.expect("error but no alt?").err);
+                Err(errs)
+            }
+        }
+    }
+
+    /// Parse a prefix of a stream of tokens, yielding the output along with the offset of the first token that was
+    /// not consumed.
+    ///
+    /// Unlike [`Parser::parse`], trailing input is not an error (see [`Parser::lazy`]). For slice-like inputs, the
+    /// offset is the number of tokens consumed (or, for [`&str`], the number of bytes consumed), so the remaining
+    /// input can be handed on to another parser by slicing at the offset.
+    ///
+    /// If parsing failed, then there will *always* be at least one item in the error `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let int = text::int::<_, _, extra::Err<Simple<char>>>(10);
+    ///
+    /// assert_eq!(int.parse_lazy("42 is the answer").into_result(), Ok(("42", 2)));
+    /// assert!(int.parse_lazy("the answer is 42").has_errors());
+    ///
+    /// // Continue with the rest of the input
+    /// let input = "42 is the answer";
+    /// let (_, offset) = int.parse_lazy(input).into_result().unwrap();
+    /// let rest = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .padded()
+    ///     .repeated()
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(rest.parse(&input[offset..]).into_result(), Ok(vec!["is", "the", "answer"]));
+    /// ```
+    fn parse_lazy(&self, input: I) -> ParseResult<(O, usize), E::Error>
+    where
+        I: Input<'a>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        let mut own = InputOwn::new(input);
+        let mut inp = own.as_ref_start();
+        let res = self.go_emit(&mut inp);
+        let offset = inp.offset;
+        let alt = inp.errors.alt.take();
+        let failed = own.input.failure().is_some();
         let mut errs = own.into_errs();
         let out = match res {
-            Ok(out) => Some(out),
+            Ok(out) if !failed => Some((out, offset.into())),
+            Ok(_) => None,
             Err(()) => {
                 errs.push(alt.expect("error but no alt?").err);
                 None
@@ -444,9 +795,11 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         let mut inp = own.as_ref_start();
         let res = self.then_ignore(end()).go::<Check>(&mut inp);
         let alt = inp.errors.alt.take();
+        let failed = own.input.failure().is_some();
         let mut errs = own.into_errs();
         let out = match res {
-            Ok(()) => Some(()),
+            Ok(()) if !failed => Some(()),
+            Ok(()) => None,
             Err(()) => {
                 errs.push(alt.expect("error but no alt?").err);
                 None
@@ -496,8 +849,15 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
 
     /// Map the output of this parser to another value.
     ///
+    /// The new value may borrow from the input, such as a `&'a str` slice of it, in which case it remains valid after
+    /// the parser itself has been dropped.
+    ///
     /// The output type of this parser is `U`, the same as the function's output.
     ///
+    /// The function is only called when the output is needed: it is skipped when the output is discarded, such as by
+    /// [`Parser::check`], [`Parser::ignored`], [`Parser::not`], or [`Parser::to_slice`]. It should therefore not be
+    /// relied upon for side effects.
+    ///
     /// # Examples
     ///
     /// ```
@@ -556,6 +916,34 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// assert_eq!(ident.parse("       hello   ").into_result(), Ok(Spanned("hello", (7..12).into())));
     /// ```
     ///
+    /// Using both the span and the slice of the output, without giving up the output itself:
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// struct Int<'a> {
+    ///     negative: bool,
+    ///     digits: &'a str,
+    ///     text: &'a str,
+    ///     span: SimpleSpan,
+    /// }
+    ///
+    /// let int = just::<_, _, extra::Err<Simple<char>>>('-')
+    ///     .or_not()
+    ///     .then(text::int(10))
+    ///     .map_with(|(sign, digits), e| Int {
+    ///         negative: sign.is_some(),
+    ///         digits,
+    ///         text: e.slice(),
+    ///         span: e.span(),
+    ///     });
+    ///
+    /// assert_eq!(
+    ///     int.parse("-42").into_result(),
+    ///     Ok(Int { negative: true, digits: "42", text: "-42", span: (0..3).into() }),
+    /// );
+    /// ```
+    ///
     /// Using the parser state in the mapping function to intern strings:
     ///
     /// ```
@@ -681,10 +1069,58 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Flatten an output of the form `((a, b), c)`, as produced by chaining [`Parser::then`] twice, into `(a, b, c)`.
+    ///
+    /// Where possible, prefer [`group`], which produces a flat tuple to begin with.
+    ///
+    /// The output type of this parser is `(A, B, C)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let digit = any::<_, extra::Err<Simple<char>>>().filter(char::is_ascii_digit);
+    /// let triple = digit.then(digit).then(digit).unnest2();
+    ///
+    /// assert_eq!(triple.parse("123").into_result(), Ok(('1', '2', '3')));
+    /// ```
+    fn unnest2<A, B, C>(self) -> Map<Self, O, fn(O) -> (A, B, C)>
+    where
+        Self: Sized,
+        O: Nested<(A, B, C)>,
+    {
+        self.map(O::unnest)
+    }
+
+    /// Flatten an output of the form `(((a, b), c), d)`, as produced by chaining [`Parser::then`] three times, into
+    /// `(a, b, c, d)`.
+    ///
+    /// Where possible, prefer [`group`], which produces a flat tuple to begin with.
+    ///
+    /// The output type of this parser is `(A, B, C, D)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let digit = any::<_, extra::Err<Simple<char>>>().filter(char::is_ascii_digit);
+    /// let quad = digit.then(digit).then(digit).then(digit).unnest3();
+    ///
+    /// assert_eq!(quad.parse("1234").into_result(), Ok(('1', '2', '3', '4')));
+    /// ```
+    fn unnest3<A, B, C, D>(self) -> Map<Self, O, fn(O) -> (A, B, C, D)>
+    where
+        Self: Sized,
+        O: Nested<(A, B, C, D)>,
+    {
+        self.map(O::unnest)
+    }
+
     /// Transform the output of this parser to the pattern's span.
     ///
     /// This is commonly used when you know what pattern you've parsed and are only interested in the span of the
-    /// pattern.
+    /// pattern. As with [`Parser::to_slice`], the pattern's own output is never generated, so no time is spent
+    /// building it.
     ///
     /// The output type of this parser is `I::Span`.
     ///
@@ -736,7 +1172,8 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// as a parsing error.
     ///
     /// If you wish parsing of this pattern to continue when an error is generated instead of halting, consider using
-    /// [`Parser::validate`] instead.
+    /// [`Parser::validate`] instead. For example, an element of [`Parser::repeated`] that fails to map ends the
+    /// repetition there, and the error it produced is reported rather than a partial output.
     ///
     /// The output type of this parser is `U`, the [`Ok`] return value of the function.
     ///
@@ -836,6 +1273,46 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         Memoized { parser: self }
     }
 
+    /// Memoize the parser like [`Parser::memoized`], growing the result of left-recursive calls so that
+    /// [left-recursive](https://en.wikipedia.org/wiki/Left_recursion) rules can be written directly.
+    ///
+    /// When the parser recursively calls itself at the same position, the inner call initially fails. The parser is
+    /// then run again with the inner call producing the previous result (the 'seed'), and again, for as long as each
+    /// attempt gets further into the input than the last. This gives left-recursive rules their natural,
+    /// left-associative meaning, and each attempt reuses the previous one rather than reparsing it, so parsing is
+    /// linear in the length of the chain.
+    ///
+    /// Seeds are cloned, so the output type must implement [`Clone`]. It must also be `'static`, so outputs cannot
+    /// borrow from the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let num = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .from_str::<i64>()
+    ///     .unwrapped();
+    ///
+    /// // expr = expr '-' num | num
+    /// let expr = recursive(|expr| {
+    ///     expr.then_ignore(just('-'))
+    ///         .then(num)
+    ///         .map(|(l, r)| l - r)
+    ///         .or(num)
+    ///         .memoized_left_recursive()
+    /// });
+    ///
+    /// // Subtraction is left-associative: `(10 - 3) - 2`
+    /// assert_eq!(expr.parse("10-3-2").into_result(), Ok(5));
+    /// ```
+    #[cfg(feature = "memoization")]
+    fn memoized_left_recursive(self) -> MemoizedLeftRecursive<Self>
+    where
+        Self: Sized,
+    {
+        MemoizedLeftRecursive { parser: self }
+    }
+
     /// Transform all outputs of this parser to a predetermined value.
     ///
     /// The output type of this parser is `U`, the type of the predetermined value.
@@ -988,6 +1465,30 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Parse a pattern, then require that the end of the input follows it.
+    ///
+    /// This is shorthand for `.then_ignore(end())`, and produces the same error if there is input left over after the
+    /// pattern.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::Simple};
+    /// let number = text::int::<_, _, extra::Err<Simple<char>>>(10).complete();
+    ///
+    /// assert_eq!(number.parse("42").into_result(), Ok("42"));
+    /// // Without `complete`, the trailing input would be silently ignored by a larger parser
+    /// assert!(number.parse("42abc").has_errors());
+    /// ```
+    fn complete(self) -> ThenIgnore<Self, End<I, E>, (), E>
+    where
+        Self: Sized,
+    {
+        self.then_ignore(end())
+    }
+
     /// Parse input as part of a token-tree - using an input generated from within the current
     /// input. In other words, this parser will attempt to create a *new* input stream from within
     /// the one it is being run on, and the parser it was called on will be provided this *new* input.
@@ -1092,8 +1593,11 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
-    /// Parse one thing and then another thing, creating the second parser from the result of
-    /// the first. If you don't need the context in the output, prefer [`Parser::ignore_with_ctx`].
+    /// Parse one thing and then another thing, using the output of the first as the context of the second. If you
+    /// don't need the context in the output, prefer [`Parser::ignore_with_ctx`].
+    ///
+    /// Unlike [`Parser::then_with`], the second parser is built once and only *configured* by the context (see
+    /// [`ConfigParser::configure`]), so no new parser needs to be created (or boxed) for each parse.
     ///
     /// The output of this parser is `(E::Context, O)`,
     /// a combination of the context and the output of the parser.
@@ -1102,6 +1606,22 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// recovery then the second produces an error, the primary error will point to the location in
     /// the second parser which failed, ignoring that the first parser may be the root cause. There
     /// may be other pathological errors cases as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::Simple};
+    /// // The first character of the input chooses the delimiter used to separate the items that follow it
+    /// let delimiter = just(',').configure(|cfg, ctx: &char| cfg.seq(*ctx));
+    /// let items = text::ascii::ident()
+    ///     .separated_by(delimiter)
+    ///     .collect::<Vec<_>>();
+    /// let list = one_of::<_, _, extra::Err<Simple<char>>>(",;|").then_with_ctx(items);
+    ///
+    /// assert_eq!(list.parse(";a;b;c").into_result(), Ok((';', vec!["a", "b", "c"])));
+    /// assert_eq!(list.parse("|a|b").into_result(), Ok(('|', vec!["a", "b"])));
+    /// assert!(list.parse(";a,b").has_errors()); // ',' isn't the chosen delimiter
+    /// ```
     fn then_with_ctx<U, P>(
         self,
         then: P,
@@ -1118,6 +1638,47 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Parse one thing and then use its output and span to build the parser for another thing.
+    ///
+    /// This is useful for contextual constructs where what comes next depends on what came before, such as
+    /// requiring text to be aligned with the end of a previous token. `f` is called each time the first parser
+    /// succeeds, so building the second parser should be cheap. For context that only needs to configure an existing
+    /// parser, prefer [`Parser::then_with_ctx`].
+    ///
+    /// The output type of this parser is `U`, the output of the second parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // A heading must be underlined by exactly as many `=` as it has characters
+    /// let heading = any::<_, extra::Err<Simple<char>>>()
+    ///     .and_is(text::newline().not())
+    ///     .repeated()
+    ///     .at_least(1)
+    ///     .to_slice()
+    ///     .then_with_span(|title: &str, span: SimpleSpan| {
+    ///         text::newline()
+    ///             .ignore_then(just('=').repeated().exactly(span.end - span.start))
+    ///             .to(title)
+    ///     });
+    ///
+    /// assert_eq!(heading.parse("Hello\n=====").into_result(), Ok("Hello"));
+    /// assert!(heading.parse("Hello\n===").has_errors());
+    /// ```
+    fn then_with_span<U, P, F>(self, f: F) -> ThenWithSpan<Self, F, O>
+    where
+        Self: Sized,
+        F: Fn(O, I::Span) -> P,
+        P: Parser<'a, I, U, E>,
+    {
+        ThenWithSpan {
+            parser: self,
+            then: f,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Run the previous contextual parser with the provided context
     ///
     /// ```
@@ -1255,6 +1816,47 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         C: Parser<'a, I, V, E>,
     {
         DelimitedBy {
+            parser: self,
+            start,
+            end,
+            recover_unclosed: false,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Parse the pattern surrounded by the given delimiters, yielding the span of each delimiter alongside the
+    /// output of the inner pattern.
+    ///
+    /// This is useful for tooling that needs to know where the delimiters themselves are, such as bracket matching,
+    /// without having to capture their spans separately with [`Parser::map_with`].
+    ///
+    /// The output type of this parser is `(I::Span, O, I::Span)`: the span of the opening delimiter, the output of
+    /// the original parser, and the span of the closing delimiter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let group = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .padded()
+    ///     .delimited_by_spanned(just('('), just(')'));
+    ///
+    /// assert_eq!(
+    ///     group.parse("( 42 )").into_result(),
+    ///     Ok((SimpleSpan::new(0, 1), "42", SimpleSpan::new(5, 6))),
+    /// );
+    /// ```
+    fn delimited_by_spanned<U, V, B, C>(
+        self,
+        start: B,
+        end: C,
+    ) -> DelimitedBySpanned<Self, B, C, U, V>
+    where
+        Self: Sized,
+        B: Parser<'a, I, U, E>,
+        C: Parser<'a, I, V, E>,
+    {
+        DelimitedBySpanned {
             parser: self,
             start,
             end,
@@ -1302,8 +1904,21 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// used is left unspecified, and is not part of the crate's semver guarantees, although regressions in error
     /// quality should be reported in the issue tracker of the main repository.
     ///
+    /// The second parser is only tried if the first fails without consuming any input. Once the first parser has
+    /// consumed input, it is committed to: if it then fails, its error is produced without trying the second. This
+    /// avoids repeating work when the first parser failing partway through means that the second cannot succeed. Use
+    /// [`Or::or_rewinding`] to always backtrack and try the second parser instead.
+    ///
+    /// A parser has consumed input if it fails after some part of it has succeeded, like `a.then(b)` when `a` matched
+    /// but `b` did not, or [`Parser::padded`] after skipping whitespace. Primitives such as [`just`], [`one_of`],
+    /// [`any`] and [`choice_trie`], along with [`Parser::filter`] and [`Parser::try_map`], leave the input where they
+    /// started when they fail, so they never count as having consumed input, however much of it they looked at:
+    /// `just("->")` failing on `-` does not commit, and neither does [`text::ascii::keyword`] failing on `letter`.
+    ///
     /// Please note that long chains of [`Parser::or`] combinators have been known to result in poor compilation times.
-    /// If you feel you are experiencing this, consider using [`choice`] instead.
+    /// If you feel you are experiencing this, consider using [`choice`] instead. Note that [`choice`] always
+    /// backtracks, trying each parser in turn whether or not the previous ones consumed input, so it corresponds to a
+    /// chain of [`Parser::or`] in which each link uses [`Or::or_rewinding`].
     ///
     /// The output type of this parser is `O`, the output of both parsers.
     ///
@@ -1326,7 +1941,9 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         B: Parser<'a, I, O, E>,
     {
         Or {
-            choice: choice((self, other)),
+            first: self,
+            second: other,
+            rewinding: false,
         }
     }
 
@@ -1358,6 +1975,67 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         OrNot { parser: self }
     }
 
+    /// Attempt to parse something, producing a default value if it does not exist.
+    ///
+    /// Unlike [`Parser::or_not`], this parser uses committed choice: the default is only produced if the pattern fails
+    /// without consuming any input. If the pattern consumes some input before failing, the error is propagated
+    /// instead, since the input is clearly an attempt at the pattern rather than its absence. The default never
+    /// consumes anything.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let sign = one_of::<_, _, extra::Err<Simple<char>>>("+-").or_value('+');
+    /// let signed = sign.then(text::int(10));
+    ///
+    /// assert_eq!(signed.parse("-42").into_result(), Ok(('-', "42")));
+    /// assert_eq!(signed.parse("42").into_result(), Ok(('+', "42")));
+    /// ```
+    fn or_value(self, default: O) -> OrValue<Self, O>
+    where
+        Self: Sized,
+        O: Clone,
+    {
+        OrValue {
+            parser: self,
+            fallback: default,
+        }
+    }
+
+    /// Attempt to parse something, producing [`Default::default`] if it does not exist.
+    ///
+    /// This is like [`Parser::or_value`], including its committed choice: if the pattern succeeds, its output is used
+    /// as-is, and if it fails without consuming any input, the default is produced without consuming anything. If the
+    /// pattern consumes some input before failing, the error is propagated instead.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let indent = just::<_, _, extra::Err<Simple<char>>>("indent=")
+    ///     .ignore_then(text::int(10).from_str::<u32>().unwrapped())
+    ///     .or_default();
+    ///
+    /// assert_eq!(indent.parse("indent=4").into_result(), Ok(4));
+    /// assert_eq!(indent.parse("").into_result(), Ok(0));
+    /// ```
+    #[doc(alias = "optional_default")]
+    fn or_default(self) -> OrDefault<Self, O>
+    where
+        Self: Sized,
+        O: Default,
+    {
+        OrValue {
+            parser: self,
+            fallback: O::default,
+        }
+    }
+
     /// Invert the result of the contained parser, failing if it succeeds and succeeding if it fails.
     /// The output of this parser is always `()`, the unit type.
     ///
@@ -1432,6 +2110,10 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// Input is eagerly parsed. Be aware that the parser will accept no occurrences of the pattern too. Consider using
     /// [`Repeated::at_least`] instead if you wish to parse a minimum number of elements.
     ///
+    /// The pattern should consume input whenever it succeeds: otherwise, it would match forever. If it succeeds without
+    /// consuming anything, debug builds panic with the location of the offending `repeated` call, while release builds
+    /// stop repeating at that point.
+    ///
     /// The output type of this parser is, by default, `()`. If you want to collect the items into a [`Container`]
     /// (such as a [`Vec`]), use [`IterParser::collect`].
     ///
@@ -1461,12 +2143,110 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
             parser: self,
             at_least: 0,
             at_most: !0,
+            reserved: 0,
+            too_few: (),
+            too_many: (),
+            #[cfg(debug_assertions)]
+            location: *Location::caller(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Parse a pattern zero or more times, stopping when `terminator` would match.
+    ///
+    /// Before each instance of the pattern, the terminator is tried as a lookahead. If it matches, repetition stops
+    /// and the terminator is left unconsumed for whatever comes next to deal with. If the terminator appears
+    /// immediately, no instances of the pattern are parsed. Otherwise, the pattern must match: unlike
+    /// [`Parser::repeated`], failing to parse an instance before reaching the terminator is an error.
+    ///
+    /// The output type of this parser is, by default, `()`. If you want to collect the items into a [`Container`]
+    /// (such as a [`Vec`]), use [`IterParser::collect`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let stmt = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .then_ignore(just(';'))
+    ///     .padded();
+    /// let block = just('{')
+    ///     .ignore_then(stmt.repeated_until(just('}')).collect::<Vec<_>>())
+    ///     .then_ignore(just('}'));
+    ///
+    /// assert_eq!(block.parse("{ a; b; }").into_result(), Ok(vec!["a", "b"]));
+    /// assert_eq!(block.parse("{}").into_result(), Ok(vec![]));
+    /// assert!(block.parse("{ a; b }").has_errors());
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn repeated_until<T, OT>(self, terminator: T) -> RepeatedUntil<Self, T, O, OT, I, E>
+    where
+        Self: Sized,
+        T: Parser<'a, I, OT, E>,
+    {
+        RepeatedUntil {
+            parser: self,
+            terminator,
             #[cfg(debug_assertions)]
             location: *Location::caller(),
             phantom: EmptyPhantom::new(),
         }
     }
 
+    /// Parse a pattern zero or more times, collecting the outputs into a [`Vec`].
+    ///
+    /// This is shorthand for `.repeated().collect::<Vec<_>>()`. It always succeeds, producing an empty [`Vec`] if the
+    /// pattern does not appear at all.
+    ///
+    /// The output type of this parser is `Vec<O>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let digits = any::<_, extra::Err<Simple<char>>>()
+    ///     .filter(char::is_ascii_digit)
+    ///     .list_or_empty();
+    ///
+    /// assert_eq!(digits.parse("123").into_result(), Ok(vec!['1', '2', '3']));
+    /// assert_eq!(digits.parse("").into_result(), Ok(vec![]));
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn list_or_empty(self) -> Collect<Repeated<Self, O, I, E>, O, Vec<O>>
+    where
+        Self: Sized,
+    {
+        self.repeated().collect()
+    }
+
+    /// Parse a pattern one or more times, collecting the outputs into a [`Vec`].
+    ///
+    /// This is shorthand for `.repeated().at_least(1).collect::<Vec<_>>()`. If the pattern does not appear at all,
+    /// parsing fails with the error produced by the first attempt at the pattern, which describes what was expected.
+    /// Use [`Repeated::at_least_with`] to produce a custom error instead.
+    ///
+    /// The output type of this parser is `Vec<O>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let digits = one_of::<_, _, extra::Err<Rich<char>>>('0'..='2').nonempty_list();
+    ///
+    /// assert_eq!(digits.parse("120").into_result(), Ok(vec!['1', '2', '0']));
+    /// // The error describes the pattern that was expected
+    /// assert_eq!(
+    ///     digits.parse("").into_result().unwrap_err()[0].expected().len(),
+    ///     3,
+    /// );
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn nonempty_list(self) -> Collect<Repeated<Self, O, I, E>, O, Vec<O>>
+    where
+        Self: Sized,
+    {
+        self.repeated().at_least(1).collect()
+    }
+
     /// Parse a pattern, separated by another, any number of times.
     ///
     /// You can use [`SeparatedBy::allow_leading`] or [`SeparatedBy::allow_trailing`] to allow leading or trailing
@@ -1499,6 +2279,7 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
             separator,
             at_least: 0,
             at_most: !0,
+            too_few: (),
             allow_leading: false,
             allow_trailing: false,
             #[cfg(debug_assertions)]
@@ -1507,6 +2288,78 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Parse a pattern any number of times, separated by any one of several separators.
+    ///
+    /// `separators` is a tuple (or array) of parsers with the same output type, which are tried in order between each
+    /// pair of elements like [`choice`]. Only one separator is accepted between two elements: two separators in a row
+    /// are an error unless [`SeparatedBy::allow_empty`] is used.
+    ///
+    /// This is shorthand for `.separated_by(choice(separators))`, so all of the configuration methods of
+    /// [`SeparatedBy`] are available.
+    ///
+    /// The output type of this parser can be any [`Container`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// // Elements separated by commas or spaces, like `[1 2, 3]`
+    /// let list = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .separated_by_any((just(',').padded().ignored(), text::inline_whitespace().at_least(1)))
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just('['), just(']'));
+    ///
+    /// assert_eq!(list.parse("[1 2, 3]").into_result(), Ok(vec!["1", "2", "3"]));
+    /// assert!(list.parse("[1,,2]").has_errors());
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn separated_by_any<U, S>(self, separators: S) -> SeparatedBy<Self, Choice<S>, O, U, I, E>
+    where
+        Self: Sized,
+        Choice<S>: Parser<'a, I, U, E>,
+    {
+        self.separated_by(choice(separators))
+    }
+
+    /// Parse a flat sequence of one or more operands (this parser) separated by infix operators, without building a
+    /// syntax tree.
+    ///
+    /// The output type of this parser is `(Vec<O>, Vec<U>)`: every operand in the order in which they appeared and
+    /// every operator between them, so there is always exactly one fewer operator than there are operands. No
+    /// precedence or associativity is applied, which is useful for tools that resolve them in a later pass (for
+    /// example, because operators are user-defined). Use [`Parser::pratt`] to build a tree instead.
+    ///
+    /// Parsing stops before any operator that isn't followed by an operand, leaving it unconsumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let int = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .from_str::<u32>()
+    ///     .unwrapped();
+    /// let op = one_of("+-*/");
+    ///
+    /// let expr = int.infix_flat(op);
+    ///
+    /// assert_eq!(expr.parse("1+2*3").into_result(), Ok((vec![1, 2, 3], vec!['+', '*'])));
+    /// assert_eq!(expr.parse("42").into_result(), Ok((vec![42], vec![])));
+    /// assert!(expr.parse("1+").has_errors());
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn infix_flat<U, B>(self, operator: B) -> InfixFlat<Self, B>
+    where
+        Self: Sized,
+        B: Parser<'a, I, U, E>,
+    {
+        InfixFlat {
+            parser: self,
+            operator,
+            #[cfg(debug_assertions)]
+            location: *Location::caller(),
+        }
+    }
+
     /// Left-fold the output of the parser into a single value.
     ///
     /// The output of the original parser must be of type `(A, impl IntoIterator<Item = B>)`.
@@ -1635,6 +2488,67 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Left-fold a sequence of operators and right-hand operands into a single value, giving the fold function the
+    /// span of each operator.
+    ///
+    /// This parses the original parser, followed by `operator` and `other` as a pair zero or more times, like
+    /// `self.foldl(operator.then(other).repeated(), ...)`. The fold function is given the value folded so far, the
+    /// output of the operator, its span, and the output of the right-hand operand. This is useful for building
+    /// left-associative syntax trees in which each node records where its operator appeared.
+    ///
+    /// If an operator is not followed by an operand, neither is consumed and the fold ends before the operator.
+    ///
+    /// The output type of this parser is `O`, the output of the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Expr {
+    ///     Num(u64),
+    ///     Sub(Box<Expr>, SimpleSpan, Box<Expr>),
+    /// }
+    ///
+    /// let num = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .from_str()
+    ///     .unwrapped()
+    ///     .map(Expr::Num)
+    ///     .padded();
+    ///
+    /// let expr = num.foldl_with_span(just('-'), num, |lhs, _, op_span, rhs| {
+    ///     Expr::Sub(Box::new(lhs), op_span, Box::new(rhs))
+    /// });
+    ///
+    /// assert_eq!(
+    ///     expr.parse("3 - 2").into_result(),
+    ///     Ok(Expr::Sub(Box::new(Expr::Num(3)), (2..3).into(), Box::new(Expr::Num(2)))),
+    /// );
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn foldl_with_span<Op, B, F, OOp, OB>(
+        self,
+        operator: Op,
+        other: B,
+        f: F,
+    ) -> FoldlWithSpan<F, Self, Op, B, OOp, OB, E>
+    where
+        Op: Parser<'a, I, OOp, E>,
+        B: Parser<'a, I, OB, E>,
+        F: Fn(O, OOp, I::Span, OB) -> O,
+        Self: Sized,
+    {
+        FoldlWithSpan {
+            parser_a: self,
+            operator,
+            parser_b: other,
+            folder: f,
+            #[cfg(debug_assertions)]
+            location: *Location::caller(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Parse a pattern. Afterwards, the input stream will be rewound to its original state, as if parsing had not
     /// occurred.
     ///
@@ -1667,6 +2581,12 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// Make the parser lazy, such that it parses as much as it validly can and then finished successfully, leaving
     /// trailing input untouched.
     ///
+    /// More precisely, a lazy parser does not require that all input be consumed: the inner parser runs exactly once,
+    /// exactly as it would otherwise, and whatever input remains after it succeeds is skipped. The inner parser is
+    /// never retried, so a parser that can match nothing (such as [`empty`]) succeeds immediately rather than looping.
+    ///
+    /// If you need to know where the inner parser stopped, use [`Parser::parse_lazy`] instead.
+    ///
     /// The output type of this parser is `O`, the same as the original parser.
     ///
     /// # Examples
@@ -1688,7 +2608,13 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         self.then_ignore(any().repeated())
     }
 
-    /// Parse a pattern, ignoring any amount of whitespace both before and after the pattern.
+    /// Observe the amount of input consumed by this parser each time it is invoked, without otherwise affecting it.
+    ///
+    /// After every invocation, the observer is called with the number of tokens (or, for [`&str`], bytes) the parser
+    /// advanced by and whether it succeeded. This is primarily useful for debugging and profiling: for example, to
+    /// find rules that are invoked a surprising number of times, or that frequently fail after consuming a lot of
+    /// input and so cause expensive backtracking. After a failed parse, the amount reported is however far the
+    /// parser got before giving up.
     ///
     /// The output type of this parser is `O`, the same as the original parser.
     ///
@@ -1696,33 +2622,144 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     ///
     /// ```
     /// # use chumsky::prelude::*;
-    /// let ident = text::ascii::ident::<_, _, extra::Err<Simple<char>>>().padded();
+    /// # use std::cell::RefCell;
+    /// let consumed = RefCell::new(Vec::new());
+    /// let word = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .observe(|n, ok| consumed.borrow_mut().push((n, ok)))
+    ///     .padded()
+    ///     .repeated()
+    ///     .collect::<Vec<_>>();
     ///
-    /// // A pattern with no whitespace surrounding it is accepted
-    /// assert_eq!(ident.parse("hello").into_result(), Ok("hello"));
-    /// // A pattern with arbitrary whitespace surrounding it is also accepted
-    /// assert_eq!(ident.parse(" \t \n  \t   world  \t  ").into_result(), Ok("world"));
+    /// assert_eq!(word.parse("hello world").into_result(), Ok(vec!["hello", "world"]));
+    /// assert_eq!(*consumed.borrow(), vec![(5, true), (5, true), (0, false)]);
     /// ```
-    fn padded(self) -> Padded<Self>
+    fn observe<F>(self, observer: F) -> Observe<Self, F>
     where
         Self: Sized,
-        I: Input<'a>,
-        I::Token: Char,
+        F: Fn(usize, bool),
     {
-        Padded { parser: self }
+        Observe {
+            parser: self,
+            observer,
+        }
     }
 
-    // /// Flatten a nested collection.
-    // ///
-    // /// This use-cases of this method are broadly similar to those of [`Iterator::flatten`].
-    // ///
-    // /// The output type of this parser is `Vec<T>`, where the original parser output was
-    // /// `impl IntoIterator<Item = impl IntoIterator<Item = T>>`.
-    // fn flatten<T, Inner>(self) -> Map<Self, O, fn(O) -> Vec<T>>
-    // where
-    //     Self: Sized,
-    //     O: IntoIterator<Item = Inner>,
-    //     Inner: IntoIterator<Item = T>,
+    /// Limit how deeply recursive parsers may be nested within this parser.
+    ///
+    /// Each time a [`recursive`] parser or a [pratt](Parser::pratt) operand is entered counts as one level of
+    /// nesting. If parsing would go more than `max_depth` levels deep, the innermost attempt fails with an error
+    /// created by [`Error::nesting_too_deep`](error::Error::nesting_too_deep) instead. This lets grammars with
+    /// unbounded nesting (parentheses, nested arrays, etc.) reject pathological input gracefully rather than
+    /// exhausting the stack or the heap.
+    ///
+    /// This is usually applied to the top-level parser. When limits are nested, the tightest one applies.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let parens = recursive::<_, _, extra::Err<Rich<char>>, _, _>(|parens| {
+    ///     parens.delimited_by(just('('), just(')')).or(empty())
+    /// })
+    /// .with_max_depth(8);
+    ///
+    /// assert!(!parens.parse("((()))").has_errors());
+    ///
+    /// let errs = parens.parse("((((((((()))))))))").into_errors();
+    /// assert_eq!(errs[0].to_string(), "nesting too deep");
+    /// ```
+    fn with_max_depth(self, max_depth: usize) -> WithMaxDepth<Self>
+    where
+        Self: Sized,
+    {
+        WithMaxDepth {
+            parser: self,
+            max_depth,
+        }
+    }
+
+    /// Report, via `sink`, each time this parser is entered and whether it succeeded or failed.
+    ///
+    /// This is intended for tracing the behaviour of a grammar during development and does not affect parsing in any
+    /// way. Each report is a line of text beginning with `label`, and includes the offset at which the parser was
+    /// entered and, if it succeeded, the range of offsets that it consumed. The sink decides where the reports go:
+    /// `|args| eprintln!("{args}")` prints them, for example.
+    ///
+    /// Parsers are often entered many times while backtracking, so a failure is not necessarily an error.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use std::cell::RefCell;
+    ///
+    /// let log = RefCell::new(Vec::new());
+    /// let sink = |args: core::fmt::Arguments| log.borrow_mut().push(args.to_string());
+    ///
+    /// let int = text::int::<_, _, extra::Err<Simple<char>>>(10).debug("int", sink);
+    /// let ints = int.separated_by(just(',')).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(ints.parse("1,23").into_result(), Ok(vec!["1", "23"]));
+    /// assert_eq!(
+    ///     log.into_inner(),
+    ///     [
+    ///         "int: entered at 0",
+    ///         "int: succeeded, consuming 0..1",
+    ///         "int: entered at 2",
+    ///         "int: succeeded, consuming 2..4",
+    ///     ],
+    /// );
+    /// ```
+    fn debug<F: Fn(fmt::Arguments)>(self, label: &'static str, sink: F) -> DebugParser<Self, F>
+    where
+        Self: Sized,
+    {
+        DebugParser {
+            parser: self,
+            label,
+            sink,
+        }
+    }
+
+    /// Parse a pattern, ignoring any amount of whitespace both before and after the pattern.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let ident = text::ascii::ident::<_, _, extra::Err<Simple<char>>>().padded();
+    ///
+    /// // A pattern with no whitespace surrounding it is accepted
+    /// assert_eq!(ident.parse("hello").into_result(), Ok("hello"));
+    /// // A pattern with arbitrary whitespace surrounding it is also accepted
+    /// assert_eq!(ident.parse(" \t \n  \t   world  \t  ").into_result(), Ok("world"));
+    /// ```
+    fn padded(self) -> Padded<Self>
+    where
+        Self: Sized,
+        I: Input<'a>,
+        I::Token: Char,
+    {
+        Padded { parser: self }
+    }
+
+    // /// Flatten a nested collection.
+    // ///
+    // /// This use-cases of this method are broadly similar to those of [`Iterator::flatten`].
+    // ///
+    // /// The output type of this parser is `Vec<T>`, where the original parser output was
+    // /// `impl IntoIterator<Item = impl IntoIterator<Item = T>>`.
+    // fn flatten<T, Inner>(self) -> Map<Self, O, fn(O) -> Vec<T>>
+    // where
+    //     Self: Sized,
+    //     O: IntoIterator<Item = Inner>,
+    //     Inner: IntoIterator<Item = T>,
     // {
     //     self.map(|xs| xs.into_iter().flat_map(|xs| xs.into_iter()).collect())
     // }
@@ -1739,6 +2776,9 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// run into cases where valid syntax fails to parse without errors, this might be happening: consider removing
     /// error recovery or switching to a more specific error recovery strategy.
     ///
+    /// Several strategies may be given as a tuple, such as `(insert(';'), skip_until(...))`. They are tried in order,
+    /// each starting from the input at which the parser failed, until one of them recovers.
+    ///
     /// The output type of this parser is `O`, the same as the original parser.
     ///
     /// # Examples
@@ -1788,6 +2828,83 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// After this parser succeeds, skip any tokens up to (but not including) the next input that matches `sync`,
+    /// recording the skipped tokens as an error.
+    ///
+    /// This is useful for parsing a sequence of items where one item being followed by junk shouldn't spoil the
+    /// items that come after it. Unlike [`Parser::recover_with`], the parser itself has already succeeded, so the
+    /// error acts as a warning: the output is still produced, and the skipped region is reported by
+    /// [`Parser::parse_with_recoveries`] with the strategy name `"then_skip_garbage_until"`. If no sync point is found,
+    /// the rest of the input is skipped. Nothing is skipped or reported if the parser is already followed by `sync`.
+    /// Once the limit set by [`Parser::parse_with_max_errors`] has been reached, junk is an error and this parser fails.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let stmt = text::ascii::keyword::<_, _, _, extra::Err<Simple<char>>>("let")
+    ///     .padded()
+    ///     .ignore_then(text::ascii::ident())
+    ///     .then_skip_garbage_until(just(';'))
+    ///     .then_ignore(just(';'));
+    /// let stmts = stmt.padded().repeated().collect::<Vec<_>>();
+    ///
+    /// let res = stmts.parse("let x; let y = oops; let z;");
+    /// assert_eq!(res.output(), Some(&vec!["x", "y", "z"]));
+    /// assert_eq!(res.errors().len(), 1);
+    /// assert_eq!(res.errors().next().unwrap().span(), &SimpleSpan::new(12, 19));
+    /// ```
+    fn then_skip_garbage_until<U, OU>(self, sync: U) -> ThenSkipGarbageUntil<Self, U, OU>
+    where
+        Self: Sized,
+        U: Parser<'a, I, OU, E>,
+    {
+        ThenSkipGarbageUntil {
+            parser: self,
+            sync,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Parse one thing and then another thing like [`Parser::then`], recovering with a default value if the second
+    /// thing fails.
+    ///
+    /// If `other` fails, its error is recorded and `default` takes the place of its output, so the output of this
+    /// parser is still produced. Any input that `other` got through before failing is skipped, and parsing continues
+    /// from the point at which it failed. If this parser fails, no recovery takes place. Recoveries are reported by
+    /// [`Parser::parse_with_recoveries`] with the strategy name `"then_recover"`.
+    ///
+    /// The output type of this parser is `(O, U)`, a combination of the outputs of both parsers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let field = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .then_ignore(just(':').padded())
+    ///     .then_recover(text::int(10), "0");
+    ///
+    /// assert_eq!(field.parse("x: 1").into_result(), Ok(("x", "1")));
+    ///
+    /// let (out, errs) = field.parse("x:").into_output_errors();
+    /// assert_eq!(out, Some(("x", "0")));
+    /// assert_eq!(errs.len(), 1);
+    /// ```
+    fn then_recover<U, B>(self, other: B, default: U) -> ThenRecover<Self, B, U>
+    where
+        Self: Sized,
+        B: Parser<'a, I, U, E>,
+        U: Clone,
+    {
+        ThenRecover {
+            parser: self,
+            other,
+            default,
+        }
+    }
+
     /// Map the primary error of this parser to another value.
     ///
     /// This function is most useful when using a custom error type, allowing you to augment errors according to
@@ -1806,6 +2923,42 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Map the primary error of this parser to a new primary error along with any number of other errors.
+    ///
+    /// This is useful for desugaring a composite error into several separate diagnostics. The parser still fails
+    /// with the first error produced by `f`, and the others are recorded before it, so they appear before it in the
+    /// result.
+    ///
+    /// The output type of this parser is `O`, the same as the original parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let digit = one_of::<_, _, extra::Err<Rich<char>>>("01")
+    ///     .flat_map_err(|e| (
+    ///         Rich::custom(*e.span(), "expected a binary digit"),
+    ///         vec![Rich::custom(*e.span(), "binary digits are written with only 0 and 1")],
+    ///     ));
+    ///
+    /// let errs = digit.parse("2").into_errors();
+    /// assert_eq!(
+    ///     errs.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+    ///     vec!["binary digits are written with only 0 and 1", "expected a binary digit"],
+    /// );
+    /// ```
+    // TODO: Map E -> D, not E -> E
+    fn flat_map_err<F>(self, f: F) -> FlatMapErr<Self, F>
+    where
+        Self: Sized,
+        F: Fn(E::Error) -> (E::Error, Vec<E::Error>),
+    {
+        FlatMapErr {
+            parser: self,
+            mapper: f,
+        }
+    }
+
     // /// Map the primary error of this parser to another value, making use of the span from the start of the attempted
     // /// to the point at which the error was encountered.
     // ///
@@ -1829,10 +2982,25 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// Map the primary error of this parser to another value, making use of the parser state.
     ///
     /// This function is useful for augmenting errors to allow them to include context in non context-free
-    /// languages, or provide contextual notes on possible causes.
+    /// languages, or provide contextual notes on possible causes. The mapping function is only called if the parser
+    /// fails, and is given the span from the start of the attempted pattern to the point at which the error occurred.
     ///
     /// The output type of this parser is `O`, the same as the original parser.
     ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let int = text::int::<_, _, extra::Full<Rich<char>, &str, ()>>(10).map_err_with_state(
+    ///     |_, span, file: &mut &str| Rich::custom(span, format!("{file}: expected an integer")),
+    /// );
+    ///
+    /// let res = int.parse_with_state("x", &mut "main.txt");
+    /// assert_eq!(
+    ///     res.into_errors(),
+    ///     vec![Rich::custom((0..0).into(), "main.txt: expected an integer")],
+    /// );
+    /// ```
     // TODO: Map E -> D, not E -> E
     fn map_err_with_state<F>(self, f: F) -> MapErrWithState<Self, F>
     where
@@ -1991,6 +3159,47 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         self.map(|o| o.as_ref().parse())
     }
 
+    /// Attempt to convert the output of this parser into something else using Rust's [`FromStr`] trait, emitting an
+    /// error and recovering if the conversion fails.
+    ///
+    /// Unlike `.from_str().unwrapped()`, which panics, a failed conversion (such as an integer literal too large for
+    /// its type) produces an [`Error::invalid_literal`] error at the span of the literal. Parsing then continues with
+    /// [`U::default()`](Default::default) in place of the value. To recover with a different value or to produce a
+    /// different error, use [`Parser::from_str`] with [`Parser::validate`].
+    ///
+    /// The output type of this parser is `U`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let int64 = text::int::<_, _, extra::Err<Rich<char>>>(10).from_str_or_error::<i64>();
+    ///
+    /// assert_eq!(int64.parse("42").into_result(), Ok(42));
+    ///
+    /// let (out, errs) = int64.parse("99999999999999999999").into_output_errors();
+    /// assert_eq!(out, Some(0));
+    /// assert_eq!(errs[0].to_string(), "number too large to fit in target type");
+    /// assert_eq!(errs[0].span(), &SimpleSpan::new(0, 20));
+    /// ```
+    #[allow(clippy::wrong_self_convention, clippy::type_complexity)]
+    fn from_str_or_error<U>(
+        self,
+    ) -> Validate<Self, O, fn(O, &mut MapExtra<'a, '_, I, E>, &mut Emitter<E::Error>) -> U>
+    where
+        Self: Sized,
+        U: FromStr + Default,
+        U::Err: fmt::Display,
+        O: AsRef<str>,
+    {
+        self.validate(|o, e, emitter| {
+            o.as_ref().parse().unwrap_or_else(|err| {
+                emitter.emit(E::Error::invalid_literal(err, e.span()));
+                U::default()
+            })
+        })
+    }
+
     /// For parsers that produce a [`Result`] as their output, unwrap the result (panicking if an [`Err`] is
     /// encountered).
     ///
@@ -2139,11 +3348,52 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         ParserSealed::boxed(self)
     }
 
+    /// Box the parser like [`Parser::boxed`], but without requiring it to be [`Send`] or [`Sync`] when the `sync`
+    /// feature is enabled.
+    ///
+    /// With the `sync` feature, [`Boxed`] parsers can be shared between threads, so parsers that capture
+    /// thread-local values (such as an [`Rc`](alloc::rc::Rc)) cannot be boxed with [`Parser::boxed`]. A [`BoxedLocal`]
+    /// parser can only be used on the thread that created it, but accepts any parser. Without the `sync` feature,
+    /// the two are equivalent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use std::{cell::RefCell, rc::Rc};
+    ///
+    /// let seen = Rc::new(RefCell::new(Vec::new()));
+    /// let word = {
+    ///     let seen = seen.clone();
+    ///     text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///         .map(move |w: &str| {
+    ///             seen.borrow_mut().push(w.to_uppercase());
+    ///             w
+    ///         })
+    ///         .boxed_local()
+    /// };
+    /// let words = word.padded().repeated().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(words.parse("a b c").into_result(), Ok(vec!["a", "b", "c"]));
+    /// assert_eq!(*seen.borrow(), ["A", "B", "C"]);
+    /// ```
+    fn boxed_local<'b>(self) -> BoxedLocal<'a, 'b, I, O, E>
+    where
+        Self: Sized + 'a + 'b,
+    {
+        BoxedLocal {
+            inner: alloc::rc::Rc::new(self),
+        }
+    }
+
     /// Use [Pratt parsing](https://en.wikipedia.org/wiki/Operator-precedence_parser#Pratt_parsing) to ergonomically
     /// parse this pattern separated by prefix, postfix, and infix operators of various associativites and precedence.
     ///
     /// Pratt parsing is a powerful technique and is recommended when writing parsers for expressions.
     ///
+    /// Any kind of operator may be left out. With only prefix operators, such as for Lisp-like notations, no attempt
+    /// is made to parse anything after each operand.
+    ///
     /// # Example
     ///
     /// See the documentation in [`pratt`] for more extensive examples and details.
@@ -2180,7 +3430,47 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     where
         Self: Sized,
     {
-        pratt::Pratt { atom: self, ops }
+        pratt::Pratt {
+            atom: self,
+            ops,
+            recovery: (),
+            padding: (),
+            groups: (),
+        }
+    }
+
+    /// Like [`Parser::pratt`], but also output the associativity (and so the binding power) of the outermost
+    /// operator of the expression, or `None` if the expression is a lone atom.
+    ///
+    /// This is shorthand for `.pratt(ops).with_prec()` (see [`pratt::Pratt::with_prec`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use chumsky::pratt::*;
+    ///
+    /// let int = text::int::<_, _, extra::Err<Rich<char>>>(10)
+    ///     .from_str()
+    ///     .unwrapped()
+    ///     .padded();
+    ///
+    /// let expr = int.pratt_with_prec((
+    ///     prefix(2, just('-'), |x: i64| -x),
+    ///     infix(left(1), just('*'), |l, r| l * r),
+    ///     infix(left(0), just('+'), |l, r| l + r),
+    /// ));
+    ///
+    /// assert_eq!(expr.parse("1 + 2 * 3").into_result(), Ok((7, Some(left(0)))));
+    /// assert_eq!(expr.parse("-5").into_result(), Ok((-5, Some(Associativity::Left(2)))));
+    /// assert_eq!(expr.parse("5").into_result(), Ok((5, None)));
+    /// ```
+    #[cfg(feature = "pratt")]
+    fn pratt_with_prec<Ops>(self, ops: Ops) -> pratt::PrattWithPrec<Self, Ops>
+    where
+        Self: Sized,
+    {
+        self.pratt(ops).with_prec()
     }
 }
 
@@ -2310,6 +3600,9 @@ where
     /// This is commonly useful for collecting parsers that output many values into containers of various kinds:
     /// [`Vec`]s, [`String`]s, or even [`HashMap`]s. This method is analogous to [`Iterator::collect`].
     ///
+    /// Each output is pushed into the container as soon as it is parsed, so collecting `char`s, `&str`s or `String`s
+    /// into a [`String`] builds it directly, without an intermediate [`Vec`].
+    ///
     /// The output type of this iterable parser is `C`, the type being collected into.
     ///
     /// # Examples
@@ -2335,6 +3628,50 @@ where
         }
     }
 
+    /// Collect key-value pairs produced by this iterable parser into a map, such as a [`HashMap`] or a
+    /// [`BTreeMap`](alloc::collections::BTreeMap), deciding what to do with repeated keys according to
+    /// `on_duplicate` (see [`OnDuplicate`]).
+    ///
+    /// Each item must be of the form `((key, key_span), value)`, where `key_span` is used to report the location of a
+    /// repeated key when its use is an error. The span can be captured with [`Parser::map_with`].
+    ///
+    /// The output type of this iterable parser is `C`, the map being collected into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, combinator::OnDuplicate};
+    /// # use std::collections::HashMap;
+    /// let key = text::ascii::ident::<_, _, extra::Err<Rich<char>>>().map_with(|key, e| (key, e.span()));
+    /// let entry = key.then_ignore(just(':')).then(text::int(10)).padded();
+    /// let object = entry
+    ///     .separated_by(just(','))
+    ///     .collect_map::<_, _, HashMap<_, _>>(OnDuplicate::Error)
+    ///     .delimited_by(just('{'), just('}'));
+    ///
+    /// let res = object.parse("{width:4, height:3, width:5}");
+    /// // The first value of a repeated key is kept, but the repetition is an error
+    /// assert_eq!(res.output(), Some(&HashMap::from([("width", "4"), ("height", "3")])));
+    /// assert_eq!(
+    ///     res.into_errors(),
+    ///     vec![Rich::custom((20..25).into(), "duplicate key")],
+    /// );
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn collect_map<K, V, C>(self, on_duplicate: OnDuplicate) -> CollectMap<Self, K, V, C>
+    where
+        Self: Sized + IterParser<'a, I, ((K, I::Span), V), E>,
+        C: MapContainer<K, V>,
+    {
+        CollectMap {
+            parser: self,
+            on_duplicate,
+            #[cfg(debug_assertions)]
+            location: *Location::caller(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Collect this iterable parser into a [`ContainerExactly`].
     ///
     /// This is useful for situations where the number of items to consume is statically known.
@@ -2364,6 +3701,39 @@ where
         }
     }
 
+    /// Collect this iterable parser into its first element and a [`Vec`] of the rest, failing if there are no
+    /// elements.
+    ///
+    /// This is useful when later code requires at least one element, since the output type guarantees it. The
+    /// error produced when there are no elements is reported at the point where the first element was expected.
+    ///
+    /// The output type of this iterable parser is `(O, Vec<O>)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let path = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .separated_by(just("::"))
+    ///     .collect_non_empty();
+    ///
+    /// assert_eq!(path.parse("std::io").into_result(), Ok(("std", vec!["io"])));
+    /// assert_eq!(path.parse("x").into_result(), Ok(("x", vec![])));
+    /// assert!(path.parse("").has_errors());
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn collect_non_empty(self) -> CollectNonEmpty<Self, O>
+    where
+        Self: Sized,
+    {
+        CollectNonEmpty {
+            parser: self,
+            #[cfg(debug_assertions)]
+            location: *Location::caller(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Collect this iterable parser into a [`usize`], outputting the number of elements that were parsed.
     ///
     /// This is sugar for [`.collect::<usize>()`](Self::collect).
@@ -2387,25 +3757,63 @@ where
         self.collect()
     }
 
-    /// Enumerate outputs of this iterable parser.
+    /// Call a function with each output of this iterable parser as it is parsed, without collecting them.
     ///
-    /// This function behaves in a similar way to [`Iterator::enumerate`].
+    /// This is useful for processing large inputs as a stream, such as writing out each record of a file as soon as it
+    /// has been parsed. Since the function is called as parsing happens, it is also called for the outputs of a
+    /// pattern that is later backtracked out of, such as one branch of [`Parser::or`] that fails part-way through.
     ///
-    /// The output type of this iterable parser is `(usize, O)`.
+    /// The output type of this parser is `()`.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use chumsky::{prelude::*, error::Simple};
-    /// let word = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    /// # use chumsky::prelude::*;
+    /// # use std::cell::Cell;
+    /// let total = Cell::new(0);
+    /// let sum = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .from_str::<u32>()
+    ///     .unwrapped()
     ///     .padded()
-    ///     .repeated() // This parser is iterable (i.e: implements `IterParser`)
-    ///     .enumerate()
-    ///     .collect::<Vec<(usize, &str)>>();
+    ///     .repeated()
+    ///     .for_each(|x| total.set(total.get() + x));
     ///
-    /// assert_eq!(word.parse("hello world").into_result(), Ok(vec![(0, "hello"), (1, "world")]));
+    /// sum.parse("1 2 3 4").into_result().unwrap();
+    /// assert_eq!(total.get(), 10);
     /// ```
-    fn enumerate(self) -> Enumerate<Self, O>
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn for_each<F: Fn(O)>(self, f: F) -> ForEach<Self, O, F>
+    where
+        Self: Sized,
+    {
+        ForEach {
+            parser: self,
+            f,
+            #[cfg(debug_assertions)]
+            location: *Location::caller(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    /// Enumerate outputs of this iterable parser.
+    ///
+    /// This function behaves in a similar way to [`Iterator::enumerate`].
+    ///
+    /// The output type of this iterable parser is `(usize, O)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::Simple};
+    /// let word = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+    ///     .padded()
+    ///     .repeated() // This parser is iterable (i.e: implements `IterParser`)
+    ///     .enumerate()
+    ///     .collect::<Vec<(usize, &str)>>();
+    ///
+    /// assert_eq!(word.parse("hello world").into_result(), Ok(vec![(0, "hello"), (1, "world")]));
+    /// ```
+    fn enumerate(self) -> Enumerate<Self, O>
     where
         Self: Sized,
     {
@@ -2641,6 +4049,33 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::boxed_local`].
+///
+/// Like [`Boxed`], the inner value is an [`Rc`](alloc::rc::Rc), so cloning is cheap.
+pub struct BoxedLocal<'a, 'b, I: Input<'a>, O, E: ParserExtra<'a, I>> {
+    inner: alloc::rc::Rc<dyn Parser<'a, I, O, E> + 'b>,
+}
+
+impl<'a, 'b, I: Input<'a>, O, E: ParserExtra<'a, I>> Clone for BoxedLocal<'a, 'b, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, 'b, I, O, E> ParserSealed<'a, I, O, E> for BoxedLocal<'a, 'b, I, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        M::invoke(&*self.inner, inp)
+    }
+
+    go_extra!(O);
+}
+
 impl<'a, I, O, E, T> ParserSealed<'a, I, O, E> for ::alloc::boxed::Box<T>
 where
     I: Input<'a>,
@@ -2698,6 +4133,10 @@ where
 /// Internally, [`select!`] is very similar to a single-token [`Parser::filter`] and thinking of it as such might make
 /// it less confusing.
 ///
+/// Since patterns aren't tokens, the error produced for an unmatched input reports the token that was found but can't
+/// list the patterns that were expected. If you want diagnostics to name what was expected (such as "expected a
+/// literal"), give the parser a label with [`Parser::labelled`].
+///
 /// `select!` requires that tokens implement [`Clone`] and the input type implements [`ValueInput`]. If you're trying
 /// to access tokens referentially (for the sake of nested parsing, or simply because you want to avoid cloning the
 /// token), see [`select_ref!`].
@@ -2825,6 +4264,57 @@ macro_rules! select_ref {
     });
 }
 
+/// Parse a sequence of parsers one after another, like [`group`](primitive::group), keeping only some of their
+/// outputs.
+///
+/// Each parser whose output should be discarded is prefixed with `_`. The output of the resulting parser is a flat
+/// tuple of the outputs of the remaining parsers, in order. This avoids the nested tuples produced by chains of
+/// [`Parser::then`], [`Parser::ignore_then`], and [`Parser::then_ignore`].
+///
+/// The output is always a tuple, even if only one output is kept (`(O,)`) or none are (`()`). Discarded parsers are
+/// run as if by [`Parser::ignored`], so their outputs are never generated.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let ident = text::ascii::ident::<_, _, extra::Err<Simple<char>>>();
+/// let int = text::int(10).from_str::<u32>().unwrapped();
+///
+/// // `let x = 42;`
+/// let binding = seq_keep!(
+///     _ text::ascii::keyword("let").padded(),
+///     ident,
+///     _ just('=').padded(),
+///     int,
+///     _ just(';'),
+/// );
+///
+/// assert_eq!(binding.parse("let x = 42;").into_result(), Ok(("x", 42)));
+/// ```
+#[macro_export]
+macro_rules! seq_keep {
+    // Discard the output of the next parser
+    (@munch [$($parsers:expr,)*] [$($pats:tt)*] [$($kept:ident)*] _ $p:expr $(, $($rest:tt)*)?) => {
+        $crate::seq_keep!(
+            @munch [$($parsers,)* $crate::Parser::ignored($p),] [$($pats)* _,] [$($kept)*] $($($rest)*)?
+        )
+    };
+    // Keep the output of the next parser
+    (@munch [$($parsers:expr,)*] [$($pats:tt)*] [$($kept:ident)*] $p:expr $(, $($rest:tt)*)?) => {
+        $crate::seq_keep!(@munch [$($parsers,)* $p,] [$($pats)* out,] [$($kept)* out] $($($rest)*)?)
+    };
+    (@munch [$($parsers:expr,)*] [$($pats:tt)*] [$($kept:ident)*]) => {
+        $crate::Parser::map(
+            $crate::primitive::group(($($parsers,)*)),
+            |($($pats)*)| ($($kept,)*),
+        )
+    };
+    ($($args:tt)+) => {
+        $crate::seq_keep!(@munch [] [] [] $($args)+)
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -3029,155 +4519,3039 @@ mod tests {
     }
 
     #[test]
-    fn unicode_str() {
-        let input = "🄯🄚🹠🴎🄐🝋🰏🄂🬯🈦g🸵🍩🕔🈳2🬙🨞🅢🭳🎅h🵚🧿🏩🰬k🠡🀔🈆🝹🤟🉗🴟📵🰄🤿🝜🙘🹄5🠻🡉🱖🠓";
-        let mut own = crate::input::InputOwn::<_, extra::Default>::new(input);
-        let mut inp = own.as_ref_start();
+    fn unnest() {
+        fn parser<'a>() -> impl Parser<'a, &'a str, (char, &'a str, char, char)> {
+            just('a')
+                .then(just("bc"))
+                .then(just('d'))
+                .then(just('e'))
+                .unnest3()
+        }
 
-        while let Some(_c) = inp.next() {}
+        assert_eq!(
+            parser().parse("abcde").into_result(),
+            Ok(('a', "bc", 'd', 'e'))
+        );
+        assert!(parser().parse("abcd").has_errors());
+
+        // A nested tuple element is left alone
+        let nested = just::<_, _, extra::Default>('a')
+            .then(just('b'))
+            .then(just('c'))
+            .then(just('d'))
+            .unnest2();
+        assert_eq!(
+            nested.parse("abcd").into_result(),
+            Ok((('a', 'b'), 'c', 'd'))
+        );
     }
 
     #[test]
-    fn iter() {
-        use crate::prelude::*;
-
-        fn parser<'a>() -> impl IterParser<'a, &'a str, char> {
-            any().repeated()
+    fn recovery_events() {
+        fn parser<'a>() -> impl Parser<'a, &'a str, Vec<char>, extra::Err<Rich<'a, char>>> {
+            let item = one_of("abc").recover_with(via_parser(any().to('?')));
+            item.separated_by(just(','))
+                .collect()
+                .delimited_by(just('['), just(']'))
+                .recover_with(skip_until(any().ignored(), end(), Vec::new))
         }
 
-        let mut chars = String::new();
-        for c in parser().parse_iter("abcdefg").into_result().unwrap() {
-            chars.push(c);
-        }
+        let (res, recoveries) = parser().parse_with_recoveries("[a,x,b,y]");
+        assert_eq!(res.output(), Some(&vec!['a', '?', 'b', '?']));
+        assert_eq!(
+            recoveries
+                .iter()
+                .map(|r| (*r.span(), r.strategy()))
+                .collect::<Vec<_>>(),
+            vec![
+                (SimpleSpan::new(3, 4), "via_parser"),
+                (SimpleSpan::new(7, 8), "via_parser"),
+            ],
+        );
 
-        assert_eq!(&chars, "abcdefg");
+        let (res, recoveries) = parser().parse_with_recoveries("]");
+        assert_eq!(res.output(), Some(&Vec::new()));
+        assert_eq!(recoveries.len(), 1);
+        assert_eq!(recoveries[0].span(), &SimpleSpan::new(0, 1));
+        assert_eq!(recoveries[0].strategy(), "skip_until");
+
+        let (res, recoveries) = parser().parse_with_recoveries("[a,b]");
+        assert!(!res.has_errors());
+        assert!(recoveries.is_empty());
     }
 
     #[test]
-    #[cfg(feature = "memoization")]
-    fn exponential() {
-        use crate::prelude::*;
+    fn recovery_events_backtracked() {
+        let recovering = just::<_, _, extra::Default>('a')
+            .recover_with(via_parser(just('b')))
+            .then(just('!'));
+        let parser = recovering.to('x').or(just("b?").to('y')).or_rewinding();
+
+        let (res, recoveries) = parser.parse_with_recoveries("b?");
+        assert_eq!(res.into_result(), Ok('y'));
+        assert!(recoveries.is_empty());
+    }
 
-        fn parser<'a>() -> impl Parser<'a, &'a str, String> {
-            recursive(|expr| {
-                let atom = any()
-                    .filter(|c: &char| c.is_alphabetic())
-                    .repeated()
-                    .at_least(1)
-                    .collect()
-                    .or(expr.delimited_by(just('('), just(')')));
+    #[test]
+    fn parse_string() {
+        let src = String::from("x = 42");
+        let assign = || {
+            text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+                .then_ignore(just('=').padded())
+                .then(text::int(10).to_slice())
+        };
 
-                atom.clone()
-                    .then_ignore(just('+'))
-                    .then(atom.clone())
-                    .map(|(a, b)| format!("{}{}", a, b))
-                    .memoized()
-                    .or(atom)
-            })
-            .then_ignore(end())
+        // The input type is inferred from the argument, with slices borrowing from the string
+        assert_eq!(assign().parse(&src).into_result(), Ok(("x", "42")));
+        let bad = String::from("x = y");
+        let errs = assign().parse(&bad).into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::new(4, 5));
+
+        // A parser that was already written for `&str` accepts a `&String` just the same
+        fn str_parser<'a>(
+        ) -> impl Parser<'a, &'a str, (&'a str, &'a str), extra::Err<Rich<'a, char>>> {
+            text::ascii::ident()
+                .then_ignore(just('=').padded())
+                .then(text::int(10))
         }
+        assert_eq!(str_parser().parse(&src).into_result(), Ok(("x", "42")));
+    }
 
-        parser()
-            .parse("((((((((((((((((((((((((((((((a+b))))))))))))))))))))))))))))))")
-            .into_result()
-            .unwrap();
+    #[test]
+    fn parse_lazy() {
+        let word = any::<_, extra::Default>()
+            .filter(|c: &char| c.is_alphabetic())
+            .repeated()
+            .to_slice();
+
+        assert_eq!(
+            word.parse_lazy("hello world").into_result(),
+            Ok(("hello", 5))
+        );
+        assert_eq!(word.parse_lazy("hello").into_result(), Ok(("hello", 5)));
+        // A parser that matches nothing consumes nothing, and does not loop
+        assert_eq!(word.parse_lazy("123").into_result(), Ok(("", 0)));
+        assert_eq!(word.lazy().parse("123").into_result(), Ok(""));
+        assert_eq!(
+            empty::<&str, extra::Default>()
+                .lazy()
+                .parse("abc")
+                .into_result(),
+            Ok(())
+        );
+
+        let pair = just::<_, &[u8], extra::Default>(b'a').then(just(b'b'));
+        assert_eq!(
+            pair.parse_lazy(b"abcd").into_result(),
+            Ok(((b'a', b'b'), 2))
+        );
+        assert!(pair.parse_lazy(b"acbd").has_errors());
     }
 
     #[test]
-    #[cfg(feature = "memoization")]
-    fn left_recursive() {
-        use crate::prelude::*;
+    fn observe() {
+        use core::cell::RefCell;
 
-        fn parser<'a>() -> impl Parser<'a, &'a str, String> {
-            recursive(|expr| {
-                let atom = any()
-                    .filter(|c: &char| c.is_alphabetic())
-                    .repeated()
-                    .at_least(1)
-                    .collect();
+        let observed = RefCell::new(Vec::new());
+        let digits = one_of::<_, _, extra::Default>('0'..='9')
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .observe(|n, ok| observed.borrow_mut().push((n, ok)));
+        let parser = digits.separated_by(just(',')).collect::<Vec<_>>();
 
-                let sum = expr
-                    .clone()
-                    .then_ignore(just('+'))
-                    .then(expr)
-                    .map(|(a, b)| format!("{}{}", a, b))
-                    .memoized();
+        assert_eq!(
+            parser.parse("12,345,6").into_result(),
+            Ok(vec!["12", "345", "6"])
+        );
+        assert_eq!(*observed.borrow(), vec![(2, true), (3, true), (1, true)]);
 
-                sum.or(atom)
-            })
+        // Observing a parser does not change what it parses
+        observed.borrow_mut().clear();
+        assert!(parser.parse("12,x").has_errors());
+        assert_eq!(*observed.borrow(), vec![(2, true), (0, false)]);
+    }
+
+    #[test]
+    fn collect_map() {
+        use crate::combinator::OnDuplicate;
+        use alloc::collections::BTreeMap;
+
+        let entries = |on_duplicate| {
+            text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+                .map_with(|key, e| (key, e.span()))
+                .then_ignore(just(':'))
+                .then(text::int(10))
+                .padded()
+                .separated_by(just(','))
+                .collect_map::<_, _, BTreeMap<_, _>>(on_duplicate)
+        };
+        let map = |entries: &[(&'static str, &'static str)]| {
+            entries.iter().copied().collect::<BTreeMap<_, _>>()
+        };
+
+        let res = entries(OnDuplicate::Error).parse("a:1, b:2, a:3");
+        assert_eq!(res.output(), Some(&map(&[("a", "1"), ("b", "2")])));
+        assert_eq!(
+            res.into_errors(),
+            vec![Rich::custom((10..11).into(), "duplicate key")],
+        );
+
+        assert_eq!(
+            entries(OnDuplicate::KeepFirst)
+                .parse("a:1, b:2, a:3")
+                .into_result(),
+            Ok(map(&[("a", "1"), ("b", "2")])),
+        );
+        assert_eq!(
+            entries(OnDuplicate::KeepLast)
+                .parse("a:1, b:2, a:3")
+                .into_result(),
+            Ok(map(&[("a", "3"), ("b", "2")])),
+        );
+
+        // Entries are still checked in check mode
+        assert!(entries(OnDuplicate::Error)
+            .check("a:1, b:2, a:3")
+            .has_errors());
+        assert!(!entries(OnDuplicate::KeepLast)
+            .check("a:1, b:2, a:3")
+            .has_errors());
+    }
+
+    #[test]
+    fn parse_result_map() {
+        let item = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .from_str::<u32>()
+            .unwrapped()
+            .recover_with(via_parser(text::ascii::ident().to(0)));
+        let items = item.separated_by(just(',')).collect::<Vec<_>>();
+
+        let res = items.parse("1,x,3").map(|items| items.iter().sum::<u32>());
+        assert!(res.has_errors());
+        assert_eq!(res.output(), Some(&4));
+        assert_eq!(res.errors().len(), 1);
+        assert_eq!(res.errors().next().unwrap().span(), &SimpleSpan::new(2, 3));
+
+        let res = items.parse("1,2").map(|items| items.len());
+        assert!(!res.has_errors());
+        assert_eq!(res.into_result(), Ok(2));
+
+        // Without output, the mapping function isn't called
+        let res = items
             .then_ignore(end())
+            .parse("1,2!")
+            .map(|_| unreachable!());
+        assert_eq!(res.output(), None::<&()>);
+        assert!(res.has_errors());
+    }
+
+    #[test]
+    fn cached_iter_input() {
+        use crate::input::CachedIterInput;
+        use core::cell::Cell;
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Token {
+            Ident(&'static str),
+            Comma,
+            Colon,
+            Eq,
         }
 
-        assert_eq!(parser().parse("a+b+c").into_result().unwrap(), "abc");
+        type Tokens<'b> = CachedIterInput<Box<dyn Iterator<Item = Token> + 'b>>;
+
+        let ident = select! { Token::Ident(x) => x };
+        let names = ident
+            .separated_by(just(Token::Comma))
+            .at_least(1)
+            .collect::<Vec<_>>();
+        // Only the token after a list of names tells the two apart, so the whole list is read again
+        let decl = names
+            .clone()
+            .then_ignore(just(Token::Colon))
+            .then(ident)
+            .map(|(names, ty)| (names, Some(ty)))
+            .or(names
+                .then_ignore(just::<_, Tokens, extra::Err<Simple<_>>>(Token::Eq))
+                .map(|names| (names, None)))
+            .or_rewinding();
+
+        let tokens = [
+            Token::Ident("a"),
+            Token::Comma,
+            Token::Ident("b"),
+            Token::Comma,
+            Token::Ident("c"),
+            Token::Eq,
+        ];
+        let pulled = Cell::new(0);
+        let counted = |iter: Box<dyn Iterator<Item = Token>>| -> Tokens {
+            let iter: Box<dyn Iterator<Item = Token>> =
+                Box::new(iter.inspect(|_| pulled.set(pulled.get() + 1)));
+            CachedIterInput::from_iter(iter)
+        };
+
+        assert_eq!(
+            decl.parse(counted(Box::new(tokens.clone().into_iter())))
+                .into_result(),
+            Ok((vec!["a", "b", "c"], None)),
+        );
+        // Backtracking reuses the tokens that have already been read rather than pulling them again
+        assert_eq!(pulled.get(), tokens.len());
+
+        // Tokens past the end of the parse are never pulled
+        pulled.set(0);
+        let input = tokens
+            .clone()
+            .into_iter()
+            .chain(core::iter::repeat(Token::Comma));
+        assert_eq!(
+            decl.parse_lazy(counted(Box::new(input))).into_result(),
+            Ok(((vec!["a", "b", "c"], None), tokens.len())),
+        );
+        assert_eq!(pulled.get(), tokens.len());
     }
 
-    #[cfg(debug_assertions)]
-    mod debug_asserts {
-        use crate::prelude::*;
+    #[test]
+    #[cfg(feature = "std")]
+    fn buf_read_input() {
+        use crate::input::BufReadInput;
+        use std::io::Cursor;
+
+        #[derive(Debug, PartialEq)]
+        struct Header {
+            binary: bool,
+            width: u32,
+            height: u32,
+        }
 
-        // TODO panic when left recursive parser is detected
-        // #[test]
-        // #[should_panic]
-        // fn debug_assert_left_recursive() {
-        //     recursive(|expr| {
-        //         let atom = any::<&str, extra::Default>()
-        //             .filter(|c: &char| c.is_alphabetic())
-        //             .repeated()
-        //             .at_least(1)
-        //             .collect();
+        let num = any::<_, extra::Err<Simple<u8>>>()
+            .filter(u8::is_ascii_digit)
+            .repeated()
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .map(|digits| {
+                digits
+                    .into_iter()
+                    .fold(0, |n, d| n * 10 + (d - b'0') as u32)
+            });
+        // Trying each magic number backtracks, which must stay within the window
+        let header = choice((just(*b"P2").to(false), just(*b"P5").to(true)))
+            .then(num.padded_by(just(b' ')))
+            .then(num)
+            .then_ignore(just(b'\n'))
+            .map(|((binary, width), height)| Header {
+                binary,
+                width,
+                height,
+            });
+
+        let input = BufReadInput::new(Cursor::new(b"P5 640 480\n".to_vec()), 4);
+        assert_eq!(
+            header.parse(input).into_result(),
+            Ok(Header {
+                binary: true,
+                width: 640,
+                height: 480,
+            }),
+        );
 
-        //         let sum = expr
-        //             .clone()
-        //             .then_ignore(just('+'))
-        //             .then(expr)
-        //             .map(|(a, b)| format!("{}{}", a, b));
+        let input = BufReadInput::new(Cursor::new(b"P6 640 480\n".to_vec()), 4);
+        let errs = header.parse(input).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&b'6'));
+        assert_eq!(errs[0].span(), &SimpleSpan::new(1, 2));
+    }
 
-        //         sum.or(atom)
-        //     })
-        //     .then_ignore(end())
-        //     .parse("a+b+c");
-        // }
+    #[test]
+    #[cfg(feature = "std")]
+    fn buf_read_input_rewind_beyond_window() {
+        use crate::input::BufReadInput;
+        use std::io::Cursor;
+
+        let parser = just::<_, _, extra::Err<Rich<u8>>>(*b"abcdefgx")
+            .ignored()
+            .or(just(*b"abc").ignored())
+            .or_rewinding();
+        let input = BufReadInput::new(Cursor::new(b"abcdefgh".to_vec()), 4);
+        let errs = parser.parse(input).into_errors();
+        assert!(!errs.is_empty());
+        assert!(errs.iter().any(|err| err.span() == &SimpleSpan::new(0, 0)
+            && err.to_string().contains("cannot rewind to offset 0")));
+        assert!(errs[0]
+            .to_string()
+            .contains("consider using a larger window"));
+    }
 
-        #[test]
-        #[should_panic]
-        #[cfg(debug_assertions)]
-        fn debug_assert_collect() {
-            empty::<&str, extra::Default>()
-                .to(())
-                .repeated()
-                .collect::<()>()
-                .parse("a+b+c")
-                .unwrap();
-        }
+    #[test]
+    #[cfg(feature = "std")]
+    fn buf_read_input_rewind_beyond_window_no_output() {
+        use crate::input::BufReadInput;
+        use std::io::Cursor;
+
+        // After the failed rewind, the input looks like it has ended, which `repeated` and `end` both accept
+        let parser = just::<_, _, extra::Err<Rich<u8>>>(*b"abcdefgx")
+            .ignored()
+            .or(any().repeated())
+            .or_rewinding();
+        let res = parser.parse(BufReadInput::new(Cursor::new(b"abcdefgh".to_vec()), 4));
+        assert_eq!(res.output(), None);
+        assert!(res.has_errors());
+        let res = parser.check(BufReadInput::new(Cursor::new(b"abcdefgh".to_vec()), 4));
+        assert_eq!(res.output(), None);
+        assert!(res.has_errors());
+        let res = parser.parse_lazy(BufReadInput::new(Cursor::new(b"abcdefgh".to_vec()), 4));
+        assert_eq!(res.output(), None);
+        assert!(res.has_errors());
+    }
 
-        #[test]
-        #[should_panic]
-        #[cfg(debug_assertions)]
-        fn debug_assert_separated_by() {
-            empty::<&str, extra::Default>()
-                .to(())
-                .separated_by(empty())
-                .collect::<()>()
-                .parse("a+b+c");
+    #[test]
+    #[cfg(feature = "std")]
+    fn buf_read_input_io_error() {
+        use crate::input::BufReadInput;
+        use std::io::{self, BufReader, ErrorKind, Read};
+
+        // Produces a few bytes, then possibly fails
+        struct Source(&'static [u8], bool);
+
+        impl Read for Source {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0.is_empty() && self.1 {
+                    return Err(io::Error::new(ErrorKind::UnexpectedEof, "truncated"));
+                }
+                self.0.read(buf)
+            }
         }
 
-        #[test]
-        fn debug_assert_separated_by2() {
-            assert_eq!(
-                empty::<&str, extra::Default>()
-                    .to(())
-                    .separated_by(just(','))
-                    .count()
-                    .parse(",")
-                    .unwrap(),
-                2
-            );
-        }
+        let bytes = any::<_, extra::Err<Rich<u8>>>()
+            .repeated()
+            .collect::<Vec<_>>();
+        let input = BufReadInput::new(BufReader::new(Source(b"abc", true)), 4);
+        let (out, errs) = bytes.parse(&input).into_output_errors();
+        // The input ends at the error, but the parse is not reported as a success
+        assert_eq!(out, None);
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 3));
+        assert_eq!(errs[0].to_string(), "failed to read input: truncated");
+        assert_eq!(
+            input.take_io_error().map(|err| err.kind()),
+            Some(ErrorKind::UnexpectedEof)
+        );
+        assert!(input.take_io_error().is_none());
 
-        #[test]
-        #[should_panic]
-        #[cfg(debug_assertions)]
-        fn debug_assert_foldl() {
+        // Without an error, nothing is reported
+        let input = BufReadInput::new(BufReader::new(Source(b"abc", false)), 4);
+        assert_eq!(bytes.parse(&input).into_result(), Ok(b"abc".to_vec()));
+        assert!(input.take_io_error().is_none());
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn grapheme_input() {
+        use crate::input::GraphemeInput;
+
+        let flag = just::<_, _, extra::Err<Simple<&str>>>("🇬🇧").to_span();
+        assert_eq!(
+            flag.parse(GraphemeInput::new("🇬🇧")).into_result(),
+            Ok(SimpleSpan::new(0, 8)),
+        );
+        // Half of the flag is a different cluster
+        assert!(flag.parse(GraphemeInput::new("🇬")).has_errors());
+
+        let clusters = any::<_, extra::Err<Simple<&str>>>()
+            .repeated()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            clusters
+                .parse(GraphemeInput::new("a🇬🇧e\u{301}👍🏽\r\n"))
+                .into_result(),
+            Ok(vec!["a", "🇬🇧", "e\u{301}", "👍🏽", "\r\n"]),
+        );
+
+        let word = none_of::<_, _, extra::Err<Simple<&str>>>(" ")
+            .repeated()
+            .to_slice()
+            .separated_by(just(" "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            word.parse(GraphemeInput::new("🇬🇧🇫🇷 e\u{301}t\u{e9}"))
+                .into_result(),
+            Ok(vec!["🇬🇧🇫🇷", "e\u{301}t\u{e9}"]),
+        );
+
+        let errs = just::<_, _, extra::Err<Simple<&str>>>("a")
+            .parse(GraphemeInput::new("👍🏽"))
+            .into_errors();
+        assert_eq!(errs[0].found(), Some(&"👍🏽"));
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 8));
+    }
+
+    #[test]
+    fn collect_string() {
+        let word = any::<_, extra::Default>()
+            .filter(|c: &char| c.is_alphabetic())
+            .repeated()
+            .collect::<String>();
+        assert_eq!(word.parse("hello").into_result(), Ok("hello".to_string()));
+
+        // Pieces of a string literal, where escapes produce a `&str` of their own
+        let piece = none_of::<_, _, extra::Default>("\\\"")
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .or(just('\\').ignore_then(just('n').to("\n").or(just('"').to("\""))));
+        let string = piece
+            .repeated()
+            .collect::<String>()
+            .delimited_by(just('"'), just('"'));
+        assert_eq!(
+            string.parse(r#""say \"hi\"\n""#).into_result(),
+            Ok("say \"hi\"\n".to_string()),
+        );
+
+        let words = text::ascii::ident::<_, _, extra::Default>()
+            .map(str::to_uppercase)
+            .separated_by(just(' '))
+            .collect::<String>();
+        assert_eq!(words.parse("ab cd").into_result(), Ok("ABCD".to_string()));
+    }
+
+    #[test]
+    fn one_of_none_of() {
+        fn parses<'a, P: Parser<'a, &'a str, char, extra::Err<Rich<'a, char>>>>(
+            parser: &P,
+            c: &'a str,
+        ) -> bool {
+            !parser.parse(c).has_errors()
+        }
+
+        // Sets may be given as strings, arrays or ranges
+        let op = one_of::<_, _, extra::Err<Rich<char>>>("+-*/");
+        let bracket = one_of(['(', ')']);
+        let digit = one_of('0'..='9');
+        assert_eq!(op.parse("*").into_result(), Ok('*'));
+        assert_eq!(bracket.parse(")").into_result(), Ok(')'));
+        assert_eq!(digit.parse("7").into_result(), Ok('7'));
+        assert!(parses(&op, "+") && !parses(&op, "%"));
+        assert!(parses(&bracket, "(") && !parses(&bracket, "["));
+        assert!(parses(&digit, "0") && !parses(&digit, "a"));
+
+        // `none_of` accepts exactly the tokens that `one_of` rejects
+        let special = "\"\\";
+        let plain = none_of::<_, _, extra::Err<Rich<char>>>(special);
+        for c in ["a", "\"", " ", "\\", "\n", "é"] {
+            assert_ne!(parses(&one_of(special), c), parses(&plain, c), "{c:?}");
+        }
+
+        // `one_of` expects any of its set, `none_of` has nothing specific to expect
+        let errs = op.parse("%").into_errors();
+        assert_eq!(errs[0].found(), Some(&'%'));
+        assert_eq!(
+            errs[0]
+                .expected()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["'+'", "'-'", "'*'", "'/'"],
+        );
+        let errs = plain.parse("\"").into_errors();
+        assert_eq!(errs[0].found(), Some(&'"'));
+        assert_eq!(errs[0].expected().len(), 0);
+
+        // Neither matches the end of input
+        assert_eq!(op.parse("").into_errors()[0].found(), None);
+        assert_eq!(plain.parse("").into_errors()[0].found(), None);
+    }
+
+    #[test]
+    fn token_range() {
+        let lowercase = range::<_, _, extra::Err<Rich<char>>>('a'..='z');
+        assert_eq!(lowercase.parse("a").into_result(), Ok('a'));
+        assert_eq!(lowercase.parse("z").into_result(), Ok('z'));
+        assert_eq!(lowercase.parse("q").into_result(), Ok('q'));
+        for c in ["A", "0", "{", "`", "é"] {
+            assert!(lowercase.parse(c).has_errors(), "{c:?}");
+        }
+
+        // The end of an exclusive range is not part of the range
+        let below_five = range::<_, _, extra::Err<Rich<char>>>('0'..'5');
+        assert_eq!(below_five.parse("4").into_result(), Ok('4'));
+        assert!(below_five.parse("5").has_errors());
+        let from_x = range::<_, _, extra::Err<Rich<char>>>('x'..);
+        assert_eq!(from_x.parse("é").into_result(), Ok('é'));
+        assert!(from_x.parse("a").has_errors());
+
+        // An empty range matches nothing
+        let empty = range::<_, _, extra::Err<Rich<char>>>('b'..'b');
+        let errs = empty.parse("b").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&'b'));
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 1));
+        assert_eq!(empty.parse("").into_errors()[0].found(), None);
+
+        // Works for any ordered token, not just those that can be iterated over
+        let unit = range::<_, _, extra::Err<Simple<f64>>>(0.0..=1.0);
+        assert_eq!(unit.parse(&[0.5][..]).into_result(), Ok(0.5));
+        assert!(unit.parse(&[1.5][..]).has_errors());
+    }
+
+    #[test]
+    fn prev_token() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Token<'a> {
+            Num(&'a str),
+            Div,
+            Regex(&'a str),
+            Paren(char),
+        }
+
+        fn lexer<'a>() -> impl Parser<'a, &'a str, Vec<Token<'a>>, extra::Err<Simple<'a, char>>> {
+            // After an operand, `/` divides. Anywhere else, it starts a regex.
+            let div = crate::primitive::prev_token()
+                .filter(|prev: &Option<char>| {
+                    matches!(prev, Some(c) if c.is_ascii_digit() || *c == ')')
+                })
+                .ignore_then(just('/'))
+                .to(Token::Div);
+            let regex = none_of('/')
+                .repeated()
+                .to_slice()
+                .delimited_by(just('/'), just('/'))
+                .map(Token::Regex);
+            choice((
+                text::int(10).map(Token::Num),
+                div,
+                regex,
+                one_of("()").map(Token::Paren),
+            ))
+            .repeated()
+            .collect()
+        }
+
+        assert_eq!(
+            lexer().parse("4/2").into_result(),
+            Ok(vec![Token::Num("4"), Token::Div, Token::Num("2")]),
+        );
+        assert_eq!(
+            lexer().parse("/4/").into_result(),
+            Ok(vec![Token::Regex("4")]),
+        );
+        assert_eq!(
+            lexer().parse("(/a(b)/)/2").into_result(),
+            Ok(vec![
+                Token::Paren('('),
+                Token::Regex("a(b)"),
+                Token::Paren(')'),
+                Token::Div,
+                Token::Num("2"),
+            ]),
+        );
+
+        // Backtracking out of a branch also backtracks the previous token
+        let prev = just::<_, _, extra::Err<Simple<char>>>('a')
+            .then(just('b'))
+            .ignored()
+            .or_not()
+            .ignore_then(crate::primitive::prev_token());
+        assert_eq!(prev.parse("").into_result(), Ok(None));
+        assert_eq!(
+            prev.then_ignore(just("ac")).parse("ac").into_result(),
+            Ok(None)
+        );
+        assert_eq!(prev.parse("ab").into_result(), Ok(Some('b')));
+
+        // Tokens that span several offsets are found in full
+        let prev = just::<_, _, extra::Err<Simple<char>>>("aé")
+            .ignore_then(crate::primitive::prev_token());
+        assert_eq!(prev.parse("aé").into_result(), Ok(Some('é')));
+    }
+
+    #[test]
+    fn complete() {
+        let a = just::<_, _, extra::Err<Rich<char>>>('a').complete();
+        assert_eq!(a.parse("a").into_result(), Ok('a'));
+        assert_eq!(
+            a.parse("ab").into_errors(),
+            just::<_, _, extra::Err<Rich<char>>>('a')
+                .then_ignore(end())
+                .parse("ab")
+                .into_errors(),
+        );
+        let errs = a.parse("ab").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&'b'));
+        assert_eq!(errs[0].span(), &SimpleSpan::new(1, 2));
+        assert!(a.parse("").has_errors());
+    }
+
+    #[test]
+    fn for_each() {
+        use core::cell::Cell;
+
+        let total = Cell::new(0);
+        let count = Cell::new(0);
+        let sum = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .from_str::<u64>()
+            .unwrapped()
+            .separated_by(just(','))
+            .for_each(|x| {
+                total.set(total.get() + x);
+                count.set(count.get() + 1);
+            });
+
+        assert_eq!(sum.parse("1,20,300").into_result(), Ok(()));
+        assert_eq!((total.get(), count.get()), (321, 3));
+        // Items are still processed when the output of `for_each` itself isn't needed
+        assert_eq!(sum.ignored().parse("4000").into_result(), Ok(()));
+        assert_eq!((total.get(), count.get()), (4321, 4));
+        assert_eq!(sum.parse("").into_result(), Ok(()));
+        assert_eq!(count.get(), 4);
+        // Items before an error are processed as they are parsed
+        assert!(sum.parse("5,x").has_errors());
+        assert_eq!((total.get(), count.get()), (4326, 5));
+    }
+
+    #[test]
+    fn map_borrowed_output() {
+        #[derive(Debug, PartialEq)]
+        struct Assign<'a> {
+            name: &'a str,
+            value: &'a str,
+        }
+
+        // The parser only lives for the duration of this function, but its outputs borrow from `src`
+        fn parse_assign(src: &str) -> Option<Assign<'_>> {
+            text::ascii::ident::<_, _, extra::Default>()
+                .map(|name: &str| name)
+                .then_ignore(just('=').padded())
+                .then(text::int(10))
+                .map(|(name, value)| Assign { name, value })
+                .parse(src)
+                .into_output()
+        }
+
+        let src = String::from("width = 42");
+        let assign = parse_assign(&src).unwrap();
+        assert_eq!(
+            assign,
+            Assign {
+                name: "width",
+                value: "42"
+            }
+        );
+        // The output points into the original input, not a copy of it
+        assert!(src
+            .as_bytes()
+            .as_ptr_range()
+            .contains(&assign.name.as_ptr()));
+    }
+
+    #[test]
+    fn separated_by_allow_empty() {
+        let row = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .separated_by(just(','))
+            .allow_empty()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            row.parse("1,,3").into_result(),
+            Ok(vec![Some("1"), None, Some("3")]),
+        );
+        assert_eq!(row.parse(",2").into_result(), Ok(vec![None, Some("2")]));
+        assert_eq!(row.parse("1,").into_result(), Ok(vec![Some("1"), None]));
+        assert_eq!(row.parse(",,").into_result(), Ok(vec![None, None, None]));
+        assert_eq!(row.parse("").into_result(), Ok(vec![None]));
+        assert!(row.parse("1,x").has_errors());
+
+        // Counts include missing items
+        let pair = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .separated_by(just(','))
+            .exactly(2)
+            .allow_empty()
+            .collect::<Vec<_>>();
+        assert_eq!(pair.parse(",").into_result(), Ok(vec![None, None]));
+        assert!(pair.parse("1,2,").has_errors());
+
+        let rows = row.separated_by(just('\n')).collect::<Vec<_>>();
+        assert_eq!(
+            rows.parse("1,,3\n,5,").into_result(),
+            Ok(vec![
+                vec![Some("1"), None, Some("3")],
+                vec![None, Some("5"), None],
+            ]),
+        );
+    }
+
+    #[test]
+    fn separated_by_any() {
+        let int = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .from_str::<u32>()
+            .unwrapped();
+        let comma = just(',').padded().ignored();
+        let space = text::inline_whitespace().at_least(1);
+
+        let list = int.separated_by_any((comma, space)).collect::<Vec<_>>();
+        assert_eq!(list.parse("1 2, 3").into_result(), Ok(vec![1, 2, 3]));
+        assert_eq!(list.parse("1,2 ,3   4").into_result(), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(list.parse("").into_result(), Ok(vec![]));
+
+        // Only one separator is allowed between elements
+        assert!(list.parse("1,,2").has_errors());
+        assert!(list.parse("1, ,2").has_errors());
+        assert!(list.parse("1 2,").has_errors());
+
+        // ...unless elements may be missing
+        let sparse = int
+            .separated_by_any((comma, space))
+            .allow_empty()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sparse.parse("1,,2").into_result(),
+            Ok(vec![Some(1), None, Some(2)]),
+        );
+
+        // Configuration methods still apply
+        let trailing = int
+            .separated_by_any([just(';'), just('|')])
+            .allow_trailing()
+            .at_least(2)
+            .collect::<Vec<_>>();
+        assert_eq!(trailing.parse("1;2|").into_result(), Ok(vec![1, 2]));
+        assert!(trailing.parse("1;").has_errors());
+    }
+
+    #[test]
+    fn collect_non_empty() {
+        let list = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+            .separated_by(just(','))
+            .collect_non_empty();
+
+        assert_eq!(list.parse("a,b,c").into_result(), Ok(("a", vec!["b", "c"])),);
+        assert_eq!(list.parse("a").into_result(), Ok(("a", vec![])));
+
+        let errs = list.parse("").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 0));
+        assert_eq!(errs[0].found(), None);
+
+        // The error points at the token where the first element was expected
+        let errs = list.parse("1").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&'1'));
+
+        // Checking without output behaves the same way
+        assert!(list.check("a,b,c").into_result().is_ok());
+        assert!(list.check("").has_errors());
+    }
+
+    #[test]
+    fn separated_by_collect_vec_with_spans() {
+        let items = any::<_, extra::Err<Rich<char>>>()
+            .filter(char::is_ascii_lowercase)
+            .separated_by(just(','))
+            .allow_trailing()
+            .collect_vec_with_spans();
+
+        assert_eq!(
+            items.parse("a,b,c").into_result(),
+            Ok(vec![
+                ('a', SimpleSpan::from(0..1)),
+                ('b', SimpleSpan::from(2..3)),
+                ('c', SimpleSpan::from(4..5)),
+            ]),
+        );
+        assert_eq!(
+            items.parse("x,").into_result(),
+            Ok(vec![('x', SimpleSpan::from(0..1))])
+        );
+        assert_eq!(items.parse("").into_result(), Ok(vec![]));
+
+        // The spans can be used to point at duplicate entries
+        let dups = items.validate(|items, _, emitter| {
+            for (i, (c, span)) in items.iter().enumerate() {
+                if items[..i].iter().any(|(prev, _)| prev == c) {
+                    emitter.emit(Rich::custom(*span, "duplicate item"));
+                }
+            }
+            items
+        });
+        assert_eq!(
+            dups.parse("a,b,a,b")
+                .into_errors()
+                .into_iter()
+                .map(|e| *e.span())
+                .collect::<Vec<_>>(),
+            vec![SimpleSpan::from(4..5), SimpleSpan::from(6..7)],
+        );
+    }
+
+    #[test]
+    fn seq_keep() {
+        let digit = any::<_, extra::Err<Simple<char>>>().filter(char::is_ascii_digit);
+
+        let first_and_third = seq_keep!(digit, _ just('+'), digit, _ just('='), _ digit);
+        assert_eq!(first_and_third.parse("1+2=3").into_result(), Ok(('1', '2')));
+        assert!(first_and_third.parse("1+2=").has_errors());
+        assert!(first_and_third.parse("1-2=3").has_errors());
+
+        // A trailing comma is permitted
+        let one = seq_keep!(_ just('('), digit, _ just(')'),);
+        assert_eq!(one.parse("(7)").into_result(), Ok(('7',)));
+
+        let none = seq_keep!(_ digit, _ digit);
+        assert_eq!(none.parse("12").into_result(), Ok(()));
+
+        let all = seq_keep!(digit, just('.').to_slice(), digit);
+        assert_eq!(all.parse("4.2").into_result(), Ok(('4', ".", '2')));
+    }
+
+    #[test]
+    fn try_parse() {
+        let ints = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .delimited_by(just('['), just(']'));
+
+        for input in ["[1,2,3]", "[]", "[1,", "[1;2]", "", "[1]x"] {
+            assert_eq!(
+                ints.try_parse(input),
+                ints.parse(input).into_result(),
+                "{input:?}"
+            );
+        }
+
+        // Errors that don't stop parsing still cause a failure
+        let even = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .from_str::<u32>()
+            .unwrapped()
+            .validate(|x, e, emitter| {
+                if x % 2 != 0 {
+                    emitter.emit(Rich::custom(e.span(), "odd"))
+                }
+                x
+            });
+        assert_eq!(even.try_parse("4"), Ok(4));
+        assert_eq!(even.try_parse("3"), even.parse("3").into_result());
+
+        // Recovery is not attempted
+        let item = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .recover_with(skip_then_retry_until(any().ignored(), just(',').ignored()));
+        let items = item.separated_by(just(',')).collect::<Vec<_>>();
+        assert_eq!(items.try_parse("1,2"), Ok(vec!["1", "2"]));
+        assert!(items.parse("1,x2").into_output().is_some());
+        let errs = items.try_parse("1,x2").unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(2, 3));
+    }
+
+    #[test]
+    fn recover_with_several_strategies() {
+        // A missing `;` is only inserted at the end of a line, otherwise the rest of the statement is skipped
+        let insert_at_eol = via_parser(
+            empty()
+                .to(';')
+                .then_ignore(just('\n').ignored().or(end()).rewind()),
+        );
+        let skip_to_semi = skip_until(any().ignored(), just(';').ignored(), || ';');
+        let stmt = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+            .then_ignore(just(';').recover_with((insert_at_eol, skip_to_semi)))
+            .then_ignore(text::whitespace());
+        let stmts = stmt.repeated().collect::<Vec<_>>();
+
+        assert_eq!(stmts.parse("a; b;").into_result(), Ok(vec!["a", "b"]));
+
+        let (res, recoveries) = stmts.parse_with_recoveries("a;b\nc d;e;");
+        assert_eq!(res.output(), Some(&vec!["a", "b", "c", "e"]));
+        let errs = res.errors().collect::<Vec<_>>();
+        assert_eq!(errs.len(), 2);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 4));
+        // Insertion didn't apply, so the error is recorded where skipping began
+        assert_eq!(errs[1].span(), &SimpleSpan::new(5, 6));
+        assert_eq!(errs[1].found(), Some(&' '));
+        assert_eq!(recoveries.len(), 2);
+        assert_eq!(recoveries[0].strategy(), "via_parser");
+        assert_eq!(recoveries[1].strategy(), "skip_until");
+        assert_eq!(recoveries[1].span(), &SimpleSpan::new(5, 8));
+
+        // If no strategy recovers, the parser fails as usual
+        assert!(stmts.parse("a b").into_output().is_none());
+    }
+
+    #[test]
+    fn recover_with_insert() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Expr {
+            Num(u64),
+            Add(Box<Expr>, Box<Expr>),
+        }
+
+        let expr = recursive(|expr| {
+            let num = text::int::<_, _, extra::Err<Rich<char>>>(10)
+                .from_str()
+                .unwrapped()
+                .map(Expr::Num);
+            let atom = num
+                .or(expr.delimited_by(just('('), just(')').recover_with(insert(')'))))
+                .padded();
+            atom.clone()
+                .foldl(just('+').ignore_then(atom).repeated(), |a, b| {
+                    Expr::Add(Box::new(a), Box::new(b))
+                })
+        });
+        let add = |a, b| Expr::Add(Box::new(a), Box::new(b));
+
+        assert_eq!(
+            expr.parse("(1+2)").into_result(),
+            Ok(add(Expr::Num(1), Expr::Num(2))),
+        );
+
+        // The missing `)` is inserted at the end of the input
+        let (res, recoveries) = expr.parse_with_recoveries("(1+2");
+        assert_eq!(res.output(), Some(&add(Expr::Num(1), Expr::Num(2))));
+        let errs = res.errors().collect::<Vec<_>>();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(4, 4));
+        assert_eq!(errs[0].found(), None);
+        assert_eq!(recoveries.len(), 1);
+        assert_eq!(recoveries[0].strategy(), "insert");
+        assert_eq!(recoveries[0].span(), &SimpleSpan::new(4, 4));
+
+        // Parsing continues after the inserted token
+        let (out, errs) = expr.parse("(1+2+3").into_output_errors();
+        assert_eq!(
+            out,
+            Some(add(add(Expr::Num(1), Expr::Num(2)), Expr::Num(3))),
+        );
+        assert_eq!(errs.len(), 1);
+        let (out, errs) = expr.parse("(1+(2 + 3").into_output_errors();
+        assert_eq!(
+            out,
+            Some(add(Expr::Num(1), add(Expr::Num(2), Expr::Num(3)))),
+        );
+        assert_eq!(errs.len(), 2);
+    }
+
+    #[test]
+    fn parse_recovery() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Expr {
+            Num(u64),
+            List(Vec<Expr>),
+            Error,
+        }
+
+        let expr = recursive(|expr| {
+            let num = text::int::<_, _, extra::Err<Simple<char>>>(10)
+                .from_str()
+                .unwrapped()
+                .map(Expr::Num);
+            let list = expr
+                .separated_by(just(','))
+                .collect()
+                .delimited_by(just('['), just(']'))
+                .map(Expr::List)
+                .recover_with(via_parser(nested_delimiters('[', ']', [], |_| Expr::Error)));
+            num.or(list).recover_with(via_parser(
+                none_of(",[]").repeated().at_least(1).to(Expr::Error),
+            ))
+        });
+        // Trailing input is skipped so that the parser is total
+        let tree = expr.then_skip_garbage_until(end());
+
+        let (out, errs) = tree.parse_recovery("[1,?,[2,3],[4 5],6]");
+        assert_eq!(
+            out,
+            Expr::List(vec![
+                Expr::Num(1),
+                Expr::Error,
+                Expr::List(vec![Expr::Num(2), Expr::Num(3)]),
+                Expr::Error,
+                Expr::Num(6),
+            ]),
+        );
+        assert_eq!(errs.len(), 2);
+
+        let (out, errs) = tree.parse_recovery("[1,2]");
+        assert_eq!(out, Expr::List(vec![Expr::Num(1), Expr::Num(2)]));
+        assert!(errs.is_empty());
+
+        let (out, errs) = tree.parse_recovery("[1]]]");
+        assert_eq!(out, Expr::List(vec![Expr::Num(1)]));
+        assert_eq!(errs.len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_recovery_failure() {
+        just::<_, _, extra::Default>('a').parse_recovery("b");
+    }
+
+    #[test]
+    fn parse_with_max_errors() {
+        let item = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .recover_with(via_parser(text::ascii::ident()));
+        let items = item.separated_by(just(',')).collect::<Vec<_>>();
+        let input = format!("1{}", ",x".repeat(50));
+        let input = input.as_str();
+
+        assert_eq!(items.parse(input).into_errors().len(), 50);
+
+        let (res, truncated) = items.parse_with_max_errors(input, 10);
+        assert!(truncated);
+        assert!(!res.has_output());
+        assert_eq!(res.errors().len(), 10);
+        // The errors that were kept are the first ones
+        assert_eq!(res.errors().next().unwrap().span(), &SimpleSpan::new(2, 3));
+
+        let (res, truncated) = items.parse_with_max_errors(input, 50);
+        assert!(!truncated);
+        assert_eq!(res.output().map(Vec::len), Some(51));
+        assert_eq!(res.errors().len(), 50);
+
+        // Junk after an item also counts towards the limit
+        let stmt = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+            .then_skip_garbage_until(just(';'))
+            .then_ignore(just(';'));
+        let stmts = stmt.repeated().collect::<Vec<_>>();
+        let (res, truncated) = stmts.parse_with_max_errors("a!;b!;c!;", 2);
+        assert!(truncated);
+        assert!(!res.has_output());
+        assert_eq!(res.errors().len(), 2);
+
+        // So do errors that are reported without failing, such as duplicate keys
+        let key =
+            text::ascii::ident::<_, _, extra::Err<Rich<char>>>().map_with(|key, e| (key, e.span()));
+        let object = key
+            .then_ignore(just(':'))
+            .then(text::int(10))
+            .separated_by(just(','))
+            .collect_map::<_, _, alloc::collections::BTreeMap<_, _>>(
+                crate::combinator::OnDuplicate::Error,
+            );
+        assert_eq!(object.parse("a:1,a:2,a:3").errors().len(), 2);
+        let (res, truncated) = object.parse_with_max_errors("a:1,a:2,a:3", 1);
+        assert!(truncated);
+        assert!(!res.has_output());
+        assert_eq!(res.errors().len(), 1);
+    }
+
+    #[test]
+    fn parse_with_budget() {
+        let input = "(".repeat(64) + "x" + &")".repeat(64) + &"x".repeat(10_000);
+        let input = input.as_str();
+        // Exponential backtracking: every group is parsed twice at each level of nesting
+        let expr = recursive(|expr| {
+            let group = expr.delimited_by(just('('), just(')'));
+            let item = group
+                .clone()
+                .then_ignore(just('!'))
+                .or(group)
+                .or_rewinding()
+                .or(just::<_, _, extra::Err<Rich<char>>>('x').ignored());
+            item.repeated().at_least(1)
+        });
+
+        let errs = expr.parse_with_budget(input, 1_000).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].to_string(), "parser step budget exceeded");
+
+        // A small input within the budget is unaffected
+        let res = expr.parse_with_budget("(x)!x", 1_000);
+        assert_eq!(res.into_result(), Ok(()));
+        let errs = expr.parse_with_budget("(x)!y", 1_000).into_errors();
+        assert_ne!(errs[0].to_string(), "parser step budget exceeded");
+
+        // The budget also bounds linear work on large inputs
+        let many = just::<_, _, extra::Err<Rich<char>>>('x').repeated();
+        let input = "x".repeat(100_000);
+        assert!(many
+            .parse_with_budget(input.as_str(), 100_001)
+            .into_result()
+            .is_ok());
+        let errs = many.parse_with_budget(input.as_str(), 10).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(10, 11));
+    }
+
+    #[test]
+    fn collect_reserves_known_count() {
+        // A length-prefixed list, where the count is only known at runtime
+        let list = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .from_str::<usize>()
+            .unwrapped()
+            .then_ignore(just(':'))
+            .ignore_with_ctx(
+                any()
+                    .repeated()
+                    .configure(|cfg, n: &usize| cfg.exactly(*n))
+                    .collect::<Vec<_>>(),
+            );
+        let out = list.parse("5:abcde").into_result().unwrap();
+        assert_eq!(out, ['a', 'b', 'c', 'd', 'e']);
+        assert!(out.capacity() >= 5);
+        assert!(list.parse("5:abcd").has_errors());
+
+        let pair = any::<_, extra::Err<Simple<char>>>()
+            .repeated()
+            .exactly(2)
+            .collect::<Vec<_>>();
+        assert!(pair.parse("ab").into_result().unwrap().capacity() >= 2);
+
+        // Only the minimum is reserved
+        let some = any::<_, extra::Err<Simple<char>>>()
+            .repeated()
+            .at_least(3)
+            .collect::<Vec<_>>();
+        assert!(some.parse("abc").into_result().unwrap().capacity() >= 3);
+
+        // An explicit reservation is made even when the count is unbounded, through `enumerate`
+        let hinted = any::<_, extra::Err<Simple<char>>>()
+            .repeated()
+            .reserve(16)
+            .enumerate()
+            .collect::<Vec<_>>();
+        assert!(hinted.parse("ab").into_result().unwrap().capacity() >= 16);
+    }
+
+    #[test]
+    fn chains() {
+        #[derive(Debug, PartialEq)]
+        enum Expr {
+            Num(u32),
+            Sub(Box<Expr>, Box<Expr>),
+            Pow(Box<Expr>, Box<Expr>),
+        }
+
+        let num = || {
+            text::int::<_, _, extra::Err<Rich<char>>>(10)
+                .from_str()
+                .unwrapped()
+                .map(Expr::Num)
+                .padded()
+        };
+        let sub = chainl1(
+            num(),
+            just('-').to(|l, r| Expr::Sub(Box::new(l), Box::new(r))),
+        );
+        let pow = chainr1(
+            num(),
+            just('^').to(|l, r| Expr::Pow(Box::new(l), Box::new(r))),
+        );
+        let n = |n| Box::new(Expr::Num(n));
+
+        assert_eq!(
+            sub.parse("1 - 2 - 3").into_result(),
+            Ok(Expr::Sub(Box::new(Expr::Sub(n(1), n(2))), n(3)))
+        );
+        assert_eq!(
+            pow.parse("1^2^3").into_result(),
+            Ok(Expr::Pow(n(1), Box::new(Expr::Pow(n(2), n(3)))))
+        );
+        assert_eq!(sub.parse("1").into_result(), Ok(Expr::Num(1)));
+        assert_eq!(pow.parse("1").into_result(), Ok(Expr::Num(1)));
+
+        // A dangling operator is left unconsumed
+        assert_eq!(
+            sub.then(just('-')).parse("1 - 2 -").into_result(),
+            Ok((Expr::Sub(n(1), n(2)), '-'))
+        );
+        assert_eq!(
+            pow.then(just('^')).parse("1^2^").into_result(),
+            Ok((Expr::Pow(n(1), n(2)), '^'))
+        );
+        let errs = sub.parse("1 - 2 +").into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::new(6, 7));
+        assert!(sub.parse("").has_errors());
+        assert!(pow.parse("^1").has_errors());
+
+        // Long chains are parsed without recursion
+        let long = ["1"; 10_000].join("-");
+        assert!(sub.check(long.as_str()).into_result().is_ok());
+        let long = ["1"; 10_000].join("^");
+        assert!(pow.check(long.as_str()).into_result().is_ok());
+    }
+
+    #[test]
+    fn end_spans_at_eof() {
+        let expected =
+            |err: &Rich<char>| err.expected().map(ToString::to_string).collect::<Vec<_>>();
+
+        let word = text::ascii::ident::<_, _, extra::Err<Rich<char>>>();
+        let tail = word.then_ignore(end());
+        let head = end().ignore_then(word.or_not());
+
+        // Fully consumed input reports nothing
+        assert_eq!(tail.parse("abc").into_result(), Ok("abc"));
+        assert_eq!(head.parse("").into_result(), Ok(None));
+
+        // Trailing input is reported at the first extra token
+        let errs = tail.parse("abc d!").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 4));
+        assert_eq!(errs[0].found(), Some(&' '));
+        assert_eq!(expected(&errs[0]), vec!["end of input"]);
+
+        // Even when it is preceded by padding that was consumed
+        let errs = word
+            .padded()
+            .then_ignore(end())
+            .parse(" abc \n!?")
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(6, 7));
+        assert_eq!(errs[0].found(), Some(&'!'));
+
+        // Alternatives that could have continued are expected alongside the end
+        let errs = word
+            .then(just('?').or_not())
+            .then_ignore(end())
+            .parse("abc!")
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 4));
+        assert_eq!(errs[0].found(), Some(&'!'));
+        assert_eq!(expected(&errs[0]), vec!["'?'", "end of input"]);
+
+        // At the start of the input
+        let errs = head.parse("abc").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 1));
+        assert_eq!(errs[0].found(), Some(&'a'));
+    }
+
+    #[test]
+    fn map_skipped_when_output_unused() {
+        fn boom<'a>() -> impl Parser<'a, &'a str, char> + Clone {
+            any()
+                .map(|_: char| -> char { panic!("`map` called") })
+                .map_with(|_, _| -> char { panic!("`map_with` called") })
+        }
+
+        assert!(!boom().check("a").has_errors());
+        assert_eq!(boom().ignored().parse("a").into_result(), Ok(()));
+        assert_eq!(
+            boom().repeated().to_slice().parse("abc").into_result(),
+            Ok("abc")
+        );
+        assert_eq!(
+            boom()
+                .not()
+                .ignore_then(any())
+                .or(boom().rewind().ignore_then(any()))
+                .parse("a")
+                .into_result(),
+            Ok('a')
+        );
+        assert_eq!(
+            text::ascii::ident()
+                .and_is(boom().then(boom()).not())
+                .parse("x")
+                .into_result(),
+            Ok("x")
+        );
+    }
+
+    #[test]
+    fn map_with_span_and_slice() {
+        #[derive(Debug, PartialEq)]
+        enum Token<'a> {
+            Hex {
+                value: u32,
+                text: &'a str,
+                span: SimpleSpan,
+            },
+            Ident {
+                name: &'a str,
+                span: SimpleSpan,
+            },
+        }
+
+        let hex = just::<_, _, extra::Err<Simple<char>>>("0x")
+            .ignore_then(text::digits(16).to_slice())
+            .map_with(|digits, e| Token::Hex {
+                value: u32::from_str_radix(digits, 16).unwrap(),
+                text: e.slice(),
+                span: e.span(),
+            });
+        let ident = text::ascii::ident().map_with(|name, e| Token::Ident {
+            name,
+            span: e.span(),
+        });
+        let tokens = hex.or(ident).padded().repeated().collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens.parse("x 0x1F").into_result(),
+            Ok(vec![
+                Token::Ident {
+                    name: "x",
+                    span: SimpleSpan::new(0, 1),
+                },
+                Token::Hex {
+                    value: 31,
+                    text: "0x1F",
+                    span: SimpleSpan::new(2, 6),
+                },
+            ]),
+        );
+    }
+
+    #[test]
+    fn parse_lazy_then_continue() {
+        let header = just::<_, _, extra::Err<Simple<u8>>>(b"MAGIC")
+            .ignore_then(any())
+            .then_ignore(just(b'\n'));
+        let body = any::<_, extra::Err<Simple<u8>>>()
+            .filter(u8::is_ascii_lowercase)
+            .repeated()
+            .collect::<Vec<_>>();
+
+        let input: &[u8] = b"MAGIC\x02\nabc";
+        let (version, offset) = header.parse_lazy(input).into_result().unwrap();
+        assert_eq!(version, 2);
+        assert_eq!(offset, 7);
+        assert_eq!(
+            body.parse(&input[offset..]).into_result(),
+            Ok(b"abc".to_vec())
+        );
+
+        // The offset of a `&str` input is in bytes, so it's always a valid place to slice
+        let word = text::ident::<_, _, extra::Err<Simple<char>>>();
+        let input = "héllo wörld";
+        let (first, offset) = word.parse_lazy(input).into_result().unwrap();
+        assert_eq!(first, "héllo");
+        assert_eq!(offset, "héllo".len());
+        assert_eq!(
+            word.padded().parse(&input[offset..]).into_result(),
+            Ok("wörld")
+        );
+    }
+
+    #[test]
+    fn debug() {
+        use alloc::string::ToString;
+        use core::cell::RefCell;
+
+        let log = RefCell::new(Vec::new());
+        let sink = |args: core::fmt::Arguments| log.borrow_mut().push(args.to_string());
+
+        let kw = just::<_, _, extra::Err<Simple<char>>>("let");
+        let ident = text::ascii::ident();
+        let word = kw
+            .debug("kw", sink)
+            .or(ident.debug("ident", sink))
+            .padded()
+            .repeated()
+            .collect::<Vec<_>>();
+
+        // The wrapper is transparent, including for errors
+        let plain = kw.or(ident).padded().repeated().collect::<Vec<_>>();
+        assert_eq!(word.parse("let 1"), plain.parse("let 1"));
+        log.borrow_mut().clear();
+
+        assert_eq!(word.parse("let x").into_result(), Ok(vec!["let", "x"]));
+        assert_eq!(
+            log.into_inner(),
+            [
+                "kw: entered at 0",
+                "kw: succeeded, consuming 0..3",
+                "kw: entered at 4",
+                "kw: failed at 4",
+                "ident: entered at 4",
+                "ident: succeeded, consuming 4..5",
+                "kw: entered at 5",
+                "kw: failed at 5",
+                "ident: entered at 5",
+                "ident: failed at 5",
+            ],
+        );
+    }
+
+    #[test]
+    fn just_sequences() {
+        let abc = just::<_, _, extra::Err<Simple<char>>>(['a', 'b', 'c']);
+        assert_eq!(abc.parse("abc").into_result(), Ok(['a', 'b', 'c']));
+        let errs = abc.parse("abd").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&'d'));
+        assert_eq!(errs[0].span(), &SimpleSpan::new(2, 3));
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Token {
+            A,
+            B,
+            C,
+        }
+
+        let ab = just::<_, _, extra::Err<Simple<Token>>>(vec![Token::A, Token::B]);
+        assert_eq!(
+            ab.parse(&[Token::A, Token::B][..]).into_result(),
+            Ok(vec![Token::A, Token::B]),
+        );
+        let errs = ab.parse(&[Token::A, Token::C][..]).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&Token::C));
+        assert_eq!(errs[0].span(), &SimpleSpan::new(1, 2));
+        // Running out of input part way through is also a mismatch
+        let errs = ab.parse(&[Token::A][..]).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), None);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(1, 1));
+
+        // Slices and strings behave the same way
+        let ab = just::<_, _, extra::Err<Simple<Token>>>(&[Token::A, Token::B][..]);
+        assert!(ab.parse(&[Token::A, Token::B][..]).into_result().is_ok());
+        let errs = just::<_, _, extra::Err<Simple<char>>>("abc")
+            .parse("abd")
+            .into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::new(2, 3));
+    }
+
+    #[test]
+    fn or_is_committed() {
+        let counter = core::cell::Cell::new(0);
+        let second = just::<_, &str, extra::Err<Simple<char>>>("a").map(|s| {
+            counter.set(counter.get() + 1);
+            s
+        });
+
+        // `or` doesn't try the second parser after part of the first has succeeded
+        let ab = just('a').then(just('b')).to_slice();
+        let committed = ab.or(second);
+        let errs = committed.parse("a").into_errors();
+        assert_eq!(counter.get(), 0);
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), None);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(1, 1));
+        assert_eq!(committed.parse("ab").into_result(), Ok("ab"));
+
+        // ...unless the first parser fails without consuming input
+        assert_eq!(just("b").or(second).parse("a").into_result(), Ok("a"));
+        assert_eq!(counter.get(), 1);
+
+        // A rewinding `or` always tries the second parser
+        let rewinding = ab.or(second).or_rewinding();
+        assert_eq!(rewinding.parse("a").into_result(), Ok("a"));
+        assert_eq!(counter.get(), 2);
+        assert_eq!(rewinding.parse("ab").into_result(), Ok("ab"));
+
+        // Errors from earlier attempts that got further don't count as consuming input
+        let parser = just::<_, &str, extra::Err<Simple<char>>>("abc")
+            .or(just("a"))
+            .then(just("q").or(just("by")));
+        assert_eq!(parser.parse("aby").into_result(), Ok(("a", "by")));
+    }
+
+    #[test]
+    fn or_primitives_never_consume() {
+        type Extra = extra::Err<Simple<'static, char>>;
+        let fallback = || any::<&str, Extra>().repeated().to_slice();
+
+        // Primitives leave the input where they started when they fail, however much of it they looked at, so `or`
+        // always goes on to try the second parser
+        let arrow = just::<_, &str, Extra>("->").or(fallback());
+        assert_eq!(arrow.parse("-").into_result(), Ok("-"));
+
+        let digit = any::<&str, Extra>()
+            .filter(|c: &char| c.is_ascii_digit())
+            .to_slice()
+            .or(fallback());
+        assert_eq!(digit.parse("x").into_result(), Ok("x"));
+
+        let kw = text::ascii::keyword::<_, _, _, Extra>("let").or(fallback());
+        assert_eq!(kw.parse("letter").into_result(), Ok("letter"));
+
+        let upper = text::ascii::ident::<_, _, Extra>()
+            .try_map(|s: &str, span| {
+                if s.chars().all(|c| c.is_ascii_uppercase()) {
+                    Ok(s)
+                } else {
+                    Err(crate::error::Error::<&str>::expected_found([], None, span))
+                }
+            })
+            .or(fallback());
+        assert_eq!(upper.parse("Abc").into_result(), Ok("Abc"));
+
+        let op = choice_trie::<_, _, _, &str, Extra>([("<<=", "shl-assign")]).or(fallback());
+        assert_eq!(op.parse("<<").into_result(), Ok("<<"));
+
+        // Sequences count as consuming input once one of their parts has succeeded
+        let arrow = just::<_, &str, Extra>('-')
+            .then(just('>'))
+            .to_slice()
+            .or(fallback());
+        assert!(arrow.parse("-").has_errors());
+        let kw = text::ascii::keyword::<_, _, _, Extra>("let")
+            .then(just('!'))
+            .to_slice()
+            .or(fallback());
+        assert!(kw.parse("let?").has_errors());
+        let padded = just::<_, &str, Extra>("x").padded().or(fallback());
+        assert!(padded.parse(" y").has_errors());
+
+        // `choice` always backtracks, like a rewinding `or`
+        let a = || just::<_, &str, Extra>('a');
+        let committed = a().then(just('b')).or(a().then(just('c')));
+        assert!(committed.parse("ac").has_errors());
+        let backtracking = choice((a().then(just('b')), a().then(just('c'))));
+        assert_eq!(backtracking.parse("ac").into_result(), Ok(('a', 'c')));
+        let rewinding = a().then(just('b')).or(a().then(just('c'))).or_rewinding();
+        assert_eq!(rewinding.parse("ac").into_result(), Ok(('a', 'c')));
+    }
+
+    #[test]
+    fn from_str_or_error() {
+        let int = text::int::<_, _, extra::Err<Rich<char>>>(10).from_str_or_error::<i64>();
+
+        let (out, errs) = int.parse("99999999999999999999").into_output_errors();
+        assert_eq!(out, Some(0));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 20));
+
+        // Parsing continues after the bad literal
+        let ints = int.padded().separated_by(just(',')).collect::<Vec<_>>();
+        let (out, errs) = ints
+            .parse("1, 99999999999999999999, 3")
+            .into_output_errors();
+        assert_eq!(out, Some(vec![1, 0, 3]));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 23));
+
+        // Other error types report the literal's span too
+        let int = text::int::<_, _, extra::Err<Simple<char>>>(10).from_str_or_error::<u8>();
+        let errs = int.parse("256").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 3));
+        assert_eq!(int.parse("255").into_result(), Ok(255));
+    }
+
+    #[test]
+    fn infix_flat() {
+        let expr = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .padded()
+            .from_str::<u32>()
+            .unwrapped()
+            .infix_flat(one_of("+*"));
+
+        assert_eq!(
+            expr.parse("1+2*3").into_result(),
+            Ok((vec![1, 2, 3], vec!['+', '*'])),
+        );
+        assert_eq!(
+            expr.parse(" 4 * 5 ").into_result(),
+            Ok((vec![4, 5], vec!['*'])),
+        );
+
+        // A dangling operator is left unconsumed
+        assert_eq!(
+            expr.then_ignore(just('+')).parse("1+2+").into_result(),
+            Ok((vec![1, 2], vec!['+'])),
+        );
+        assert!(!expr.check("1+2*3").has_errors());
+        assert!(expr.check("+1").has_errors());
+    }
+
+    #[test]
+    fn separated_by_enumerate() {
+        let items = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+            .separated_by(just(','))
+            .allow_trailing()
+            .enumerate()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items.parse("a,b,c").into_result(),
+            Ok(vec![(0, "a"), (1, "b"), (2, "c")]),
+        );
+        assert_eq!(items.parse("a,").into_result(), Ok(vec![(0, "a")]));
+        assert_eq!(items.parse("").into_result(), Ok(vec![]));
+
+        // The index of each element is available when validating it
+        let digits = text::digits::<_, _, extra::Err<Rich<char>>>(10)
+            .to_slice()
+            .map_with(|digits: &str, e| (digits, e.span()))
+            .separated_by(just(','))
+            .enumerate()
+            .collect::<Vec<_>>()
+            .validate(|items, _, emitter| {
+                for (i, (digits, span)) in &items {
+                    if digits.len() > 1 {
+                        emitter.emit(Rich::custom(
+                            *span,
+                            format!("element {} is more than one digit", i),
+                        ));
+                    }
+                }
+                items
+            });
+        let errs = digits.parse("1,2,34").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].to_string(), "element 2 is more than one digit");
+        assert_eq!(errs[0].span(), &SimpleSpan::new(4, 6));
+    }
+
+    #[test]
+    fn delimited_by_spanned() {
+        let list = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+            .padded()
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .map_with(|items, e| (items, e.span()))
+            .delimited_by_spanned(just("[["), just("]]"));
+
+        let (open, (items, inner), close) = list.parse("[[ a, b ]]").into_result().unwrap();
+        assert_eq!(items, vec!["a", "b"]);
+        assert_eq!(open, SimpleSpan::new(0, 2));
+        assert_eq!(inner, SimpleSpan::new(2, 8));
+        assert_eq!(close, SimpleSpan::new(8, 10));
+
+        // A missing closing delimiter is still an error
+        assert!(list.parse("[[ a, b ]").has_errors());
+    }
+
+    #[test]
+    fn select_variants() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Token<'a> {
+            Num(u64),
+            Ident(&'a str),
+            Comma,
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Value<'a> {
+            Num(u64),
+            Var(&'a str),
+        }
+
+        let value = select! {
+            Token::Num(n) => Value::Num(n),
+            Token::Ident(s) => Value::Var(s),
+        };
+        let values = value
+            .separated_by(just(Token::Comma))
+            .collect::<Vec<_>>()
+            .then_ignore(end::<_, extra::Err<Simple<Token>>>());
+
+        let tokens = [Token::Num(1), Token::Comma, Token::Ident("x")];
+        assert_eq!(
+            values.parse(&tokens[..]).into_result(),
+            Ok(vec![Value::Num(1), Value::Var("x")]),
+        );
+
+        // An unmatched token is reported as found, with the span of that token
+        let tokens = [Token::Num(1), Token::Comma, Token::Comma];
+        let errs = values.parse(&tokens[..]).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&Token::Comma));
+        assert_eq!(errs[0].span(), &SimpleSpan::new(2, 3));
+    }
+
+    #[test]
+    fn to_span_keyword() {
+        use core::cell::Cell;
+
+        let emitted = Cell::new(0);
+        let kw = text::ascii::keyword::<_, _, _, extra::Err<Simple<char>>>("let")
+            .map(|kw| {
+                emitted.set(emitted.get() + 1);
+                kw
+            })
+            .to_span();
+        let kws = kw.clone().padded().repeated().collect::<Vec<_>>();
+
+        assert_eq!(kw.parse("let").into_result(), Ok(SimpleSpan::new(0, 3)));
+        assert_eq!(
+            kws.parse(" let  let").into_result(),
+            Ok(vec![SimpleSpan::new(1, 4), SimpleSpan::new(6, 9)]),
+        );
+        assert!(kw.parse("letter").has_errors());
+        // The keyword's own output is never produced
+        assert_eq!(emitted.get(), 0);
+    }
+
+    #[test]
+    fn recover_nested_delimiters() {
+        #[derive(Debug, PartialEq)]
+        enum Expr<'a> {
+            Error,
+            Var(&'a str),
+            Call(&'a str, Vec<Expr<'a>>),
+            Add(Box<Expr<'a>>, Box<Expr<'a>>),
+        }
+
+        let expr = recursive::<_, _, extra::Err<Simple<char>>, _, _>(|expr| {
+            let args = expr
+                .separated_by(just(','))
+                .collect()
+                .delimited_by(just('('), just(')'))
+                .recover_with(via_parser(nested_delimiters(
+                    '(',
+                    ')',
+                    [('[', ']')],
+                    |_| vec![Expr::Error],
+                )));
+            let atom = text::ascii::ident()
+                .then(args.or_not())
+                .map(|(name, args)| match args {
+                    Some(args) => Expr::Call(name, args),
+                    None => Expr::Var(name),
+                })
+                .padded();
+
+            atom.clone()
+                .foldl(just('+').ignore_then(atom).repeated(), |a, b| {
+                    Expr::Add(Box::new(a), Box::new(b))
+                })
+        });
+
+        let recovered = Expr::Add(
+            Box::new(Expr::Call("foo", vec![Expr::Error])),
+            Box::new(Expr::Var("x")),
+        );
+
+        // Nested delimiters inside the malformed region are skipped along with it
+        for src in ["foo(@@@) + x", "foo(@(a, @)[@]) + x", "foo(a, (@)) + x"] {
+            let res = expr.parse(src);
+            assert_eq!(res.errors().len(), 1, "{src}");
+            assert_eq!(res.output(), Some(&recovered), "{src}");
+        }
+
+        // Mismatched or unclosed delimiters can't be recovered from
+        assert_eq!(expr.parse("foo(@[@)] + x").output(), None);
+        assert_eq!(expr.parse("foo(@@@ + x").output(), None);
+    }
+
+    #[test]
+    fn foldl_left_nested() {
+        #[derive(Debug, PartialEq)]
+        enum Expr {
+            Num(u64),
+            Sub(Box<Expr>, Box<Expr>),
+        }
+
+        let num = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .from_str()
+            .unwrapped()
+            .map(Expr::Num);
+        let expr = num.foldl(just('-').ignore_then(num).repeated(), |lhs, rhs| {
+            Expr::Sub(Box::new(lhs), Box::new(rhs))
+        });
+
+        let sub = |lhs, rhs| Expr::Sub(Box::new(lhs), Box::new(rhs));
+        assert_eq!(
+            expr.parse("1-2-3").into_result(),
+            Ok(sub(sub(Expr::Num(1), Expr::Num(2)), Expr::Num(3))),
+        );
+        assert_eq!(expr.parse("1").into_result(), Ok(Expr::Num(1)));
+        assert!(expr.parse("1-").has_errors());
+    }
+
+    #[test]
+    fn foldl_with_span() {
+        #[derive(Debug, PartialEq)]
+        enum Expr {
+            Num(u64),
+            Bin(Box<Expr>, char, SimpleSpan, Box<Expr>),
+        }
+
+        let num = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .from_str()
+            .unwrapped()
+            .map(Expr::Num);
+        let expr = num.foldl_with_span(one_of("+-").padded(), num, |lhs, op, op_span, rhs| {
+            Expr::Bin(Box::new(lhs), op, op_span, Box::new(rhs))
+        });
+
+        let bin = |lhs, op, span: std::ops::Range<usize>, rhs| {
+            Expr::Bin(Box::new(lhs), op, span.into(), Box::new(rhs))
+        };
+        assert_eq!(
+            expr.parse("10 + 2-3").into_result(),
+            Ok(bin(
+                bin(Expr::Num(10), '+', 2..5, Expr::Num(2)),
+                '-',
+                6..7,
+                Expr::Num(3)
+            )),
+        );
+        assert_eq!(expr.parse("7").into_result(), Ok(Expr::Num(7)));
+
+        // An operator without an operand is left unconsumed
+        let rest = expr.then(any().repeated().to_slice());
+        assert_eq!(
+            rest.parse("1+2 +").into_result(),
+            Ok((bin(Expr::Num(1), '+', 1..2, Expr::Num(2)), " +")),
+        );
+    }
+
+    #[test]
+    fn token_input_eoi_span() {
+        use crate::input::TokenInput;
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Tok {
+            Let,
+            Ident(&'static str),
+            Eq,
+            Num(u64),
+        }
+
+        let tokens = vec![
+            (Tok::Let, SimpleSpan::new(0, 3)),
+            (Tok::Ident("x"), SimpleSpan::new(4, 5)),
+            (Tok::Eq, SimpleSpan::new(6, 7)),
+        ];
+        let eoi = SimpleSpan::new(7, 7);
+
+        let stmt = just::<_, _, extra::Err<Rich<Tok>>>(Tok::Let)
+            .ignore_then(select! { Tok::Ident(name) => name })
+            .then_ignore(just(Tok::Eq))
+            .then(select! { Tok::Num(x) => x });
+
+        let mut complete = tokens.clone();
+        complete.push((Tok::Num(5), SimpleSpan::new(8, 9)));
+        assert_eq!(
+            stmt.parse(TokenInput::new(&complete, SimpleSpan::new(9, 9)))
+                .into_result(),
+            Ok(("x", 5)),
+        );
+
+        // The missing number is reported at the end of input rather than spanning the whole input
+        let errs = stmt.parse(TokenInput::new(&tokens, eoi)).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &eoi);
+        assert_eq!(errs[0].found(), None);
+
+        // An input with no tokens at all reports at the end of input too
+        let errs = stmt.parse(TokenInput::new(&[], eoi)).into_errors();
+        assert_eq!(errs[0].span(), &eoi);
+    }
+
+    #[test]
+    fn with_offset_input() {
+        let src = "fn main() { call!(foo, @bar) }";
+        let base = src.find("foo").unwrap();
+        let snippet = &src[base..src.rfind(')').unwrap()];
+
+        fn args<'a, I>(
+        ) -> impl Parser<'a, I, Vec<(&'a str, SimpleSpan)>, extra::Err<Rich<'a, char>>>
+        where
+            I: crate::input::StrInput<'a, char> + Input<'a, Span = SimpleSpan>,
+        {
+            text::ascii::ident()
+                .map_with(|name, e| (name, e.span()))
+                .padded()
+                .separated_by(just(','))
+                .collect()
+        }
+
+        // Without an offset, spans are relative to the snippet
+        let errs = args().parse(snippet).into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::new(5, 6));
+
+        // With one, they refer to the original source
+        let errs = args().parse(snippet.with_offset(base)).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(base + 5, base + 6));
+        assert_eq!(&src[errs[0].span().into_range()], "@");
+
+        assert_eq!(
+            args().parse("foo, bar".with_offset(100)).into_result(),
+            Ok(vec![
+                ("foo", SimpleSpan::new(100, 103)),
+                ("bar", SimpleSpan::new(105, 108)),
+            ]),
+        );
+    }
+
+    #[test]
+    fn map_token_input() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Tok {
+            Ident(String),
+            Comma,
+        }
+
+        struct RawTok {
+            text: &'static str,
+        }
+
+        impl From<&RawTok> for Tok {
+            fn from(raw: &RawTok) -> Self {
+                match raw.text {
+                    "," => Tok::Comma,
+                    s => Tok::Ident(s.to_string()),
+                }
+            }
+        }
+
+        fn idents<'a, I>() -> impl Parser<'a, I, Vec<String>, extra::Err<Rich<'a, Tok, I::Span>>>
+        where
+            I: crate::input::ValueInput<'a, Token = Tok>,
+        {
+            select! { Tok::Ident(s) => s }
+                .separated_by(just(Tok::Comma))
+                .collect()
+        }
+
+        // The same parser works over both token representations
+        let toks = [
+            Tok::Ident("a".to_string()),
+            Tok::Comma,
+            Tok::Ident("b".to_string()),
+        ];
+        assert_eq!(
+            idents().parse(&toks[..]).into_result(),
+            Ok(vec!["a".to_string(), "b".to_string()]),
+        );
+
+        let raw = [
+            RawTok { text: "a" },
+            RawTok { text: "," },
+            RawTok { text: "b" },
+            RawTok { text: "c" },
+        ];
+        // Spans are those of the original input
+        let errs = idents()
+            .parse((&raw[..]).map_token(|t| Tok::from(t)))
+            .into_errors();
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 4));
+        assert_eq!(errs[0].found(), Some(&Tok::Ident("c".to_string())));
+
+        let input = (&raw[..3]).map_token(|t| Tok::from(t));
+        assert_eq!(
+            idents().parse(input).into_result(),
+            Ok(vec!["a".to_string(), "b".to_string()]),
+        );
+    }
+
+    #[test]
+    fn try_map_in_repeated() {
+        let byte = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .try_map(|s: &str, span| s.parse::<u8>().map_err(|e| Rich::custom(span, e)));
+        let bytes = byte.padded().repeated().collect::<Vec<_>>();
+
+        assert_eq!(bytes.parse("1 2 3").into_result(), Ok(vec![1, 2, 3]));
+
+        // The second element fails to map, so the repetition stops there with no partial output
+        let (out, errs) = bytes.parse("1 300 3").into_output_errors();
+        assert_eq!(out, None);
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(2, 5));
+        assert_eq!(
+            errs[0].to_string(),
+            "number too large to fit in target type"
+        );
+
+        // With validation instead, every element is still produced
+        let lenient = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .validate(|s: &str, e, emitter| {
+                s.parse::<u8>().unwrap_or_else(|err| {
+                    emitter.emit(Rich::custom(e.span(), err));
+                    0
+                })
+            })
+            .padded()
+            .repeated()
+            .collect::<Vec<_>>();
+        let (out, errs) = lenient.parse("1 300 3").into_output_errors();
+        assert_eq!(out, Some(vec![1, 0, 3]));
+        assert_eq!(errs.len(), 1);
+    }
+
+    #[test]
+    fn then_with_span() {
+        // A value on the following line must be aligned with the end of the key
+        let entry = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+            .then_ignore(just(':'))
+            .then_with_span(|key, span: SimpleSpan| {
+                text::newline()
+                    .ignore_then(just(' ').repeated().exactly(span.end))
+                    .ignore_then(text::int(10))
+                    .map(move |value| (key, value))
+            });
+
+        assert_eq!(entry.parse("abc:\n    1").into_result(), Ok(("abc", "1")));
+        assert_eq!(entry.parse("x:\n  42").into_result(), Ok(("x", "42")));
+
+        let errs = entry.parse("abc:\n   1").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(8, 9));
+        assert!(entry.parse("abc:\n     1").has_errors());
+
+        // The first parser failing means the second is never built
+        assert!(entry.parse(":\n 1").has_errors());
+        assert!(entry.check("abc:\n    1").into_result().is_ok());
+    }
+
+    #[test]
+    fn empty_base_case() {
+        let a = empty::<_, extra::Err<Simple<char>>>().then(just('a'));
+        assert_eq!(a.parse("a").into_result(), Ok(((), 'a')));
+        assert!(a.parse("").has_errors());
+
+        // It never fails, even at the end of input, and consumes nothing
+        assert_eq!(
+            empty::<&str, extra::Default>().parse("").into_result(),
+            Ok(())
+        );
+        assert!(empty::<&str, extra::Default>().parse("a").has_errors());
+        assert_eq!(
+            empty::<_, extra::Default>()
+                .or_not()
+                .parse("")
+                .into_result(),
+            Ok(Some(())),
+        );
+
+        // As the last alternative of a choice, it's used only if nothing else matches
+        let sign = choice((
+            just::<_, _, extra::Err<Simple<char>>>('-').to(-1),
+            just('+').to(1),
+            empty().to(1),
+        ));
+        let num = sign.then(text::int(10).from_str::<i32>().unwrapped());
+        let num = num.map(|(sign, x)| sign * x);
+        assert_eq!(num.parse("-5").into_result(), Ok(-5));
+        assert_eq!(num.parse("+5").into_result(), Ok(5));
+        assert_eq!(num.parse("5").into_result(), Ok(5));
+
+        // As the initial value of a fold
+        let chars = empty::<_, extra::Err<Simple<char>>>()
+            .to(String::new())
+            .foldl(any().repeated(), |mut s, c| {
+                s.push(c);
+                s
+            });
+        assert_eq!(chars.parse("abc").into_result(), Ok("abc".to_string()));
+    }
+
+    #[test]
+    fn flat_map_err() {
+        let parser = just::<_, _, extra::Err<Rich<char>>>("ab").flat_map_err(|e| {
+            (
+                Rich::custom(*e.span(), "primary"),
+                vec![
+                    Rich::custom(*e.span(), "first"),
+                    Rich::custom(*e.span(), "second"),
+                ],
+            )
+        });
+
+        assert_eq!(parser.parse("ab").into_result(), Ok("ab"));
+
+        let res = parser.parse("ax");
+        assert_eq!(res.output(), None);
+        assert_eq!(
+            res.into_errors(),
+            vec![
+                Rich::custom(SimpleSpan::new(1, 2), "first"),
+                Rich::custom(SimpleSpan::new(1, 2), "second"),
+                Rich::custom(SimpleSpan::new(1, 2), "primary"),
+            ],
+        );
+
+        // Mapping to a single error behaves like `map_err`
+        let single = just::<_, _, extra::Err<Rich<char>>>("ab")
+            .flat_map_err(|e| (Rich::custom(*e.span(), "only"), Vec::new()));
+        assert_eq!(
+            single.parse("ax").into_errors(),
+            vec![Rich::custom(SimpleSpan::new(1, 2), "only")],
+        );
+
+        // Errors produced on a path that is backtracked away from are discarded
+        let alt = parser.or(just("ax")).or_rewinding();
+        assert_eq!(alt.parse("ax").into_result(), Ok("ax"));
+    }
+
+    #[test]
+    fn map_errors_from_simple() {
+        #[derive(Debug, PartialEq)]
+        struct MyError {
+            at: usize,
+            found: Option<char>,
+        }
+
+        let num = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .recover_with(via_parser(any().to("?")))
+            .separated_by(just(','))
+            .collect::<Vec<_>>();
+
+        let to_mine = |e: Simple<char>| MyError {
+            at: e.span().start,
+            found: e.found().copied(),
+        };
+
+        assert_eq!(
+            num.parse("1,2").map_errors(to_mine).into_result(),
+            Ok(vec!["1", "2"])
+        );
+        assert_eq!(
+            num.parse("1,x,3").map_errors(to_mine).into_result(),
+            Err(vec![MyError {
+                at: 2,
+                found: Some('x')
+            }]),
+        );
+
+        // Recovered output is kept alongside the converted errors
+        let (out, errs) = num
+            .parse("x,1")
+            .map_errors(Rich::<char>::from)
+            .into_output_errors();
+        assert_eq!(out, Some(vec!["?", "1"]));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 1));
+        assert_eq!(errs[0].found(), Some(&'x'));
+    }
+
+    #[test]
+    fn map_err_with_state() {
+        struct Files {
+            current: usize,
+            mapped: usize,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct FileErr {
+            file: Option<usize>,
+            span: SimpleSpan,
+        }
+
+        impl<'a> crate::error::Error<'a, &'a str> for FileErr {
+            fn expected_found<E: IntoIterator<Item = Option<crate::util::MaybeRef<'a, char>>>>(
+                _expected: E,
+                _found: Option<crate::util::MaybeRef<'a, char>>,
+                span: SimpleSpan,
+            ) -> Self {
+                Self { file: None, span }
+            }
+        }
+
+        let item = text::ascii::ident::<_, _, extra::Full<FileErr, Files, ()>>()
+            .map_err_with_state(|err: FileErr, _, files: &mut Files| {
+                files.mapped += 1;
+                FileErr {
+                    file: Some(files.current),
+                    ..err
+                }
+            });
+
+        let mut files = Files {
+            current: 3,
+            mapped: 0,
+        };
+        assert_eq!(
+            item.parse_with_state("abc", &mut files).into_result(),
+            Ok("abc")
+        );
+        // The mapper doesn't run when the parser succeeds
+        assert_eq!(files.mapped, 0);
+
+        assert_eq!(
+            item.parse_with_state("1", &mut files).into_result(),
+            Err(vec![FileErr {
+                file: Some(3),
+                span: SimpleSpan::new(0, 1),
+            }]),
+        );
+        assert_eq!(files.mapped, 1);
+    }
+
+    #[test]
+    fn custom_backtracking() {
+        // A custom combinator that tries both parsers from the same position and keeps the longer match
+        fn longest<'a, A, B>(a: A, b: B) -> impl Parser<'a, &'a str, &'a str> + Clone
+        where
+            A: Parser<'a, &'a str, &'a str> + Clone,
+            B: Parser<'a, &'a str, &'a str> + Clone,
+        {
+            custom(move |inp| {
+                let before = inp.checkpoint();
+                let a_out = inp.parse(&a).ok().map(|out| (out, inp.checkpoint()));
+                inp.rewind_to(before);
+                let b_out = inp.parse(&b).ok().map(|out| (out, inp.checkpoint()));
+                inp.rewind_to(before);
+
+                let (out, after) = match (a_out, b_out) {
+                    (Some(a), Some(b)) if b.0.len() > a.0.len() => b,
+                    (Some(a), _) => a,
+                    (None, Some(b)) => b,
+                    (None, None) => return inp.parse(&a),
+                };
+                inp.rewind_to(after);
+                Ok(out)
+            })
+        }
+
+        let kw = longest(just("let"), text::ascii::ident());
+        let parser = kw.clone().then_ignore(just(' ')).then(kw);
+
+        assert_eq!(
+            parser.parse("let letter").into_result(),
+            Ok(("let", "letter"))
+        );
+        assert_eq!(
+            parser.parse("lettuce let").into_result(),
+            Ok(("lettuce", "let"))
+        );
+        assert!(parser.parse("let 1").has_errors());
+    }
+
+    #[test]
+    fn lists() {
+        let item = text::ascii::ident::<_, _, extra::Err<Simple<char>>>().padded();
+
+        let any_items = item.list_or_empty();
+        assert_eq!(any_items.parse("").into_result(), Ok(vec![]));
+        assert_eq!(any_items.parse("a b").into_result(), Ok(vec!["a", "b"]));
+        // An empty list still needs the input that follows it to be valid
+        assert!(any_items.parse("1").has_errors());
+
+        let some_items = item.nonempty_list();
+        assert_eq!(some_items.parse("a b").into_result(), Ok(vec!["a", "b"]));
+        assert_eq!(some_items.parse(" a ").into_result(), Ok(vec!["a"]));
+        let errs = some_items.parse("").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), None);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 0));
+
+        // Inside a larger parser, an empty list is only accepted by `list_or_empty`
+        let call = |args| {
+            text::ascii::ident()
+                .then(args)
+                .delimited_by(just('('), just(')'))
+        };
+        assert_eq!(
+            call(any_items).parse("(f)").into_result(),
+            Ok(("f", vec![])),
+        );
+        assert!(call(some_items).parse("(f)").has_errors());
+        assert_eq!(
+            call(some_items).parse("(f x)").into_result(),
+            Ok(("f", vec!["x"])),
+        );
+    }
+
+    #[test]
+    fn delimited_by_recover_unclosed() {
+        #[derive(Debug, PartialEq)]
+        enum Item<'a> {
+            Ident(&'a str),
+            Block(Vec<Item<'a>>),
+        }
+
+        let block = recursive::<_, _, extra::Err<Rich<char>>, _, _>(|block| {
+            let ident = text::ascii::ident()
+                .and_is(text::ascii::keyword("begin").not())
+                .and_is(text::ascii::keyword("end").not())
+                .map(Item::Ident);
+            ident
+                .or(block)
+                .padded()
+                .repeated()
+                .collect()
+                .delimited_by(just("begin"), just("end"))
+                .recover_unclosed()
+                .map(Item::Block)
+        });
+
+        assert_eq!(
+            block.parse("begin X end").into_result(),
+            Ok(Item::Block(vec![Item::Ident("X")])),
+        );
+
+        let (res, recoveries) = block.parse_with_recoveries("begin X");
+        assert_eq!(res.output(), Some(&Item::Block(vec![Item::Ident("X")])));
+        let errs = res.errors().collect::<Vec<_>>();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 5));
+        assert_eq!(errs[0].to_string(), "unclosed delimiter");
+        assert_eq!(recoveries.len(), 1);
+        assert_eq!(recoveries[0].strategy(), "recover_unclosed");
+
+        // The closing delimiter balances the inner block, leaving the outer one unclosed
+        let res = block.parse("begin begin X end");
+        assert_eq!(
+            res.output(),
+            Some(&Item::Block(vec![Item::Block(vec![Item::Ident("X")])])),
+        );
+        assert_eq!(
+            res.errors().map(|e| *e.span()).collect::<Vec<_>>(),
+            vec![SimpleSpan::new(0, 5)],
+        );
+
+        // Without recovery, a missing closing delimiter is a plain error
+        let strict = text::ascii::ident::<_, _, extra::Err<Rich<char>>>()
+            .padded()
+            .delimited_by(just("begin"), just("end"));
+        assert_eq!(strict.parse("begin X end").into_result(), Ok("X"));
+        assert_eq!(strict.parse("begin X").output(), None);
+    }
+
+    #[test]
+    fn then_recover() {
+        let pair = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+            .then_ignore(just('='))
+            .then_recover(text::int(10), "0");
+
+        assert_eq!(pair.parse("a=1").into_result(), Ok(("a", "1")));
+
+        // A missing second element is replaced by the default
+        let (res, recoveries) = pair.parse_with_recoveries("a=");
+        assert_eq!(res.output(), Some(&("a", "0")));
+        assert_eq!(res.errors().len(), 1);
+        assert_eq!(res.errors().next().unwrap().found(), None);
+        assert_eq!(recoveries.len(), 1);
+        assert_eq!(recoveries[0].strategy(), "then_recover");
+
+        // The first element failing isn't recovered from
+        assert_eq!(pair.parse("=1").output(), None);
+
+        // Input consumed by the second element before it failed is skipped
+        let pairs = pair
+            .then_recover(just("!!").to(true), false)
+            .separated_by(just(','))
+            .collect::<Vec<_>>();
+        let (out, errs) = pairs.parse("a=1!!,b=2!,c=3").into_output_errors();
+        assert_eq!(
+            out,
+            Some(vec![
+                (("a", "1"), true),
+                (("b", "2"), false),
+                (("c", "3"), false)
+            ]),
+        );
+        assert_eq!(errs.len(), 2);
+        assert_eq!(errs[0].found(), Some(&','));
+        assert_eq!(errs[0].span(), &SimpleSpan::new(10, 11));
+        assert_eq!(errs[1].found(), None);
+    }
+
+    #[test]
+    fn then_skip_garbage_until() {
+        let item = just::<_, _, extra::Err<Simple<char>>>("item")
+            .then(text::digits(10).or_not())
+            .to_slice();
+        let items = item
+            .then_ignore(just(';').padded().or_not())
+            .then_skip_garbage_until(just("item"))
+            .repeated()
+            .collect::<Vec<_>>();
+
+        let (res, recoveries) = items.parse_with_recoveries("item; junk item2");
+        assert_eq!(res.output(), Some(&vec!["item", "item2"]));
+        assert_eq!(res.errors().len(), 1);
+        assert_eq!(res.errors().next().unwrap().span(), &SimpleSpan::new(6, 11));
+        assert_eq!(recoveries.len(), 1);
+        assert_eq!(recoveries[0].span(), &SimpleSpan::new(6, 11));
+        assert_eq!(recoveries[0].strategy(), "then_skip_garbage_until");
+
+        // Garbage with no sync point after it runs to the end of the input
+        let res = items.parse("item; item2 junk");
+        assert_eq!(res.output(), Some(&vec!["item", "item2"]));
+        assert_eq!(
+            res.errors().next().unwrap().span(),
+            &SimpleSpan::new(11, 16)
+        );
+
+        // Nothing is reported when the items are well-formed
+        assert_eq!(
+            items.parse("item; item2").into_result(),
+            Ok(vec!["item", "item2"]),
+        );
+    }
+
+    #[test]
+    // Debug builds treat a repetition that makes no progress as a bug in the parser
+    #[cfg_attr(debug_assertions, should_panic(expected = "making no progress"))]
+    fn repeated_no_progress() {
+        let empty = just::<_, _, extra::Default>("").or_not();
+
+        assert!(!empty.repeated().parse("").has_errors());
+        assert!(!empty
+            .repeated()
+            .collect::<Vec<_>>()
+            .then_ignore(just("ab"))
+            .parse("ab")
+            .has_errors());
+        assert!(!empty
+            .separated_by(empty)
+            .collect::<Vec<_>>()
+            .parse("")
+            .has_errors());
+        assert!(!empty
+            .foldl(empty.repeated(), |a, _| a)
+            .parse("")
+            .has_errors());
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "making no progress"))]
+    fn collect_non_empty_no_progress() {
+        let maybe_a = just::<_, _, extra::Default>('a').or_not();
+
+        assert!(!maybe_a
+            .repeated()
+            .collect_non_empty()
+            .then_ignore(just('b'))
+            .parse("b")
+            .has_errors());
+    }
+
+    #[test]
+    fn or_default() {
+        let flag = |name| {
+            just::<_, _, extra::Err<Simple<char>>>("--")
+                .then(just(name))
+                .ignore_then(
+                    just('=')
+                        .ignore_then(text::int(10).from_str::<u32>().unwrapped())
+                        .or_default(),
+                )
+                .padded()
+        };
+        let opts = flag("width").then(flag("height"));
+
+        assert_eq!(
+            opts.parse("--width=80 --height=24").into_result(),
+            Ok((80, 24))
+        );
+        // A missing number defaults to `0`
+        assert_eq!(opts.parse("--width --height=24").into_result(), Ok((0, 24)));
+        assert_eq!(opts.parse("--width=80 --height").into_result(), Ok((80, 0)));
+
+        // Nothing is consumed when the pattern is missing
+        let digits = text::digits::<_, _, extra::Err<Simple<char>>>(10)
+            .collect::<String>()
+            .or_default()
+            .then(any().repeated().collect::<String>());
+        assert_eq!(
+            digits.parse("abc").into_result(),
+            Ok((String::new(), "abc".to_string())),
+        );
+        assert_eq!(
+            digits.parse("12c").into_result(),
+            Ok(("12".to_string(), "c".to_string())),
+        );
+
+        // A partially consumed pattern is an error rather than falling back to the default
+        assert!(opts.parse("--width= --height=24").has_errors());
+    }
+
+    #[test]
+    fn or_value() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Mutability {
+            Mutable,
+            Immutable,
+        }
+
+        let binding = text::ascii::keyword::<_, _, _, extra::Err<Simple<char>>>("let")
+            .padded()
+            .ignore_then(
+                text::ascii::keyword("mut")
+                    .to(Mutability::Mutable)
+                    .padded()
+                    .or_value(Mutability::Immutable),
+            )
+            .then(text::ascii::ident());
+
+        assert_eq!(
+            binding.parse("let mut x").into_result(),
+            Ok((Mutability::Mutable, "x")),
+        );
+        assert_eq!(
+            binding.parse("let x").into_result(),
+            Ok((Mutability::Immutable, "x")),
+        );
+        // The default does not consume the identifier, even though it starts like `mut`
+        assert_eq!(
+            binding.parse("let mutant").into_result(),
+            Ok((Mutability::Immutable, "mutant")),
+        );
+        assert!(binding.parse("let mut").has_errors());
+
+        // A partially consumed pattern is an error rather than falling back to the default
+        let mutability = just::<_, _, extra::Err<Simple<char>>>("mut")
+            .then(just(' '))
+            .to(Mutability::Mutable)
+            .or_value(Mutability::Immutable)
+            .then(any().repeated().collect::<String>());
+        assert_eq!(
+            mutability.parse("mut x").into_result(),
+            Ok((Mutability::Mutable, "x".to_string())),
+        );
+        assert_eq!(
+            mutability.parse("x").into_result(),
+            Ok((Mutability::Immutable, "x".to_string())),
+        );
+        assert!(mutability.parse("mutx").has_errors());
+    }
+
+    #[test]
+    fn max_depth() {
+        let nested = |depth| "(".repeat(depth) + "x" + &")".repeat(depth);
+        let (shallow, deep) = (nested(255), nested(10_000));
+        let (shallow_pair, deep_pair) = (
+            nested(200) + "," + &nested(255),
+            nested(200) + "," + &nested(256),
+        );
+
+        let parens = recursive::<_, _, extra::Err<Rich<char>>, _, _>(|parens| {
+            parens
+                .delimited_by(just('('), just(')'))
+                .or(just('x').ignored())
+        })
+        .with_max_depth(256);
+
+        assert!(!parens.parse(shallow.as_str()).has_errors());
+
+        let errs = parens.parse(deep.as_str()).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].to_string(), "nesting too deep");
+        assert_eq!(errs[0].span(), &SimpleSpan::new(256, 257));
+
+        // The limit is counted from where it is applied, and the tightest enclosing limit wins
+        let items = parens
+            .clone()
+            .with_max_depth(1_000)
+            .separated_by(just(','))
+            .collect::<Vec<_>>();
+        assert!(!items.parse(shallow_pair.as_str()).has_errors());
+        assert!(items.parse(deep_pair.as_str()).has_errors());
+
+        let errs = items.with_max_depth(2).parse("x,((x))").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(4, 5));
+
+        // Reaching the limit in an alternative that was backtracked out of doesn't make it the reason for failure
+        let backtracked = parens
+            .or(just("((").then(just('y')).ignored())
+            .or_rewinding()
+            .with_max_depth(2);
+        let errs = backtracked.parse("(((x)))").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_ne!(errs[0].to_string(), "nesting too deep");
+    }
+
+    #[test]
+    fn choice_longest_match() {
+        let eq = just::<_, _, extra::Err<Simple<char>>>('=').to_slice();
+        let eq_eq = just("==");
+        let forwards = choice_longest((eq, eq_eq)).repeated().collect::<Vec<_>>();
+        let backwards = choice_longest((eq_eq, eq)).repeated().collect::<Vec<_>>();
+
+        for ops in [forwards.boxed(), backwards.boxed()] {
+            assert_eq!(ops.parse("=").into_result(), Ok(vec!["="]));
+            assert_eq!(ops.parse("==").into_result(), Ok(vec!["=="]));
+            assert_eq!(ops.parse("===").into_result(), Ok(vec!["==", "="]));
+        }
+
+        // `choice`, in contrast, takes the first branch that matches
+        assert_eq!(
+            choice((eq, eq_eq))
+                .repeated()
+                .collect::<Vec<_>>()
+                .parse("==")
+                .into_result(),
+            Ok(vec!["=", "="]),
+        );
+
+        // Ties go to the first branch
+        let kw = choice_longest((
+            just::<_, _, extra::Err<Simple<char>>>("let").to(true),
+            text::ascii::ident().to(false),
+        ));
+        assert_eq!(kw.parse("let").into_result(), Ok(true));
+        assert_eq!(kw.parse("letter").into_result(), Ok(false));
+        assert!(kw.parse("1").has_errors());
+
+        // Errors emitted by the longest branch are kept, while those of the other branches are not
+        let recovered = just::<_, _, extra::Err<Simple<char>>>("ab")
+            .recover_with(via_parser(just("ax")))
+            .to(1);
+        let plain = just("a").to(2);
+        let res = choice_longest([recovered.boxed(), plain.boxed()])
+            .lazy()
+            .parse("axb");
+        assert_eq!(res.output(), Some(&1));
+        assert_eq!(res.errors().len(), 1);
+        let res = choice_longest(vec![plain.boxed(), recovered.boxed()]).parse("a");
+        assert_eq!(res.into_result(), Ok(2));
+    }
+
+    #[test]
+    fn choice_trie_longest_match() {
+        let op = choice_trie::<_, _, _, _, extra::Err<Simple<char>>>([
+            ("<", 1),
+            ("<<", 2),
+            ("<<=", 3),
+            ("<=", 4),
+            ("<", 5),
+        ]);
+        let ops = op.clone().repeated().collect::<Vec<_>>();
+
+        assert_eq!(ops.parse("<").into_result(), Ok(vec![1]));
+        assert_eq!(ops.parse("<<").into_result(), Ok(vec![2]));
+        assert_eq!(ops.parse("<<=").into_result(), Ok(vec![3]));
+        // Backtracks to the longest complete literal when a longer one is only partially present
+        assert_eq!(ops.parse("<<<=").into_result(), Ok(vec![2, 4]));
+        assert_eq!(ops.parse("<=<<=<").into_result(), Ok(vec![4, 3, 1]));
+
+        let err = op.parse("=").into_errors();
+        assert_eq!(err.len(), 1);
+        assert_eq!(err[0].span(), &SimpleSpan::new(0, 1));
+
+        // Expected tokens are listed in the order the literals were given
+        let kw = choice_trie::<_, _, _, _, extra::Err<Rich<char>>>([
+            ("let", 0),
+            ("if", 1),
+            ("else", 2),
+            ("fn", 3),
+            ("loop", 4),
+            ("in", 5),
+        ]);
+        let err = kw.parse("x").into_errors();
+        assert_eq!(
+            err[0].expected().cloned().collect::<Vec<_>>(),
+            ['l', 'i', 'e', 'f']
+                .map(|c| crate::error::RichPattern::Token(c.into()))
+                .to_vec(),
+        );
+
+        // Literals are matched against an input of tokens, not just chars
+        let kw =
+            choice_trie::<_, _, _, &[u8], extra::Default>([(&b"in"[..], "in"), (b"int", "int")]);
+        assert_eq!(kw.parse(b"int").into_result(), Ok("int"));
+        assert_eq!(kw.parse(b"in").into_result(), Ok("in"));
+        assert!(kw.parse(b"i").has_errors());
+    }
+
+    #[test]
+    fn choice_trie_many_operators() {
+        let ops = [
+            "+", "-", "*", "/", "%", "^", "!", "&", "|", "&&", "||", "<<", ">>", "+=", "-=", "*=",
+            "/=", "%=", "^=", "&=", "|=", "<<=", ">>=", "=", "==", "!=", ">", "<", ">=", "<=", "@",
+            ".", "..", "..=", "...", ",", ";", ":", "::", "->", "=>", "#", "$", "?", "~", "<-",
+            "<=>", "**", "**=", "//",
+        ];
+        assert_eq!(ops.len(), 50);
+
+        let trie = choice_trie::<_, _, _, _, extra::Err<Simple<char>>>(
+            ops.iter().enumerate().map(|(i, op)| (*op, i)),
+        )
+        .padded()
+        .repeated()
+        .collect::<Vec<_>>();
+
+        // Reference parser: try the longest literals first so that `choice` also finds the longest match
+        let mut by_len = ops.iter().enumerate().collect::<Vec<_>>();
+        by_len.sort_by_key(|(_, op)| core::cmp::Reverse(op.len()));
+        let reference = choice(
+            by_len
+                .into_iter()
+                .map(|(i, op)| just::<_, _, extra::Err<Simple<char>>>(*op).to(i))
+                .collect::<Vec<_>>(),
+        )
+        .padded()
+        .repeated()
+        .collect::<Vec<_>>();
+
+        let input = ops
+            .iter()
+            .cycle()
+            .take(1000)
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let expected = (0..1000).map(|i| i % ops.len()).collect::<Vec<_>>();
+        assert_eq!(
+            trie.parse(input.as_str()).into_result(),
+            Ok(expected.clone())
+        );
+        assert_eq!(reference.parse(input.as_str()).into_result(), Ok(expected));
+
+        let dense = "<<=>>=..=...<=>**=";
+        assert_eq!(
+            trie.parse(dense).into_result(),
+            reference.parse(dense).into_result(),
+        );
+    }
+
+    #[test]
+    fn unicode_str() {
+        let input = "🄯🄚🹠🴎🄐🝋🰏🄂🬯🈦g🸵🍩🕔🈳2🬙🨞🅢🭳🎅h🵚🧿🏩🰬k🠡🀔🈆🝹🤟🉗🴟📵🰄🤿🝜🙘🹄5🠻🡉🱖🠓";
+        let mut own = crate::input::InputOwn::<_, extra::Default>::new(input);
+        let mut inp = own.as_ref_start();
+
+        while let Some(_c) = inp.next() {}
+    }
+
+    #[test]
+    fn iter() {
+        use crate::prelude::*;
+
+        fn parser<'a>() -> impl IterParser<'a, &'a str, char> {
+            any().repeated()
+        }
+
+        let mut chars = String::new();
+        for c in parser().parse_iter("abcdefg").into_result().unwrap() {
+            chars.push(c);
+        }
+
+        assert_eq!(&chars, "abcdefg");
+    }
+
+    #[test]
+    #[cfg(feature = "memoization")]
+    fn exponential() {
+        use crate::prelude::*;
+
+        fn parser<'a>() -> impl Parser<'a, &'a str, String> {
+            recursive(|expr| {
+                let atom = any()
+                    .filter(|c: &char| c.is_alphabetic())
+                    .repeated()
+                    .at_least(1)
+                    .collect()
+                    .or(expr.delimited_by(just('('), just(')')));
+
+                atom.clone()
+                    .then_ignore(just('+'))
+                    .then(atom.clone())
+                    .map(|(a, b)| format!("{}{}", a, b))
+                    .memoized()
+                    .or(atom)
+                    .or_rewinding()
+            })
+            .then_ignore(end())
+        }
+
+        parser()
+            .parse("((((((((((((((((((((((((((((((a+b))))))))))))))))))))))))))))))")
+            .into_result()
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "memoization")]
+    fn left_recursive() {
+        use crate::prelude::*;
+
+        fn parser<'a>() -> impl Parser<'a, &'a str, String> {
+            recursive(|expr| {
+                let atom = any()
+                    .filter(|c: &char| c.is_alphabetic())
+                    .repeated()
+                    .at_least(1)
+                    .collect();
+
+                let sum = expr
+                    .clone()
+                    .then_ignore(just('+'))
+                    .then(expr)
+                    .map(|(a, b)| format!("{}{}", a, b))
+                    .memoized();
+
+                sum.or(atom).or_rewinding()
+            })
+            .then_ignore(end())
+        }
+
+        assert_eq!(parser().parse("a+b+c").into_result().unwrap(), "abc");
+    }
+
+    #[test]
+    #[cfg(feature = "memoization")]
+    fn memoized_left_recursive() {
+        use crate::prelude::*;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Expr {
+            Num(u32),
+            Sub(Box<Expr>, Box<Expr>),
+            Call(Box<Expr>),
+        }
+
+        fn parser<'a>(calls: &'a AtomicUsize) -> impl Parser<'a, &'a str, Expr> + 'a {
+            let num = any()
+                .filter(move |c: &char| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    c.is_ascii_digit()
+                })
+                .map(|c| Expr::Num(c.to_digit(10).unwrap()));
+
+            // expr = expr '-' num | expr '()' | num
+            recursive(|expr| {
+                choice((
+                    expr.clone()
+                        .then_ignore(just('-'))
+                        .then(num)
+                        .map(|(l, r)| Expr::Sub(Box::new(l), Box::new(r))),
+                    expr.then_ignore(just("()"))
+                        .map(|f| Expr::Call(Box::new(f))),
+                    num,
+                ))
+                .memoized_left_recursive()
+            })
+            .then_ignore(end())
+        }
+
+        let calls = AtomicUsize::new(0);
+        let sub = |l, r| Expr::Sub(Box::new(l), Box::new(r));
+        assert_eq!(
+            parser(&calls).parse("1-2()-3").into_result(),
+            Ok(sub(
+                Expr::Call(Box::new(sub(Expr::Num(1), Expr::Num(2)))),
+                Expr::Num(3)
+            )),
+        );
+        assert_eq!(parser(&calls).parse("7").into_result(), Ok(Expr::Num(7)));
+        assert!(parser(&calls).parse("-1").has_errors());
+        assert!(parser(&calls).parse("1-").has_errors());
+
+        // Each growth step reuses the previous seed, so the number of attempts to parse a number grows linearly
+        for len in [10, 100, 1000] {
+            let input = vec!["1"; len].join("-");
+            calls.store(0, Ordering::Relaxed);
+            assert!(parser(&calls).parse(&input).into_result().is_ok());
+            let calls = calls.load(Ordering::Relaxed);
+            assert!(calls <= 4 * len, "{calls} calls for {len} terms");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "extension")]
+    fn extension_combinator() {
+        use crate::{
+            extension::v1::{Ext, ExtParser},
+            extra::ParserExtra,
+            input::InputRef,
+        };
+
+        // Tries both parsers and keeps whichever consumed more input
+        struct Longest<A, B>(A, B);
+
+        impl<'a, I, O, E, A, B> ExtParser<'a, I, O, E> for Longest<A, B>
+        where
+            I: Input<'a>,
+            E: ParserExtra<'a, I>,
+            A: Parser<'a, I, O, E>,
+            B: Parser<'a, I, O, E>,
+        {
+            fn parse(&self, inp: &mut InputRef<'a, '_, I, E>) -> Result<O, E::Error> {
+                let before = inp.save();
+                let a = inp.parse(&self.0).map(|out| (out, inp.save()));
+                inp.rewind(before);
+                let b = inp.parse(&self.1).map(|out| (out, inp.save()));
+                match (a, b) {
+                    (Ok((a, a_end)), Ok((_, b_end))) if a_end.offset() >= b_end.offset() => {
+                        inp.rewind(a_end);
+                        Ok(a)
+                    }
+                    (Ok((a, a_end)), Err(_)) => {
+                        inp.rewind(a_end);
+                        Ok(a)
+                    }
+                    (_, Ok((b, b_end))) => {
+                        inp.rewind(b_end);
+                        Ok(b)
+                    }
+                    (Err(a), Err(b)) => Err(a.merge(b)),
+                }
+            }
+        }
+
+        fn longest<A, B>(a: A, b: B) -> Ext<Longest<A, B>> {
+            Ext(Longest(a, b))
+        }
+
+        let word = longest(
+            just::<_, _, extra::Err<Simple<char>>>("for").to_slice(),
+            text::ascii::ident(),
+        );
+
+        assert_eq!(word.parse("for").into_result(), Ok("for"));
+        assert_eq!(word.parse("format").into_result(), Ok("format"));
+        assert!(word.parse("1").has_errors());
+
+        // Extension combinators compose with built-in ones
+        let words = word.padded().repeated().collect::<Vec<_>>();
+        assert_eq!(
+            words.parse("for forty fo").into_result(),
+            Ok(vec!["for", "forty", "fo"]),
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    mod debug_asserts {
+        use crate::prelude::*;
+
+        // TODO panic when left recursive parser is detected
+        // #[test]
+        // #[should_panic]
+        // fn debug_assert_left_recursive() {
+        //     recursive(|expr| {
+        //         let atom = any::<&str, extra::Default>()
+        //             .filter(|c: &char| c.is_alphabetic())
+        //             .repeated()
+        //             .at_least(1)
+        //             .collect();
+
+        //         let sum = expr
+        //             .clone()
+        //             .then_ignore(just('+'))
+        //             .then(expr)
+        //             .map(|(a, b)| format!("{}{}", a, b));
+
+        //         sum.or(atom)
+        //     })
+        //     .then_ignore(end())
+        //     .parse("a+b+c");
+        // }
+
+        #[test]
+        #[should_panic]
+        #[cfg(debug_assertions)]
+        fn debug_assert_collect() {
+            empty::<&str, extra::Default>()
+                .to(())
+                .repeated()
+                .collect::<()>()
+                .parse("a+b+c")
+                .unwrap();
+        }
+
+        #[test]
+        #[should_panic]
+        #[cfg(debug_assertions)]
+        fn debug_assert_separated_by() {
+            empty::<&str, extra::Default>()
+                .to(())
+                .separated_by(empty())
+                .collect::<()>()
+                .parse("a+b+c");
+        }
+
+        #[test]
+        fn debug_assert_separated_by2() {
+            assert_eq!(
+                empty::<&str, extra::Default>()
+                    .to(())
+                    .separated_by(just(','))
+                    .count()
+                    .parse(",")
+                    .unwrap(),
+                2
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        #[cfg(debug_assertions)]
+        fn debug_assert_foldl() {
             assert_eq!(
                 empty::<&str, extra::Default>()
                     .to(1)
@@ -3298,6 +7672,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn boxed_local() {
+        use alloc::rc::Rc;
+        use core::cell::RefCell;
+
+        let counts = Rc::new(RefCell::new(Vec::new()));
+        let item = {
+            let counts = counts.clone();
+            text::int::<_, _, extra::Err<Simple<char>>>(10)
+                .map(move |n: &str| {
+                    counts.borrow_mut().push(n.len());
+                    n
+                })
+                .boxed_local()
+        };
+
+        // Boxed parsers of the same type can be combined dynamically, and clones share the captured state
+        let mut list = item.clone();
+        for sep in [',', ';'] {
+            list = list
+                .clone()
+                .then_ignore(just(sep))
+                .then(item.clone())
+                .map(|(_, b)| b)
+                .boxed_local();
+        }
+
+        assert_eq!(list.parse("1,22;333").into_result(), Ok("333"));
+        assert_eq!(*counts.borrow(), [1, 2, 3]);
+        assert!(list.parse("1;22,333").has_errors());
+    }
+
     #[test]
     fn box_impl() {
         fn parser<'a>() -> impl Parser<'a, &'a str, Vec<u64>> {
@@ -3416,6 +7822,93 @@ mod tests {
         )
     }
 
+    #[test]
+    fn add_expected() {
+        // An error that accumulates expected inputs in place
+        #[derive(Debug, PartialEq)]
+        struct Expected(Vec<Option<char>>);
+
+        impl<'a> crate::Error<'a, &'a str> for Expected {
+            fn expected_found<E: IntoIterator<Item = Option<crate::MaybeRef<'a, char>>>>(
+                expected: E,
+                _found: Option<crate::MaybeRef<'a, char>>,
+                _span: SimpleSpan,
+            ) -> Self {
+                let mut this = Expected(Vec::new());
+                crate::Error::<&str>::add_expected(&mut this, expected);
+                this
+            }
+
+            fn add_expected<E: IntoIterator<Item = Option<crate::MaybeRef<'a, char>>>>(
+                &mut self,
+                expected: E,
+            ) {
+                self.0
+                    .extend(expected.into_iter().map(|e| e.as_deref().copied()));
+            }
+
+            fn merge_expected_found<E: IntoIterator<Item = Option<crate::MaybeRef<'a, char>>>>(
+                mut self,
+                expected: E,
+                _found: Option<crate::MaybeRef<'a, char>>,
+                _span: SimpleSpan,
+            ) -> Self {
+                crate::Error::<&str>::add_expected(&mut self, expected);
+                self
+            }
+        }
+
+        // An error that only knows how to merge whole errors, relying on the default `merge_expected_found`
+        #[derive(Debug, PartialEq)]
+        struct MergeOnly(Vec<Option<char>>);
+
+        impl<'a> crate::Error<'a, &'a str> for MergeOnly {
+            fn expected_found<E: IntoIterator<Item = Option<crate::MaybeRef<'a, char>>>>(
+                expected: E,
+                _found: Option<crate::MaybeRef<'a, char>>,
+                _span: SimpleSpan,
+            ) -> Self {
+                MergeOnly(
+                    expected
+                        .into_iter()
+                        .map(|e| e.as_deref().copied())
+                        .collect(),
+                )
+            }
+
+            fn merge(mut self, other: Self) -> Self {
+                self.0.extend(other.0);
+                self
+            }
+        }
+
+        fn abc<'a, E: crate::Error<'a, &'a str> + 'a>(
+        ) -> impl Parser<'a, &'a str, char, extra::Err<E>> + Clone {
+            choice((just('a'), just('b'), just('c'))).then_ignore(end())
+        }
+
+        let parser = abc::<Expected>();
+        assert_eq!(
+            parser.parse("d").into_errors(),
+            vec![Expected(vec![Some('a'), Some('b'), Some('c')])],
+        );
+        assert_eq!(parser.parse("ab").into_errors(), vec![Expected(vec![None])]);
+        assert_eq!(
+            just('x').or(parser).parse("").into_errors(),
+            vec![Expected(vec![Some('x'), Some('a'), Some('b'), Some('c')])],
+        );
+
+        let parser = abc::<MergeOnly>();
+        assert_eq!(
+            parser.parse("d").into_errors(),
+            vec![MergeOnly(vec![Some('a'), Some('b'), Some('c')])],
+        );
+        assert_eq!(
+            just('x').or(parser).parse("").into_errors(),
+            vec![MergeOnly(vec![Some('x'), Some('a'), Some('b'), Some('c')])],
+        );
+    }
+
     #[test]
     fn into_iter_no_error() {
         fn parser<'a>() -> impl Parser<'a, &'a str, (), extra::Err<MyErr>> {
@@ -25,7 +25,21 @@
 //!
 //! Fold functions have several overloads, allowing you to make use of only the operands, the operands and the
 //! operators, and even additionally [`MapExtra`], providing access to the span, slice, and parser state. See the
-//! documentation for each function to see which fold signatures can be used.
+//! documentation for each function to see which fold signatures can be used. Because several signatures are accepted,
+//! a closure that takes a [`MapExtra`] usually needs the type of that argument to be written out.
+//!
+//! Operators that need to reject some of their operands (such as assignment, which requires a place expression on its
+//! left-hand side) can be defined with [`try_infix`], which takes a fallible fold function.
+//!
+//! Instead of a tuple, operators can also be given as a [`PrattOps`] table, which is built up one operator at a time.
+//!
+//! # Recursion
+//!
+//! A chain of left-associative operators, such as `1 + 2 + 3 + ...`, is folded in a loop: parsing each right-hand
+//! operand recurses only once, and the operand's parse stops at the next operator of the same strength. The
+//! recursion depth needed for such a chain is therefore constant, however long it is. Right-associative and prefix
+//! operators nest their operands, so chains of them need recursion proportional to their length. If that is a
+//! concern for untrusted input, the depth can be bounded with [`Parser::with_max_depth`].
 //!
 //! # Examples
 //!
@@ -91,19 +105,48 @@
 
 use super::*;
 
-trait Operator<'a, I, O, E>
+/// An operator for a pratt parser, created by [`infix`], [`try_infix`], [`prefix`], or [`postfix`].
+///
+/// This trait is sealed and cannot be implemented by types outside of this crate.
+pub trait Operator<'a, I, O, E>: Sealed
 where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
 {
+    #[doc(hidden)]
     type Op;
+    #[doc(hidden)]
     type OpParser: Parser<'a, I, Self::Op, E>;
+    #[doc(hidden)]
     const IS_INFIX: bool = false;
+    #[doc(hidden)]
     const IS_PREFIX: bool = false;
+    #[doc(hidden)]
     const IS_POSTFIX: bool = false;
+    #[doc(hidden)]
+    const IS_FALLIBLE: bool = false;
 
+    #[doc(hidden)]
+    fn is_infix(&self) -> bool {
+        Self::IS_INFIX
+    }
+    #[doc(hidden)]
+    fn is_prefix(&self) -> bool {
+        Self::IS_PREFIX
+    }
+    #[doc(hidden)]
+    fn is_postfix(&self) -> bool {
+        Self::IS_POSTFIX
+    }
+    #[doc(hidden)]
+    fn is_fallible(&self) -> bool {
+        Self::IS_FALLIBLE
+    }
+    #[doc(hidden)]
     fn op_parser(&self) -> &Self::OpParser;
+    #[doc(hidden)]
     fn associativity(&self) -> Associativity;
+    #[doc(hidden)]
     fn fold_infix(
         &self,
         _lhs: O,
@@ -113,9 +156,21 @@ where
     ) -> O {
         unreachable!()
     }
+    #[doc(hidden)]
+    fn try_fold_infix(
+        &self,
+        lhs: (O, I::Span),
+        op: Self::Op,
+        rhs: (O, I::Span),
+        extra: &mut MapExtra<'a, '_, I, E>,
+    ) -> Result<O, E::Error> {
+        Ok(self.fold_infix(lhs.0, op, rhs.0, extra))
+    }
+    #[doc(hidden)]
     fn fold_prefix(&self, _op: Self::Op, _rhs: O, _extra: &mut MapExtra<'a, '_, I, E>) -> O {
         unreachable!()
     }
+    #[doc(hidden)]
     fn fold_postfix(&self, _lhs: O, _op: Self::Op, _extra: &mut MapExtra<'a, '_, I, E>) -> O {
         unreachable!()
     }
@@ -156,7 +211,68 @@ pub fn right(binding_power: u16) -> Associativity {
 /// Not-associative operators are evaluated from the left-most terms, moving rightward.
 pub fn non(binding_power: u16) -> Associativity {Associativity::Non(binding_power)}
 
+/// A precedence level that can be shared by several operators, so that operators of equal precedence (such as `+`
+/// and `-`) are guaranteed to use the same binding power.
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// let sum = Prec::level(1);
+/// let product = Prec::level(2);
+///
+/// let int = text::int::<_, _, extra::Default>(10).from_str::<i64>().unwrapped();
+/// let expr = int.pratt((
+///     infix(sum.left(), just('+'), |l, r| l + r),
+///     infix(sum.left(), just('-'), |l, r| l - r),
+///     infix(product.left(), just('*'), |l, r| l * r),
+/// ));
+///
+/// assert_eq!(expr.parse("1-2*3+4").into_result(), Ok(-1));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Prec(u16);
+
+impl Prec {
+    /// Create a precedence level with the given binding power. Higher levels bind more tightly.
+    pub const fn level(binding_power: u16) -> Self {
+        Self(binding_power)
+    }
+
+    /// The binding power of this level, for use with [`prefix`] and [`postfix`] operators.
+    pub const fn binding_power(&self) -> u16 {
+        self.0
+    }
+
+    /// A left [`Associativity`] at this level (see [`left`]).
+    pub const fn left(&self) -> Associativity {
+        Associativity::Left(self.0)
+    }
+
+    /// A right [`Associativity`] at this level (see [`right`]).
+    pub const fn right(&self) -> Associativity {
+        Associativity::Right(self.0)
+    }
+
+    /// A non-associative [`Associativity`] at this level (see [`non`]).
+    pub const fn non(&self) -> Associativity {
+        Associativity::Non(self.0)
+    }
+}
+
 impl Associativity {
+    /// The binding power of an operator with this associativity. Operators with higher binding powers bind more
+    /// tightly.
+    ///
+    /// ```
+    /// # use chumsky::pratt::*;
+    /// assert_eq!(left(3).binding_power(), 3);
+    /// assert!(right(1).binding_power() < non(2).binding_power());
+    /// ```
+    pub const fn binding_power(&self) -> u16 {
+        match self {
+            Self::Left(x) | Self::Right(x) | Self::Non(x) => *x,
+        }
+    }
+
     fn left_power(&self) -> u32 {
         match self {
             Self::Left(x) => *x as u32 * 3,
@@ -172,14 +288,6 @@ impl Associativity {
             Self::Non(x) => *x as u32 * 3 + 1,
         }
     }
-
-    fn next_power(&self) -> u32 {
-        match self {
-            Self::Left(x) => *x as u32 * 3,
-            Self::Right(x) => *x as u32 * 3,
-            Self::Non(x) => *x as u32 * 3 - 1,
-        }
-    }
 }
 
 /// See [`infix`].
@@ -203,6 +311,20 @@ impl<A: Clone, F: Clone, Op, Args> Clone for Infix<A, F, Op, Args> {
     }
 }
 
+impl<A, F, Op, Args> Sealed for Infix<A, F, Op, Args> {}
+
+impl<A, F, Op, Args> Infix<A, F, Op, Args> {
+    /// The associativity of this operator, including its binding power.
+    pub const fn associativity(&self) -> Associativity {
+        self.associativity
+    }
+
+    /// The binding power of this operator (see [`Associativity::binding_power`]).
+    pub const fn binding_power(&self) -> u16 {
+        self.associativity.binding_power()
+    }
+}
+
 /// Specify a binary infix operator for a pratt parser with the given associativity, binding power, and
 /// [fold function](crate::pratt#fold-functions).
 ///
@@ -262,6 +384,136 @@ infix_op!(
     |f: Fn(O, Op, O, &mut MapExtra<'a, '_, I, E>) -> O, lhs, op, rhs, extra| f(lhs, op, rhs, extra)
 );
 
+/// See [`try_infix`].
+pub struct TryInfix<A, F, Op, I, O, E> {
+    op_parser: A,
+    fold: F,
+    associativity: Associativity,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(Op, I, O, E)>,
+}
+
+impl<A: Copy, F: Copy, Op, I, O, E> Copy for TryInfix<A, F, Op, I, O, E> {}
+impl<A: Clone, F: Clone, Op, I, O, E> Clone for TryInfix<A, F, Op, I, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            op_parser: self.op_parser.clone(),
+            fold: self.fold.clone(),
+            associativity: self.associativity,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<A, F, Op, I, O, E> Sealed for TryInfix<A, F, Op, I, O, E> {}
+
+impl<A, F, Op, I, O, E> TryInfix<A, F, Op, I, O, E> {
+    /// The associativity of this operator, including its binding power.
+    pub const fn associativity(&self) -> Associativity {
+        self.associativity
+    }
+
+    /// The binding power of this operator (see [`Associativity::binding_power`]).
+    pub const fn binding_power(&self) -> u16 {
+        self.associativity.binding_power()
+    }
+}
+
+/// Specify a binary infix operator for a pratt parser, like [`infix`], but with a fallible
+/// [fold function](crate::pratt#fold-functions) that may reject its operands.
+///
+/// This is useful for operators that place restrictions on their operands that can't be expressed in the grammar
+/// itself. For example, the left operand of an assignment must usually be a place expression (an 'lvalue').
+///
+/// The fold function receives each operand along with its span, and must have the following signature:
+///
+/// ```ignore
+/// impl Fn((O, I::Span), Op, (O, I::Span), &mut MapExtra<'a, '_, I, E>) -> Result<O, E::Error>
+/// ```
+///
+/// If the fold function returns an error, the expression fails to parse with that error.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, pratt::*};
+/// #[derive(Debug, PartialEq)]
+/// enum Expr {
+///     Var(char),
+///     Num(u32),
+///     Assign(Box<Self>, Box<Self>),
+/// }
+///
+/// let atom = choice((
+///     any::<_, extra::Err<Rich<char>>>().filter(char::is_ascii_lowercase).map(Expr::Var),
+///     text::int(10).from_str().unwrapped().map(Expr::Num),
+/// ))
+/// .padded();
+///
+/// let expr = atom.pratt((try_infix(
+///     right(1),
+///     just('='),
+///     |(lhs, lhs_span), _, (rhs, _), _| match lhs {
+///         Expr::Var(_) => Ok(Expr::Assign(Box::new(lhs), Box::new(rhs))),
+///         _ => Err(Rich::custom(lhs_span, "cannot assign to this expression")),
+///     },
+/// ),));
+///
+/// assert_eq!(
+///     expr.parse("x = 2").into_result(),
+///     Ok(Expr::Assign(Box::new(Expr::Var('x')), Box::new(Expr::Num(2)))),
+/// );
+/// assert!(expr.parse("1 = 2").has_errors());
+/// ```
+pub const fn try_infix<'a, A, F, Op, I, O, E>(
+    associativity: Associativity,
+    op_parser: A,
+    fold: F,
+) -> TryInfix<A, F, Op, I, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    F: Fn((O, I::Span), Op, (O, I::Span), &mut MapExtra<'a, '_, I, E>) -> Result<O, E::Error>,
+{
+    TryInfix {
+        op_parser,
+        fold,
+        associativity,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, I, O, E, A, F, Op> Operator<'a, I, O, E> for TryInfix<A, F, Op, I, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, Op, E>,
+    F: Fn((O, I::Span), Op, (O, I::Span), &mut MapExtra<'a, '_, I, E>) -> Result<O, E::Error>,
+{
+    type Op = Op;
+    type OpParser = A;
+    const IS_INFIX: bool = true;
+    const IS_FALLIBLE: bool = true;
+    #[inline(always)]
+    fn op_parser(&self) -> &Self::OpParser {
+        &self.op_parser
+    }
+    #[inline(always)]
+    fn associativity(&self) -> Associativity {
+        self.associativity
+    }
+    #[inline(always)]
+    fn try_fold_infix(
+        &self,
+        lhs: (O, I::Span),
+        op: Self::Op,
+        rhs: (O, I::Span),
+        extra: &mut MapExtra<'a, '_, I, E>,
+    ) -> Result<O, E::Error> {
+        (self.fold)(lhs, op, rhs, extra)
+    }
+}
+
 /// See [`prefix`].
 pub struct Prefix<A, F, Op, Args> {
     op_parser: A,
@@ -283,6 +535,21 @@ impl<A: Clone, F: Clone, Op, Args> Clone for Prefix<A, F, Op, Args> {
     }
 }
 
+impl<A, F, Op, Args> Sealed for Prefix<A, F, Op, Args> {}
+
+impl<A, F, Op, Args> Prefix<A, F, Op, Args> {
+    /// The binding power of this operator.
+    pub const fn binding_power(&self) -> u16 {
+        self.binding_power
+    }
+
+    /// The associativity of this operator, as used to compare it with other operators. Unary operators are
+    /// left-associative.
+    pub const fn associativity(&self) -> Associativity {
+        Associativity::Left(self.binding_power)
+    }
+}
+
 /// Specify a unary prefix operator for a pratt parser with the given binding power and
 /// [fold function](crate::pratt#fold-functions).
 ///
@@ -358,6 +625,21 @@ impl<A: Clone, F: Clone, Op, Args> Clone for Postfix<A, F, Op, Args> {
     }
 }
 
+impl<A, F, Op, Args> Sealed for Postfix<A, F, Op, Args> {}
+
+impl<A, F, Op, Args> Postfix<A, F, Op, Args> {
+    /// The binding power of this operator.
+    pub const fn binding_power(&self) -> u16 {
+        self.binding_power
+    }
+
+    /// The associativity of this operator, as used to compare it with other operators. Unary operators are
+    /// left-associative.
+    pub const fn associativity(&self) -> Associativity {
+        Associativity::Left(self.binding_power)
+    }
+}
+
 /// Specify a unary postfix operator for a pratt parser with the given binding power and
 /// [fold function](crate::pratt#fold-functions).
 ///
@@ -414,202 +696,1358 @@ postfix_op!(|f: Fn(O, Op, &mut MapExtra<'a, '_, I, E>) -> O, lhs, op, extra| f(l
 
 /// See [`Parser::pratt`].
 #[derive(Copy, Clone)]
-pub struct Pratt<Atom, Ops> {
+pub struct Pratt<Atom, Ops, R = (), P = (), G = ()> {
     pub(crate) atom: Atom,
     pub(crate) ops: Ops,
+    pub(crate) recovery: R,
+    pub(crate) padding: P,
+    pub(crate) groups: G,
 }
 
-macro_rules! impl_pratt_for_tuple {
-    () => {};
-    ($head:ident $($X:ident)*) => {
-        impl_pratt_for_tuple!($($X)*);
-        impl_pratt_for_tuple!(~ $head $($X)*);
-    };
-    (~ $($X:ident)+) => {
-        #[allow(unused_variables, non_snake_case)]
-        impl<'a, Atom, $($X),*> Pratt<Atom, ($($X,)*)> {
-            #[inline]
-            fn pratt_go<M: Mode, I, O, E>(&self, inp: &mut InputRef<'a, '_, I, E>, min_power: u32) -> PResult<M, O>
-            where
-                I: Input<'a>,
-                E: ParserExtra<'a, I>,
-                Atom: Parser<'a, I, O, E>,
-                $($X: Operator<'a, I, O, E>),*
-            {
-                let pre_expr = inp.save();
-                let mut lhs = 'choice: {
-                    let ($($X,)*) = &self.ops;
-
-                    // Prefix unary operators
-                    $(
-                        if $X::IS_PREFIX {
-                            match $X.op_parser().go::<M>(inp) {
-                                Ok(op) => {
-                                    match recursive::recurse(|| self.pratt_go::<M, _, _, _>(inp, $X.associativity().left_power())) {
-                                        Ok(rhs) => break 'choice M::combine(op, rhs, |op, rhs| {
-                                            $X.fold_prefix(op, rhs, &mut MapExtra::new(pre_expr.offset(), inp))
-                                        }),
-                                        Err(()) => inp.rewind(pre_expr),
-                                    }
-                                },
-                                Err(()) => inp.rewind(pre_expr),
-                            }
-                        }
-                    )*
-
-                    self.atom.go::<M>(inp)?
-                };
-
-                loop {
-                    let ($($X,)*) = &self.ops;
-
-                    let pre_op = inp.save();
-
-                    // Postfix unary operators
-                    $(
-                        let assoc = $X.associativity();
-                        if $X::IS_POSTFIX && assoc.right_power() >= min_power {
-                            match $X.op_parser().go::<M>(inp) {
-                                Ok(op) => {
-                                    lhs = M::combine(lhs, op, |lhs, op| {
-                                        $X.fold_postfix(lhs, op, &mut MapExtra::new(pre_expr.offset(), inp))
-                                    });
-                                    continue
-                                },
-                                Err(()) => inp.rewind(pre_op),
-                            }
-                        }
-                    )*
-
-                    // Infix binary operators
-                    $(
-                        let assoc = $X.associativity();
-                        let mut upper_bound: u32 = u32::MAX;
-                        if $X::IS_INFIX && assoc.left_power() >= min_power && assoc.left_power() <= upper_bound {
-                            match $X.op_parser().go::<M>(inp) {
-                                Ok(op) => match recursive::recurse(|| self.pratt_go::<M, _, _, _>(inp, assoc.right_power())) {
-                                    Ok(rhs) => {
-                                        lhs = M::combine(
-                                            M::combine(lhs, rhs, |lhs, rhs| (lhs, rhs)),
-                                            op,
-                                            |(lhs, rhs), op| {
-                                                $X.fold_infix(lhs, op, rhs, &mut MapExtra::new(pre_expr.offset(), inp))
-                                            },
-                                        );
-                                        upper_bound = assoc.next_power();
-                                        continue
-                                    },
-                                    Err(()) => inp.rewind(pre_op),
-                                },
-                                Err(()) => inp.rewind(pre_op),
-                            }
-                        }
-                    )*
-
-                    inp.rewind(pre_op);
-                    break;
-                }
-
-                Ok(lhs)
-            }
+impl<Atom, Ops, R, G> Pratt<Atom, Ops, R, (), G> {
+    /// Allow whitespace before and after every atom and operator, as if each of them were wrapped in
+    /// [`Parser::padded`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use chumsky::pratt::*;
+    ///
+    /// let atom = text::int::<_, _, extra::Err<Simple<char>>>(10).from_str().unwrapped();
+    ///
+    /// let expr = atom
+    ///     .pratt((
+    ///         prefix(2, just('-'), |x: i64| -x),
+    ///         infix(left(1), just('*'), |l, r| l * r),
+    ///         infix(left(0), just('+'), |l, r| l + r),
+    ///     ))
+    ///     .padded_tokens();
+    ///
+    /// assert_eq!(expr.parse(" 1 + - 2 *3 ").into_result(), Ok(-5));
+    /// ```
+    pub fn padded_tokens(self) -> Pratt<Atom, Ops, R, Whitespace, G> {
+        Pratt {
+            atom: self.atom,
+            ops: self.ops,
+            recovery: self.recovery,
+            padding: Whitespace,
+            groups: self.groups,
         }
+    }
+}
 
-        #[allow(unused_variables, non_snake_case)]
-        impl<'a, I, O, E, Atom, $($X),*> ParserSealed<'a, I, O, E> for Pratt<Atom, ($($X,)*)>
-        where
-            I: Input<'a>,
-            E: ParserExtra<'a, I>,
-            Atom: Parser<'a, I, O, E>,
-            $($X: Operator<'a, I, O, E>),*
-        {
-            fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-                self.pratt_go::<M, _, _, _>(inp, 0)
-            }
+impl<Atom, Ops, P, G> Pratt<Atom, Ops, (), P, G> {
+    /// Recover from a right-hand operand of an infix operator that fails to parse by applying the given recovery
+    /// strategy (see [`Parser::recover_with`]) in its place.
+    ///
+    /// The strategy is used to parse an operand, usually an error node, from the input following the operator. The
+    /// operator is then folded as usual and parsing continues with the next operator. If the strategy fails too, the
+    /// operator is left unparsed, as it would be without recovery.
+    ///
+    /// Strategies that retry the parser, such as [`skip_then_retry_until`](crate::recovery::skip_then_retry_until), retry the atom parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use chumsky::pratt::*;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Expr {
+    ///     Num(u64),
+    ///     Add(Box<Expr>, Box<Expr>),
+    ///     Error,
+    /// }
+    ///
+    /// let atom = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .from_str()
+    ///     .unwrapped()
+    ///     .map(Expr::Num)
+    ///     .padded();
+    ///
+    /// let add = infix(left(1), just('+').padded(), |l, r| Expr::Add(Box::new(l), Box::new(r)));
+    ///
+    /// let expr = atom
+    ///     .pratt((add,))
+    ///     // Replace any bad operand with a single error token
+    ///     .recover_operand_with(via_parser(none_of("+").padded().to(Expr::Error)));
+    ///
+    /// let (out, errs) = expr.parse("1 + @ + 2").into_output_errors();
+    /// assert_eq!(
+    ///     out,
+    ///     Some(Expr::Add(
+    ///         Box::new(Expr::Add(Box::new(Expr::Num(1)), Box::new(Expr::Error))),
+    ///         Box::new(Expr::Num(2)),
+    ///     )),
+    /// );
+    /// assert_eq!(errs.len(), 1);
+    /// ```
+    pub fn recover_operand_with<S>(self, strategy: S) -> Pratt<Atom, Ops, S, P, G> {
+        Pratt {
+            atom: self.atom,
+            ops: self.ops,
+            recovery: strategy,
+            padding: self.padding,
+            groups: self.groups,
+        }
+    }
+}
 
-            go_extra!(O);
+impl<Atom, Ops, R, P> Pratt<Atom, Ops, R, P> {
+    /// Accept an expression wrapped in `open` and `close`, such as parentheses, anywhere an atom is accepted.
+    ///
+    /// The wrapped expression is parsed by this same pratt parser, starting again from the lowest binding power, so
+    /// groups may be nested and may appear as the operands of any operator. This saves having to build the same thing
+    /// out of [`recursive`](crate::recursive::recursive). A group is treated as an atom: [`Pratt::with_prec`] reports
+    /// no operator for `(1 + 2)`.
+    ///
+    /// If a group fails to parse, the atom parser is tried in its place, so atoms that also start with `open` (such as
+    /// tuples) keep working.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use chumsky::pratt::*;
+    ///
+    /// let atom = text::int::<_, _, extra::Err<Simple<char>>>(10).from_str().unwrapped();
+    ///
+    /// let expr = atom
+    ///     .pratt((
+    ///         prefix(2, just('-'), |x: i64| -x),
+    ///         infix(left(1), just('*'), |l, r| l * r),
+    ///         infix(left(0), just('+'), |l, r| l + r),
+    ///     ))
+    ///     .with_parens(just('('), just(')'));
+    ///
+    /// assert_eq!(expr.parse("(1+2)*3").into_result(), Ok(9));
+    /// assert_eq!(expr.parse("-(1+2)").into_result(), Ok(-3));
+    /// assert_eq!(expr.parse("((2))").into_result(), Ok(2));
+    /// assert!(expr.parse("(1+2").has_errors());
+    /// ```
+    pub fn with_parens<A, B, OA, OB>(
+        self,
+        open: A,
+        close: B,
+    ) -> Pratt<Atom, Ops, R, P, Parens<A, B, OA, OB>> {
+        Pratt {
+            atom: self.atom,
+            ops: self.ops,
+            recovery: self.recovery,
+            padding: self.padding,
+            groups: Parens {
+                open,
+                close,
+                phantom: EmptyPhantom::new(),
+            },
         }
-    };
+    }
 }
 
-impl_pratt_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+impl<Atom, Ops, R, P, G> Pratt<Atom, Ops, R, P, G> {
+    /// Output the associativity of the outermost operator alongside the parsed expression, or `None` if the
+    /// expression is just an atom.
+    ///
+    /// This is useful for deciding whether an expression needs parentheses when it is pretty-printed, or for
+    /// rejecting ambiguous expressions that mix operators of different precedence without parentheses.
+    ///
+    /// See also [`Parser::pratt_with_prec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// use chumsky::pratt::*;
+    ///
+    /// let atom = text::int::<_, _, extra::Err<Simple<char>>>(10).from_str().unwrapped();
+    ///
+    /// let expr = atom
+    ///     .pratt((
+    ///         infix(left(1), just('*'), |l, r| l * r),
+    ///         infix(left(0), just('+'), |l, r| l + r),
+    ///     ))
+    ///     .with_prec();
+    ///
+    /// assert_eq!(expr.parse("2*3").into_result(), Ok((6, Some(left(1)))));
+    /// assert_eq!(expr.parse("2*3+4").into_result(), Ok((10, Some(left(0)))));
+    /// assert_eq!(expr.parse("7").into_result(), Ok((7i64, None)));
+    /// ```
+    pub fn with_prec(self) -> PrattWithPrec<Atom, Ops, R, P, G> {
+        PrattWithPrec { pratt: self }
+    }
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{extra::Err, prelude::*};
+/// See [`Pratt::with_prec`].
+#[derive(Copy, Clone)]
+pub struct PrattWithPrec<Atom, Ops, R = (), P = (), G = ()> {
+    pub(crate) pratt: Pratt<Atom, Ops, R, P, G>,
+}
 
-    fn factorial(x: i64) -> i64 {
-        if x == 0 {
-            1
-        } else {
-            x * factorial(x - 1)
+/// See [`Pratt::with_parens`].
+pub struct Parens<A, B, OA, OB> {
+    pub(crate) open: A,
+    pub(crate) close: B,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(OA, OB)>,
+}
+
+impl<A: Copy, B: Copy, OA, OB> Copy for Parens<A, B, OA, OB> {}
+impl<A: Clone, B: Clone, OA, OB> Clone for Parens<A, B, OA, OB> {
+    fn clone(&self) -> Self {
+        Self {
+            open: self.open.clone(),
+            close: self.close.clone(),
+            phantom: EmptyPhantom::new(),
         }
     }
+}
 
-    fn parser<'a>() -> impl Parser<'a, &'a str, i64> {
-        let atom = text::int(10).padded().from_str::<i64>().unwrapped();
+/// See [`Pratt::padded_tokens`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Whitespace;
 
-        atom.pratt((
-            prefix(2, just('-'), |x: i64| -x),
-            postfix(2, just('!'), factorial),
-            infix(left(0), just('+'), |l, r| l + r),
-            infix(left(0), just('-'), |l, r| l - r),
-            infix(left(1), just('*'), |l, r| l * r),
-            infix(left(1), just('/'), |l, _, r| l / r),
-        ))
-    }
+// Padding around the atoms and operators of a pratt parser, implemented by no padding at all (`()`) and by
+// `Whitespace`
+trait TokenPadding<'a, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    fn pad<M: Mode, O, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        parser: &P,
+    ) -> PResult<M, O>;
+}
 
-    #[test]
-    fn precedence() {
-        assert_eq!(parser().parse("2 + 3 * 4").into_result(), Ok(14));
-        assert_eq!(parser().parse("2 * 3 + 4").into_result(), Ok(10));
+impl<'a, I, E> TokenPadding<'a, I, E> for ()
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline(always)]
+    fn pad<M: Mode, O, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        parser: &P,
+    ) -> PResult<M, O> {
+        parser.go::<M>(inp)
     }
+}
 
-    #[test]
-    fn unary() {
-        assert_eq!(parser().parse("-2").into_result(), Ok(-2));
-        assert_eq!(parser().parse("4!").into_result(), Ok(24));
-        assert_eq!(parser().parse("2 + 4!").into_result(), Ok(26));
-        assert_eq!(parser().parse("-2 + 2").into_result(), Ok(0));
+impl<'a, I, E> TokenPadding<'a, I, E> for Whitespace
+where
+    I: ValueInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: Char,
+{
+    #[inline(always)]
+    fn pad<M: Mode, O, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        parser: &P,
+    ) -> PResult<M, O> {
+        inp.skip_while(|c| c.is_whitespace());
+        let out = parser.go::<M>(inp)?;
+        inp.skip_while(|c| c.is_whitespace());
+        Ok(out)
     }
+}
 
-    // TODO: Make this work
-    // fn parser_dynamic<'a>() -> impl Parser<'a, &'a str, i64> {
-    //     let atom = text::int(10).padded().from_str::<i64>().unwrapped();
+// Groups that may appear in place of an atom, implemented by no groups at all (`()`) and by `Parens`
+trait Grouping<'a, I, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    fn group<'parse, M: Mode, P: TokenPadding<'a, I, E>>(
+        &self,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        padding: &P,
+        expr: impl FnOnce(&mut InputRef<'a, 'parse, I, E>) -> PResult<M, O>,
+    ) -> PResult<M, O>;
+}
 
-    //     atom.pratt(vec![
-    //         prefix(2, just('-'), |x: i64| -x).into(),
-    //         postfix(2, just('!'), factorial).into(),
-    //         infix(left(0), just('+'), |l, r| l + r).into(),
-    //         infix(left(0), just('-'), |l, r| l - r).into(),
-    //         infix(left(1), just('*'), |l, r| l * r).into(),
-    //         infix(left(1), just('/'), |l, _, r| l / r).into(),
-    //     ])
-    // }
+impl<'a, I, O, E> Grouping<'a, I, O, E> for ()
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline(always)]
+    fn group<'parse, M: Mode, P: TokenPadding<'a, I, E>>(
+        &self,
+        _inp: &mut InputRef<'a, 'parse, I, E>,
+        _padding: &P,
+        _expr: impl FnOnce(&mut InputRef<'a, 'parse, I, E>) -> PResult<M, O>,
+    ) -> PResult<M, O> {
+        Err(())
+    }
+}
 
-    enum Expr {
-        Literal(i64),
-        Not(Box<Expr>),
-        Negate(Box<Expr>),
-        Confusion(Box<Expr>),
-        Factorial(Box<Expr>),
-        Value(Box<Expr>),
-        Add(Box<Expr>, Box<Expr>),
-        Sub(Box<Expr>, Box<Expr>),
-        Mul(Box<Expr>, Box<Expr>),
-        Div(Box<Expr>, Box<Expr>),
+impl<'a, I, O, E, A, B, OA, OB> Grouping<'a, I, O, E> for Parens<A, B, OA, OB>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+{
+    #[inline(always)]
+    fn group<'parse, M: Mode, P: TokenPadding<'a, I, E>>(
+        &self,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        padding: &P,
+        expr: impl FnOnce(&mut InputRef<'a, 'parse, I, E>) -> PResult<M, O>,
+    ) -> PResult<M, O> {
+        padding.pad::<Check, _, _>(inp, &self.open)?;
+        let out = expr(inp)?;
+        padding.pad::<Check, _, _>(inp, &self.close)?;
+        Ok(out)
     }
+}
 
-    impl std::fmt::Display for Expr {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            match self {
-                Self::Literal(literal) => write!(f, "{literal}"),
-                Self::Not(right) => write!(f, "(~{right})"),
-                Self::Negate(right) => write!(f, "(-{right})"),
-                Self::Confusion(right) => write!(f, "(§{right})"),
-                Self::Factorial(right) => write!(f, "({right}!)"),
-                Self::Value(right) => write!(f, "({right}$)"),
-                Self::Add(left, right) => write!(f, "({left} + {right})"),
+// Recovery for the right-hand operands of infix operators, implemented by no recovery at all (`()`) and by every
+// recovery strategy
+trait OperandRecovery<'a, I, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    fn recover_operand<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        atom: &P,
+    ) -> PResult<M, O>;
+}
+
+impl<'a, I, O, E> OperandRecovery<'a, I, O, E> for ()
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline(always)]
+    fn recover_operand<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        _inp: &mut InputRef<'a, '_, I, E>,
+        _atom: &P,
+    ) -> PResult<M, O> {
+        Err(())
+    }
+}
+
+impl<'a, I, O, E, S> OperandRecovery<'a, I, O, E> for S
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    S: Strategy<'a, I, O, E>,
+{
+    fn recover_operand<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        atom: &P,
+    ) -> PResult<M, O> {
+        if !inp.can_recover() {
+            return Err(());
+        }
+        let before = inp.save();
+        match self.recover_named::<M, _>(inp, atom) {
+            Ok((out, name)) => {
+                inp.record_recovery(before.offset, name);
+                Ok(out)
+            }
+            Err(()) => {
+                inp.rewind(before);
+                Err(())
+            }
+        }
+    }
+}
+
+// The outcome of trying an operator after an operand
+enum Trailing<T> {
+    // The operator was parsed and folded into the operand
+    Folded(T),
+    // The operator did not apply, leaving the operand as it was
+    Skipped(T),
+    // The operator was parsed, but the expression cannot be completed
+    Failed,
+}
+
+// The operators of a pratt parser, implemented by tuples of operators and by `PrattOps`. Each of `prefix`, `postfix`,
+// and `infix` tries the operators in order using the steps implemented on `Pratt` below.
+trait Operators<'a, I, O, E>: Sized
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    // Whether any operators are infix or postfix, i.e: whether anything may follow an operand
+    fn has_trailing(&self) -> bool;
+    // Whether any operators are fallible, and so need to inspect their operands
+    fn is_fallible(&self) -> bool;
+
+    fn prefix<'parse, M: Mode, Atom, R, P, G>(
+        pratt: &Pratt<Atom, Self, R, P, G>,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        pre_expr: input::Marker<'a, 'parse, I>,
+        top: &mut Option<Associativity>,
+    ) -> Result<Option<M::Output<O>>, ()>
+    where
+        Atom: Parser<'a, I, O, E>,
+        R: OperandRecovery<'a, I, O, E>,
+        P: TokenPadding<'a, I, E>,
+        G: Grouping<'a, I, O, E>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn postfix<'parse, M: Mode, Atom, R, P, G>(
+        pratt: &Pratt<Atom, Self, R, P, G>,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        pre_expr: input::Marker<'a, 'parse, I>,
+        pre_op: input::Marker<'a, 'parse, I>,
+        top: &mut Option<Associativity>,
+    ) -> Trailing<M::Output<O>>
+    where
+        Atom: Parser<'a, I, O, E>,
+        R: OperandRecovery<'a, I, O, E>,
+        P: TokenPadding<'a, I, E>,
+        G: Grouping<'a, I, O, E>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn infix<'parse, M: Mode, Atom, R, P, G>(
+        pratt: &Pratt<Atom, Self, R, P, G>,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        pre_expr: input::Marker<'a, 'parse, I>,
+        pre_op: input::Marker<'a, 'parse, I>,
+        top: &mut Option<Associativity>,
+    ) -> Trailing<M::Output<O>>
+    where
+        Atom: Parser<'a, I, O, E>,
+        R: OperandRecovery<'a, I, O, E>,
+        P: TokenPadding<'a, I, E>,
+        G: Grouping<'a, I, O, E>;
+}
+
+impl<'a, Atom, Ops, R, P, G> Pratt<Atom, Ops, R, P, G> {
+    #[inline]
+    fn pratt_go<M: Mode, I, O, E>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        min_power: u32,
+        top: &mut Option<Associativity>,
+    ) -> PResult<M, O>
+    where
+        I: Input<'a>,
+        E: ParserExtra<'a, I>,
+        Atom: Parser<'a, I, O, E>,
+        Ops: Operators<'a, I, O, E>,
+        R: OperandRecovery<'a, I, O, E>,
+        P: TokenPadding<'a, I, E>,
+        G: Grouping<'a, I, O, E>,
+    {
+        let pre_expr = inp.save();
+        let mut lhs = match Ops::prefix::<M, _, _, _, _>(self, inp, pre_expr, top)? {
+            Some(out) => out,
+            None => {
+                let group = self.groups.group::<M, _>(inp, &self.padding, |inp| {
+                    recursive::recurse(|| {
+                        inp.nested(|inp| self.pratt_go::<M, _, _, _>(inp, 0, &mut None))
+                    })
+                });
+                match group {
+                    Ok(out) => out,
+                    Err(()) => {
+                        inp.rewind(pre_expr);
+                        self.padding.pad::<M, _, _>(inp, &self.atom)?
+                    }
+                }
+            }
+        };
+
+        // Without any infix or postfix operators, nothing can follow the operand
+        if !self.ops.has_trailing() {
+            return Ok(lhs);
+        }
+
+        loop {
+            let pre_op = inp.save();
+
+            let trailing = match Ops::postfix::<M, _, _, _, _>(
+                self, inp, lhs, min_power, pre_expr, pre_op, top,
+            ) {
+                Trailing::Skipped(lhs) => {
+                    Ops::infix::<M, _, _, _, _>(self, inp, lhs, min_power, pre_expr, pre_op, top)
+                }
+                trailing => trailing,
+            };
+            match trailing {
+                Trailing::Folded(out) => lhs = out,
+                Trailing::Skipped(out) => {
+                    inp.rewind(pre_op);
+                    return Ok(out);
+                }
+                Trailing::Failed => return Err(()),
+            }
+        }
+    }
+
+    // Prefix unary operators. If an operator or its operand fails, even after consuming input, the next operator (and
+    // finally the atom) is tried from the start of the expression, since the same tokens may be valid atoms. The
+    // exception is an operand that was nested too deeply, which the expression cannot recover from.
+    #[inline]
+    fn prefix_op<'parse, M: Mode, I, O, E, X>(
+        &self,
+        op: &X,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        pre_expr: input::Marker<'a, 'parse, I>,
+        top: &mut Option<Associativity>,
+    ) -> Result<Option<M::Output<O>>, ()>
+    where
+        I: Input<'a>,
+        E: ParserExtra<'a, I>,
+        Atom: Parser<'a, I, O, E>,
+        Ops: Operators<'a, I, O, E>,
+        R: OperandRecovery<'a, I, O, E>,
+        P: TokenPadding<'a, I, E>,
+        G: Grouping<'a, I, O, E>,
+        X: Operator<'a, I, O, E>,
+    {
+        if !op.is_prefix() {
+            return Ok(None);
+        }
+        let out = match self.padding.pad::<M, _, _>(inp, op.op_parser()) {
+            Ok(out) => out,
+            Err(()) => {
+                inp.rewind(pre_expr);
+                return Ok(None);
+            }
+        };
+        let times_exceeded = inp.depth.times_exceeded;
+        match recursive::recurse(|| {
+            inp.nested(|inp| {
+                self.pratt_go::<M, _, _, _>(inp, op.associativity().left_power(), &mut None)
+            })
+        }) {
+            Ok(rhs) => {
+                *top = Some(op.associativity());
+                Ok(Some(M::combine(out, rhs, |out, rhs| {
+                    op.fold_prefix(out, rhs, &mut MapExtra::new(pre_expr.offset(), inp))
+                })))
+            }
+            Err(()) if inp.depth.times_exceeded != times_exceeded => Err(()),
+            Err(()) => {
+                inp.rewind(pre_expr);
+                Ok(None)
+            }
+        }
+    }
+
+    // Postfix unary operators
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    fn postfix_op<'parse, M: Mode, I, O, E, X>(
+        &self,
+        op: &X,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        pre_expr: input::Marker<'a, 'parse, I>,
+        pre_op: input::Marker<'a, 'parse, I>,
+        top: &mut Option<Associativity>,
+    ) -> Trailing<M::Output<O>>
+    where
+        I: Input<'a>,
+        E: ParserExtra<'a, I>,
+        P: TokenPadding<'a, I, E>,
+        X: Operator<'a, I, O, E>,
+    {
+        let assoc = op.associativity();
+        if !(op.is_postfix() && assoc.right_power() >= min_power) {
+            return Trailing::Skipped(lhs);
+        }
+        match self.padding.pad::<M, _, _>(inp, op.op_parser()) {
+            Ok(out) => {
+                *top = Some(assoc);
+                Trailing::Folded(M::combine(lhs, out, |lhs, out| {
+                    op.fold_postfix(lhs, out, &mut MapExtra::new(pre_expr.offset(), inp))
+                }))
+            }
+            Err(()) => {
+                inp.rewind(pre_op);
+                Trailing::Skipped(lhs)
+            }
+        }
+    }
+
+    // Infix binary operators
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    fn infix_op<'parse, M: Mode, I, O, E, X>(
+        &self,
+        op: &X,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        lhs: M::Output<O>,
+        min_power: u32,
+        pre_expr: input::Marker<'a, 'parse, I>,
+        pre_op: input::Marker<'a, 'parse, I>,
+        top: &mut Option<Associativity>,
+    ) -> Trailing<M::Output<O>>
+    where
+        I: Input<'a>,
+        E: ParserExtra<'a, I>,
+        Atom: Parser<'a, I, O, E>,
+        Ops: Operators<'a, I, O, E>,
+        R: OperandRecovery<'a, I, O, E>,
+        P: TokenPadding<'a, I, E>,
+        G: Grouping<'a, I, O, E>,
+        X: Operator<'a, I, O, E>,
+    {
+        let assoc = op.associativity();
+        if !(op.is_infix() && assoc.left_power() >= min_power) {
+            return Trailing::Skipped(lhs);
+        }
+        let out = match self.padding.pad::<M, _, _>(inp, op.op_parser()) {
+            Ok(out) => out,
+            Err(()) => {
+                inp.rewind(pre_op);
+                return Trailing::Skipped(lhs);
+            }
+        };
+        let post_op = inp.save();
+        let times_exceeded = inp.depth.times_exceeded;
+        let rhs = match recursive::recurse(|| {
+            inp.nested(|inp| self.pratt_go::<M, _, _, _>(inp, assoc.right_power(), &mut None))
+        }) {
+            // If the operand was too deeply nested, falling back to a shorter expression would silently parse the
+            // remaining operators with the wrong associativity
+            Err(()) if inp.depth.times_exceeded != times_exceeded => return Trailing::Failed,
+            Err(()) => {
+                inp.rewind(post_op);
+                self.recovery.recover_operand::<M, _>(inp, &self.atom)
+            }
+            rhs => rhs,
+        };
+        let rhs = match rhs {
+            Ok(rhs) => rhs,
+            Err(()) => {
+                inp.rewind(pre_op);
+                return Trailing::Skipped(lhs);
+            }
+        };
+        *top = Some(assoc);
+        if op.is_fallible() {
+            let lhs_span = inp.span(pre_expr.offset()..pre_op.offset());
+            let rhs_span = inp.span_since(post_op.offset());
+            let res = M::combine(
+                M::combine(lhs, rhs, |lhs, rhs| (lhs, rhs)),
+                out,
+                |(lhs, rhs), out| {
+                    op.try_fold_infix(
+                        (lhs, lhs_span),
+                        out,
+                        (rhs, rhs_span),
+                        &mut MapExtra::new(pre_expr.offset(), inp),
+                    )
+                },
+            );
+            match M::transpose(res) {
+                Ok(out) => Trailing::Folded(out),
+                Err(err) => {
+                    // The operands parsed successfully, so this error takes precedence over any speculative errors
+                    // produced while parsing them
+                    inp.errors.alt = Some(Located::at(inp.offset, err));
+                    Trailing::Failed
+                }
+            }
+        } else {
+            Trailing::Folded(M::combine(
+                M::combine(lhs, rhs, |lhs, rhs| (lhs, rhs)),
+                out,
+                |(lhs, rhs), out| {
+                    op.fold_infix(lhs, out, rhs, &mut MapExtra::new(pre_expr.offset(), inp))
+                },
+            ))
+        }
+    }
+}
+
+macro_rules! impl_operators_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_operators_for_tuple!($($X)*);
+        impl_operators_for_tuple!(~ $head $($X)*);
+    };
+    (~ $($X:ident)+) => {
+        #[allow(unused_variables, non_snake_case)]
+        impl<'a, I, O, E, $($X),*> Operators<'a, I, O, E> for ($($X,)*)
+        where
+            I: Input<'a>,
+            E: ParserExtra<'a, I>,
+            $($X: Operator<'a, I, O, E>),*
+        {
+            #[inline]
+            fn has_trailing(&self) -> bool {
+                let ($($X,)*) = self;
+                false $(|| $X.is_infix() || $X.is_postfix())*
+            }
+
+            #[inline]
+            fn is_fallible(&self) -> bool {
+                let ($($X,)*) = self;
+                false $(|| $X.is_fallible())*
+            }
+
+            #[inline]
+            fn prefix<'parse, M: Mode, Atom, R, P, G>(
+                pratt: &Pratt<Atom, Self, R, P, G>,
+                inp: &mut InputRef<'a, 'parse, I, E>,
+                pre_expr: input::Marker<'a, 'parse, I>,
+                top: &mut Option<Associativity>,
+            ) -> Result<Option<M::Output<O>>, ()>
+            where
+                Atom: Parser<'a, I, O, E>,
+                R: OperandRecovery<'a, I, O, E>,
+                P: TokenPadding<'a, I, E>,
+                G: Grouping<'a, I, O, E>,
+            {
+                let ($($X,)*) = &pratt.ops;
+                $(
+                    if let Some(out) = pratt.prefix_op::<M, _, _, _, _>($X, inp, pre_expr, top)? {
+                        return Ok(Some(out));
+                    }
+                )*
+                Ok(None)
+            }
+
+            #[inline]
+            fn postfix<'parse, M: Mode, Atom, R, P, G>(
+                pratt: &Pratt<Atom, Self, R, P, G>,
+                inp: &mut InputRef<'a, 'parse, I, E>,
+                lhs: M::Output<O>,
+                min_power: u32,
+                pre_expr: input::Marker<'a, 'parse, I>,
+                pre_op: input::Marker<'a, 'parse, I>,
+                top: &mut Option<Associativity>,
+            ) -> Trailing<M::Output<O>>
+            where
+                Atom: Parser<'a, I, O, E>,
+                R: OperandRecovery<'a, I, O, E>,
+                P: TokenPadding<'a, I, E>,
+                G: Grouping<'a, I, O, E>,
+            {
+                let ($($X,)*) = &pratt.ops;
+                $(
+                    let lhs = match pratt.postfix_op::<M, _, _, _, _>($X, inp, lhs, min_power, pre_expr, pre_op, top) {
+                        Trailing::Skipped(lhs) => lhs,
+                        trailing => return trailing,
+                    };
+                )*
+                Trailing::Skipped(lhs)
+            }
+
+            #[inline]
+            fn infix<'parse, M: Mode, Atom, R, P, G>(
+                pratt: &Pratt<Atom, Self, R, P, G>,
+                inp: &mut InputRef<'a, 'parse, I, E>,
+                lhs: M::Output<O>,
+                min_power: u32,
+                pre_expr: input::Marker<'a, 'parse, I>,
+                pre_op: input::Marker<'a, 'parse, I>,
+                top: &mut Option<Associativity>,
+            ) -> Trailing<M::Output<O>>
+            where
+                Atom: Parser<'a, I, O, E>,
+                R: OperandRecovery<'a, I, O, E>,
+                P: TokenPadding<'a, I, E>,
+                G: Grouping<'a, I, O, E>,
+            {
+                let ($($X,)*) = &pratt.ops;
+                $(
+                    let lhs = match pratt.infix_op::<M, _, _, _, _>($X, inp, lhs, min_power, pre_expr, pre_op, top) {
+                        Trailing::Skipped(lhs) => lhs,
+                        trailing => return trailing,
+                    };
+                )*
+                Trailing::Skipped(lhs)
+            }
+        }
+    };
+}
+
+impl_operators_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+
+impl<'a, I, O, E, Atom, Ops, R, P, G> ParserSealed<'a, I, O, E> for Pratt<Atom, Ops, R, P, G>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    Atom: Parser<'a, I, O, E>,
+    Ops: Operators<'a, I, O, E>,
+    R: OperandRecovery<'a, I, O, E>,
+    P: TokenPadding<'a, I, E>,
+    G: Grouping<'a, I, O, E>,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        if self.ops.is_fallible() {
+            // Fallible operators need to inspect their operands, so we can't avoid generating output
+            let out = self.pratt_go::<Emit, _, _, _>(inp, 0, &mut None)?;
+            Ok(M::bind(|| out))
+        } else {
+            self.pratt_go::<M, _, _, _>(inp, 0, &mut None)
+        }
+    }
+
+    go_extra!(O);
+}
+
+impl<'a, I, O, E, Atom, Ops, R, P, G> ParserSealed<'a, I, (O, Option<Associativity>), E>
+    for PrattWithPrec<Atom, Ops, R, P, G>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    Atom: Parser<'a, I, O, E>,
+    Ops: Operators<'a, I, O, E>,
+    R: OperandRecovery<'a, I, O, E>,
+    P: TokenPadding<'a, I, E>,
+    G: Grouping<'a, I, O, E>,
+{
+    fn go<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<M, (O, Option<Associativity>)> {
+        let mut top = None;
+        let out = if self.pratt.ops.is_fallible() {
+            let out = self.pratt.pratt_go::<Emit, _, _, _>(inp, 0, &mut top)?;
+            M::bind(|| out)
+        } else {
+            self.pratt.pratt_go::<M, _, _, _>(inp, 0, &mut top)?
+        };
+        Ok(M::map(out, |out| (out, top)))
+    }
+
+    go_extra!((O, Option<Associativity>));
+}
+
+// An operator in a `PrattOps` table with its type erased, allowing operators with different parsers and fold
+// functions to share a table. It parses its own operator token, and otherwise mirrors `Operator`.
+trait TableOperator<'a, I, O, E, Op>: Parser<'a, I, Op, E> + MaybeSync
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    fn is_infix(&self) -> bool;
+    fn is_prefix(&self) -> bool;
+    fn is_postfix(&self) -> bool;
+    fn is_fallible(&self) -> bool;
+    fn associativity(&self) -> Associativity;
+    fn fold_infix(&self, lhs: O, op: Op, rhs: O, extra: &mut MapExtra<'a, '_, I, E>) -> O;
+    fn try_fold_infix(
+        &self,
+        lhs: (O, I::Span),
+        op: Op,
+        rhs: (O, I::Span),
+        extra: &mut MapExtra<'a, '_, I, E>,
+    ) -> Result<O, E::Error>;
+    fn fold_prefix(&self, op: Op, rhs: O, extra: &mut MapExtra<'a, '_, I, E>) -> O;
+    fn fold_postfix(&self, lhs: O, op: Op, extra: &mut MapExtra<'a, '_, I, E>) -> O;
+}
+
+struct TableEntry<X, O> {
+    op: X,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<O>,
+}
+
+impl<'a, I, O, E, X> ParserSealed<'a, I, X::Op, E> for TableEntry<X, O>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    X: Operator<'a, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, X::Op> {
+        self.op.op_parser().go::<M>(inp)
+    }
+
+    go_extra!(X::Op);
+}
+
+impl<'a, I, O, E, X> TableOperator<'a, I, O, E, X::Op> for TableEntry<X, O>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    X: Operator<'a, I, O, E> + MaybeSync,
+{
+    fn is_infix(&self) -> bool {
+        self.op.is_infix()
+    }
+    fn is_prefix(&self) -> bool {
+        self.op.is_prefix()
+    }
+    fn is_postfix(&self) -> bool {
+        self.op.is_postfix()
+    }
+    fn is_fallible(&self) -> bool {
+        self.op.is_fallible()
+    }
+    fn associativity(&self) -> Associativity {
+        self.op.associativity()
+    }
+    fn fold_infix(&self, lhs: O, op: X::Op, rhs: O, extra: &mut MapExtra<'a, '_, I, E>) -> O {
+        self.op.fold_infix(lhs, op, rhs, extra)
+    }
+    fn try_fold_infix(
+        &self,
+        lhs: (O, I::Span),
+        op: X::Op,
+        rhs: (O, I::Span),
+        extra: &mut MapExtra<'a, '_, I, E>,
+    ) -> Result<O, E::Error> {
+        self.op.try_fold_infix(lhs, op, rhs, extra)
+    }
+    fn fold_prefix(&self, op: X::Op, rhs: O, extra: &mut MapExtra<'a, '_, I, E>) -> O {
+        self.op.fold_prefix(op, rhs, extra)
+    }
+    fn fold_postfix(&self, lhs: O, op: X::Op, extra: &mut MapExtra<'a, '_, I, E>) -> O {
+        self.op.fold_postfix(lhs, op, extra)
+    }
+}
+
+struct TableOp<'a, 'b, I: Input<'a>, Op, O, E: ParserExtra<'a, I>> {
+    op: RefC<dyn TableOperator<'a, I, O, E, Op> + 'b>,
+}
+
+impl<'a, 'b, I: Input<'a>, Op, O, E: ParserExtra<'a, I>> Clone for TableOp<'a, 'b, I, Op, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            op: self.op.clone(),
+        }
+    }
+}
+
+impl<'a, 'b, I, Op, O, E> ParserSealed<'a, I, Op, E> for TableOp<'a, 'b, I, Op, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, Op> {
+        M::invoke(&*self.op, inp)
+    }
+
+    go_extra!(Op);
+}
+
+impl<'a, 'b, I: Input<'a>, Op, O, E: ParserExtra<'a, I>> Sealed for TableOp<'a, 'b, I, Op, O, E> {}
+impl<'a, 'b, I, Op, O, E> Operator<'a, I, O, E> for TableOp<'a, 'b, I, Op, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    type Op = Op;
+    type OpParser = Self;
+    fn is_infix(&self) -> bool {
+        self.op.is_infix()
+    }
+    fn is_prefix(&self) -> bool {
+        self.op.is_prefix()
+    }
+    fn is_postfix(&self) -> bool {
+        self.op.is_postfix()
+    }
+    fn is_fallible(&self) -> bool {
+        self.op.is_fallible()
+    }
+    fn op_parser(&self) -> &Self::OpParser {
+        self
+    }
+    fn associativity(&self) -> Associativity {
+        self.op.associativity()
+    }
+    fn fold_infix(&self, lhs: O, op: Op, rhs: O, extra: &mut MapExtra<'a, '_, I, E>) -> O {
+        self.op.fold_infix(lhs, op, rhs, extra)
+    }
+    fn try_fold_infix(
+        &self,
+        lhs: (O, I::Span),
+        op: Op,
+        rhs: (O, I::Span),
+        extra: &mut MapExtra<'a, '_, I, E>,
+    ) -> Result<O, E::Error> {
+        self.op.try_fold_infix(lhs, op, rhs, extra)
+    }
+    fn fold_prefix(&self, op: Op, rhs: O, extra: &mut MapExtra<'a, '_, I, E>) -> O {
+        self.op.fold_prefix(op, rhs, extra)
+    }
+    fn fold_postfix(&self, lhs: O, op: Op, extra: &mut MapExtra<'a, '_, I, E>) -> O {
+        self.op.fold_postfix(lhs, op, extra)
+    }
+}
+
+/// A table of operators for a pratt parser, built up one operator at a time.
+///
+/// This is an alternative to passing a tuple of [`infix`], [`prefix`], and [`postfix`] operators to
+/// [`Parser::pratt`] that reads more easily for languages with many operators, and that allows operators to be added
+/// conditionally (for example, depending on a language edition or a feature flag). Operators behave exactly as they
+/// would in a tuple, including being tried in the order in which they were added.
+///
+/// Operators are boxed, so every operator in a table must produce the same output type, `Op`. The shorthand methods
+/// ([`PrattOps::infix`], [`PrattOps::prefix`], etc.) take fold functions that always receive the operator (like the
+/// `Fn(O, Op, O) -> O` form accepted by [`infix`]). Any other operator, including those created by [`try_infix`] and
+/// those with fold functions that take a [`MapExtra`], can be added with [`PrattOps::operator`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// use chumsky::pratt::*;
+///
+/// let allow_pow = true;
+///
+/// let atom = text::int::<_, _, extra::Err<Simple<char>>>(10)
+///     .from_str::<i64>()
+///     .unwrapped()
+///     .padded();
+/// let op = |c| just(c).padded();
+///
+/// let mut ops = PrattOps::new()
+///     .infix_left(1, op('+'), |l, _, r| l + r)
+///     .infix_left(1, op('-'), |l, _, r| l - r)
+///     .infix_left(2, op('*'), |l, _, r| l * r)
+///     .prefix(3, op('-'), |_, x: i64| -x);
+/// if allow_pow {
+///     ops = ops.infix_right(4, op('^'), |l, _, r: i64| l.pow(r as u32));
+/// }
+///
+/// let expr = atom.pratt(ops);
+/// assert_eq!(expr.parse("1 + 2 * -3 ^ 2").into_result(), Ok(-17));
+/// ```
+pub struct PrattOps<'a, 'b, I: Input<'a>, Op, O, E: ParserExtra<'a, I> = extra::Default> {
+    ops: Vec<TableOp<'a, 'b, I, Op, O, E>>,
+    // Whether any operators are infix or postfix, i.e: whether anything may follow an operand
+    has_trailing: bool,
+    // Whether any operators are fallible
+    is_fallible: bool,
+}
+
+impl<'a, 'b, I: Input<'a>, Op, O, E: ParserExtra<'a, I>> Clone for PrattOps<'a, 'b, I, Op, O, E> {
+    fn clone(&self) -> Self {
+        Self {
+            ops: self.ops.clone(),
+            has_trailing: self.has_trailing,
+            is_fallible: self.is_fallible,
+        }
+    }
+}
+
+impl<'a, 'b, I: Input<'a>, Op, O, E: ParserExtra<'a, I>> Default for PrattOps<'a, 'b, I, Op, O, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, 'b, I: Input<'a>, Op, O, E: ParserExtra<'a, I>> PrattOps<'a, 'b, I, Op, O, E> {
+    /// Create an empty operator table.
+    pub fn new() -> Self {
+        Self {
+            ops: Vec::new(),
+            has_trailing: false,
+            is_fallible: false,
+        }
+    }
+
+    /// Add an operator created by [`infix`], [`try_infix`], [`prefix`], or [`postfix`], with any of the fold
+    /// functions that they accept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, input::MapExtra, pratt::*};
+    /// fn parser<'a>() -> impl Parser<'a, &'a str, String> {
+    ///     let ops = PrattOps::new()
+    ///         .infix_left(1, just('+').padded(), |l, _, r| format!("({l} + {r})"))
+    ///         .operator(postfix(
+    ///             2,
+    ///             just('!').padded(),
+    ///             |lhs: String, _, e: &mut MapExtra<'a, '_, &'a str, extra::Default>| {
+    ///                 format!("{lhs}! at {}", e.span())
+    ///             },
+    ///         ));
+    ///     text::int(10).padded().map(str::to_string).pratt(ops)
+    /// }
+    ///
+    /// assert_eq!(parser().parse("1 + 2!").into_result(), Ok("(1 + 2! at 4..6)".to_string()));
+    /// ```
+    pub fn operator<X>(mut self, op: X) -> Self
+    where
+        X: Operator<'a, I, O, E, Op = Op> + MaybeSync + 'b,
+        O: 'b,
+    {
+        self.has_trailing |= op.is_infix() || op.is_postfix();
+        self.is_fallible |= op.is_fallible();
+        self.ops.push(TableOp {
+            op: RefC::new(TableEntry {
+                op,
+                phantom: EmptyPhantom::new(),
+            }),
+        });
+        self
+    }
+
+    /// Add a binary infix operator with the given associativity (see [`left`], [`right`], and [`non`]), like
+    /// [`infix`].
+    pub fn infix<A, F>(self, associativity: Associativity, op_parser: A, fold: F) -> Self
+    where
+        A: Parser<'a, I, Op, E> + MaybeSync + 'b,
+        F: Fn(O, Op, O) -> O + MaybeSync + 'b,
+        Op: 'b,
+        O: 'b,
+    {
+        self.operator(infix::<_, _, _, (O, Op, O)>(associativity, op_parser, fold))
+    }
+
+    /// Add a left-associative binary infix operator with the given binding power.
+    pub fn infix_left<A, F>(self, binding_power: u16, op_parser: A, fold: F) -> Self
+    where
+        A: Parser<'a, I, Op, E> + MaybeSync + 'b,
+        F: Fn(O, Op, O) -> O + MaybeSync + 'b,
+        Op: 'b,
+        O: 'b,
+    {
+        self.infix(left(binding_power), op_parser, fold)
+    }
+
+    /// Add a right-associative binary infix operator with the given binding power.
+    pub fn infix_right<A, F>(self, binding_power: u16, op_parser: A, fold: F) -> Self
+    where
+        A: Parser<'a, I, Op, E> + MaybeSync + 'b,
+        F: Fn(O, Op, O) -> O + MaybeSync + 'b,
+        Op: 'b,
+        O: 'b,
+    {
+        self.infix(right(binding_power), op_parser, fold)
+    }
+
+    /// Add a unary prefix operator with the given binding power, like [`prefix`].
+    pub fn prefix<A, F>(self, binding_power: u16, op_parser: A, fold: F) -> Self
+    where
+        A: Parser<'a, I, Op, E> + MaybeSync + 'b,
+        F: Fn(Op, O) -> O + MaybeSync + 'b,
+        Op: 'b,
+        O: 'b,
+    {
+        self.operator(prefix::<_, _, _, (Op, O)>(binding_power, op_parser, fold))
+    }
+
+    /// Add a unary postfix operator with the given binding power, like [`postfix`].
+    pub fn postfix<A, F>(self, binding_power: u16, op_parser: A, fold: F) -> Self
+    where
+        A: Parser<'a, I, Op, E> + MaybeSync + 'b,
+        F: Fn(O, Op) -> O + MaybeSync + 'b,
+        Op: 'b,
+        O: 'b,
+    {
+        self.operator(postfix::<_, _, _, (O, Op)>(binding_power, op_parser, fold))
+    }
+}
+
+impl<'a, 'b, I, Op, O, E> Operators<'a, I, O, E> for PrattOps<'a, 'b, I, Op, O, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    fn has_trailing(&self) -> bool {
+        self.has_trailing
+    }
+
+    fn is_fallible(&self) -> bool {
+        self.is_fallible
+    }
+
+    fn prefix<'parse, M: Mode, Atom, R, P, G>(
+        pratt: &Pratt<Atom, Self, R, P, G>,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        pre_expr: input::Marker<'a, 'parse, I>,
+        top: &mut Option<Associativity>,
+    ) -> Result<Option<M::Output<O>>, ()>
+    where
+        Atom: Parser<'a, I, O, E>,
+        R: OperandRecovery<'a, I, O, E>,
+        P: TokenPadding<'a, I, E>,
+        G: Grouping<'a, I, O, E>,
+    {
+        for op in &pratt.ops.ops {
+            if let Some(out) = pratt.prefix_op::<M, _, _, _, _>(op, inp, pre_expr, top)? {
+                return Ok(Some(out));
+            }
+        }
+        Ok(None)
+    }
+
+    fn postfix<'parse, M: Mode, Atom, R, P, G>(
+        pratt: &Pratt<Atom, Self, R, P, G>,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        mut lhs: M::Output<O>,
+        min_power: u32,
+        pre_expr: input::Marker<'a, 'parse, I>,
+        pre_op: input::Marker<'a, 'parse, I>,
+        top: &mut Option<Associativity>,
+    ) -> Trailing<M::Output<O>>
+    where
+        Atom: Parser<'a, I, O, E>,
+        R: OperandRecovery<'a, I, O, E>,
+        P: TokenPadding<'a, I, E>,
+        G: Grouping<'a, I, O, E>,
+    {
+        for op in &pratt.ops.ops {
+            lhs = match pratt
+                .postfix_op::<M, _, _, _, _>(op, inp, lhs, min_power, pre_expr, pre_op, top)
+            {
+                Trailing::Skipped(lhs) => lhs,
+                trailing => return trailing,
+            };
+        }
+        Trailing::Skipped(lhs)
+    }
+
+    fn infix<'parse, M: Mode, Atom, R, P, G>(
+        pratt: &Pratt<Atom, Self, R, P, G>,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        mut lhs: M::Output<O>,
+        min_power: u32,
+        pre_expr: input::Marker<'a, 'parse, I>,
+        pre_op: input::Marker<'a, 'parse, I>,
+        top: &mut Option<Associativity>,
+    ) -> Trailing<M::Output<O>>
+    where
+        Atom: Parser<'a, I, O, E>,
+        R: OperandRecovery<'a, I, O, E>,
+        P: TokenPadding<'a, I, E>,
+        G: Grouping<'a, I, O, E>,
+    {
+        for op in &pratt.ops.ops {
+            lhs = match pratt
+                .infix_op::<M, _, _, _, _>(op, inp, lhs, min_power, pre_expr, pre_op, top)
+            {
+                Trailing::Skipped(lhs) => lhs,
+                trailing => return trailing,
+            };
+        }
+        Trailing::Skipped(lhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extra::Err, prelude::*};
+
+    fn factorial(x: i64) -> i64 {
+        if x == 0 {
+            1
+        } else {
+            x * factorial(x - 1)
+        }
+    }
+
+    fn parser<'a>() -> impl Parser<'a, &'a str, i64> {
+        let atom = text::int(10).padded().from_str::<i64>().unwrapped();
+
+        atom.pratt((
+            prefix(2, just('-'), |x: i64| -x),
+            postfix(2, just('!'), factorial),
+            infix(left(0), just('+'), |l, r| l + r),
+            infix(left(0), just('-'), |l, r| l - r),
+            infix(left(1), just('*'), |l, r| l * r),
+            infix(left(1), just('/'), |l, _, r| l / r),
+        ))
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(parser().parse("2 + 3 * 4").into_result(), Ok(14));
+        assert_eq!(parser().parse("2 * 3 + 4").into_result(), Ok(10));
+    }
+
+    #[test]
+    fn with_parens() {
+        let atom = text::int::<_, _, Err<Simple<char>>>(10)
+            .padded()
+            .from_str::<i64>()
+            .unwrapped();
+        let expr = atom.pratt((
+            prefix(2, just('-'), |x: i64| -x),
+            postfix(2, just('!'), factorial),
+            infix(left(0), just('+'), |l, r| l + r),
+            infix(left(0), just('-'), |l, r| l - r),
+            infix(left(1), just('*'), |l, r| l * r),
+        ));
+        let parens = expr.with_parens(just('(').padded(), just(')').padded());
+
+        assert_eq!(parens.parse("(1 + 2) * 3").into_result(), Ok(9));
+        assert_eq!(parens.parse("-(1 + 2)").into_result(), Ok(-3));
+        assert_eq!(parens.parse("2 * (3 - (4 + 5))").into_result(), Ok(-12));
+        assert_eq!(parens.parse("(3)!").into_result(), Ok(6));
+        assert!(parens.parse("(1 + 2").has_errors());
+        assert!(parens.parse("()").has_errors());
+
+        // A group counts as an atom, so it has no outermost operator
+        let prec = expr
+            .with_parens(just('(').padded(), just(')').padded())
+            .with_prec();
+        assert_eq!(prec.parse("(1 + 2)").into_result(), Ok((3, None)));
+        assert_eq!(
+            prec.parse("(1 + 2) * 3").into_result(),
+            Ok((9, Some(left(1))))
+        );
+    }
+
+    #[test]
+    fn unary() {
+        assert_eq!(parser().parse("-2").into_result(), Ok(-2));
+        assert_eq!(parser().parse("4!").into_result(), Ok(24));
+        assert_eq!(parser().parse("2 + 4!").into_result(), Ok(26));
+        assert_eq!(parser().parse("-2 + 2").into_result(), Ok(0));
+    }
+
+    // TODO: Make this work
+    // fn parser_dynamic<'a>() -> impl Parser<'a, &'a str, i64> {
+    //     let atom = text::int(10).padded().from_str::<i64>().unwrapped();
+
+    //     atom.pratt(vec![
+    //         prefix(2, just('-'), |x: i64| -x).into(),
+    //         postfix(2, just('!'), factorial).into(),
+    //         infix(left(0), just('+'), |l, r| l + r).into(),
+    //         infix(left(0), just('-'), |l, r| l - r).into(),
+    //         infix(left(1), just('*'), |l, r| l * r).into(),
+    //         infix(left(1), just('/'), |l, _, r| l / r).into(),
+    //     ])
+    // }
+
+    enum Expr {
+        Literal(i64),
+        Not(Box<Expr>),
+        Negate(Box<Expr>),
+        Confusion(Box<Expr>),
+        Factorial(Box<Expr>),
+        Value(Box<Expr>),
+        Add(Box<Expr>, Box<Expr>),
+        Sub(Box<Expr>, Box<Expr>),
+        Mul(Box<Expr>, Box<Expr>),
+        Div(Box<Expr>, Box<Expr>),
+    }
+
+    impl std::fmt::Display for Expr {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Literal(literal) => write!(f, "{literal}"),
+                Self::Not(right) => write!(f, "(~{right})"),
+                Self::Negate(right) => write!(f, "(-{right})"),
+                Self::Confusion(right) => write!(f, "(§{right})"),
+                Self::Factorial(right) => write!(f, "({right}!)"),
+                Self::Value(right) => write!(f, "({right}$)"),
+                Self::Add(left, right) => write!(f, "({left} + {right})"),
                 Self::Sub(left, right) => write!(f, "({left} - {right})"),
                 Self::Mul(left, right) => write!(f, "({left} * {right})"),
                 Self::Div(left, right) => write!(f, "({left} / {right})"),
@@ -791,4 +2229,586 @@ mod tests {
             Ok("(((§(1 + (-(~(2!)))))$) * 3)".to_string()),
         )
     }
+
+    #[test]
+    fn try_infix_assignment() {
+        #[derive(Debug, PartialEq)]
+        enum Ast {
+            Var(char),
+            Num(i64),
+            Add(Box<Ast>, Box<Ast>),
+            Assign(Box<Ast>, Box<Ast>),
+        }
+
+        fn parser<'a>() -> impl Parser<'a, &'a str, Ast, Err<Rich<'a, char>>> {
+            let atom = choice((
+                any().filter(char::is_ascii_lowercase).map(Ast::Var),
+                text::int(10).from_str().unwrapped().map(Ast::Num),
+            ))
+            .padded();
+
+            atom.pratt((
+                try_infix(
+                    right(0),
+                    just('='),
+                    |(lhs, lhs_span), _, (rhs, _), _| match lhs {
+                        Ast::Var(_) => Ok(Ast::Assign(Box::new(lhs), Box::new(rhs))),
+                        _ => Err(Rich::custom(lhs_span, "invalid assignment target")),
+                    },
+                ),
+                infix(left(1), just('+'), |l, r| {
+                    Ast::Add(Box::new(l), Box::new(r))
+                }),
+            ))
+        }
+
+        let var = |c| Box::new(Ast::Var(c));
+        let num = |n| Box::new(Ast::Num(n));
+
+        assert_eq!(
+            parser().parse("x = 2").into_result(),
+            Ok(Ast::Assign(var('x'), num(2))),
+        );
+        assert_eq!(
+            parser().parse("x = y = 1 + 2").into_result(),
+            Ok(Ast::Assign(
+                var('x'),
+                Box::new(Ast::Assign(var('y'), Box::new(Ast::Add(num(1), num(2))))),
+            )),
+        );
+
+        let errs = parser().parse("1 = 2").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 2));
+        assert_eq!(errs[0].to_string(), "invalid assignment target");
+
+        let errs = parser().parse("x = 1 + 2 = 3").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 10));
+
+        // Fallible operators are still checked when output isn't required
+        assert!(!parser().check("x = 2").has_errors());
+        assert_eq!(parser().check("1 = 2").errors().len(), 1);
+    }
+
+    #[test]
+    fn borrowed_atoms() {
+        #[derive(Debug, PartialEq)]
+        enum Ast<'a> {
+            Var(&'a str),
+            Neg(Box<Ast<'a>>),
+            Call(Box<Ast<'a>>, &'a str),
+            Add(Box<Ast<'a>>, Box<Ast<'a>>),
+            Field(Box<Ast<'a>>, &'a str, &'a str),
+        }
+
+        fn parser<'a>() -> impl Parser<'a, &'a str, Ast<'a>, Err<Rich<'a, char>>> {
+            let atom = text::ascii::ident().map(Ast::Var);
+
+            atom.pratt((
+                prefix(2, just('-'), |rhs| Ast::Neg(Box::new(rhs))),
+                postfix(3, just("()").to_slice(), |lhs, op| {
+                    Ast::Call(Box::new(lhs), op)
+                }),
+                infix(left(1), just('+'), |l, r| {
+                    Ast::Add(Box::new(l), Box::new(r))
+                }),
+                // Folds can also borrow the slice of input covered by the whole operation
+                infix(
+                    left(4),
+                    just('.'),
+                    |l, _, r, e: &mut MapExtra<'a, '_, &'a str, Err<Rich<'a, char>>>| match r {
+                        Ast::Var(field) => Ast::Field(Box::new(l), field, e.slice()),
+                        r => Ast::Add(Box::new(l), Box::new(r)),
+                    },
+                ),
+            ))
+        }
+
+        let src = String::from("-foo+bar.baz()");
+        let ast = parser().parse(src.as_str()).into_result();
+        assert_eq!(
+            ast,
+            Ok(Ast::Add(
+                Box::new(Ast::Neg(Box::new(Ast::Var("foo")))),
+                Box::new(Ast::Call(
+                    Box::new(Ast::Field(Box::new(Ast::Var("bar")), "baz", "bar.baz")),
+                    "()",
+                )),
+            )),
+        );
+    }
+
+    #[test]
+    fn postfix_rewinds_on_partial_match() {
+        #[derive(Debug, PartialEq)]
+        enum Ast<'a> {
+            Var(&'a str),
+            Index(Box<Ast<'a>>, Box<Ast<'a>>),
+        }
+
+        let expr = recursive::<_, _, Err<Simple<char>>, _, _>(|expr| {
+            text::ascii::ident().map(Ast::Var).pratt((postfix(
+                1,
+                expr.delimited_by(just('['), just(']')),
+                |lhs, idx| Ast::Index(Box::new(lhs), Box::new(idx)),
+            ),))
+        });
+
+        let var = |name| Box::new(Ast::Var(name));
+
+        assert_eq!(
+            expr.parse("a[b][c]").into_result(),
+            Ok(Ast::Index(
+                Box::new(Ast::Index(var("a"), var("b"))),
+                var("c")
+            )),
+        );
+
+        // A postfix operator that fails part-way through leaves the input where it was before the operator
+        assert_eq!(expr.parse_lazy("a[").into_result(), Ok((Ast::Var("a"), 1)));
+        assert_eq!(
+            expr.parse_lazy("a[b][c").into_result(),
+            Ok((Ast::Index(var("a"), var("b")), 4)),
+        );
+        assert_eq!(
+            expr.parse_lazy("a[b[").into_result(),
+            Ok((Ast::Var("a"), 1)),
+        );
+
+        // Without `parse_lazy`, the unclosed index is reported where it was found to be incomplete
+        let errs = expr.parse("a[").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(2, 2));
+        assert!(expr.check("a[b][").has_errors());
+    }
+
+    #[test]
+    fn prefix_rewinds_on_partial_match() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Ast<'a> {
+            Var(&'a str),
+            Minus,
+            Neg(Box<Ast<'a>>),
+            Dec(Box<Ast<'a>>),
+        }
+
+        // `-` is both a prefix operator and, on its own, an atom
+        let atom = || {
+            text::ascii::ident::<_, _, Err<Simple<char>>>()
+                .map(Ast::Var)
+                .or(just('-').to(Ast::Minus))
+        };
+        let dec = || just('-').then(just('-')).ignored();
+
+        let tuple = atom().pratt((
+            prefix(1, dec(), |r| Ast::Dec(Box::new(r))),
+            prefix(1, just('-'), |r| Ast::Neg(Box::new(r))),
+        ));
+        let table = atom().pratt(
+            PrattOps::new()
+                .prefix(1, dec(), |_, r| Ast::Dec(Box::new(r)))
+                .prefix(1, just('-').ignored(), |_, r| Ast::Neg(Box::new(r))),
+        );
+
+        for expr in [Parser::boxed(tuple), Parser::boxed(table)] {
+            assert_eq!(
+                expr.parse("--x").into_result(),
+                Ok(Ast::Dec(Box::new(Ast::Var("x"))))
+            );
+            assert_eq!(
+                expr.parse("-x").into_result(),
+                Ok(Ast::Neg(Box::new(Ast::Var("x"))))
+            );
+            // Both operators consume the `-` before failing, so the atom must see it again
+            assert_eq!(expr.parse("-").into_result(), Ok(Ast::Minus));
+            // `--` matches but has no operand, falling back to `-` as a prefix of the `-` atom
+            assert_eq!(
+                expr.parse("--").into_result(),
+                Ok(Ast::Neg(Box::new(Ast::Minus)))
+            );
+            assert_eq!(
+                expr.parse("---").into_result(),
+                Ok(Ast::Dec(Box::new(Ast::Minus)))
+            );
+            assert_eq!(expr.parse_lazy("-+").into_result(), Ok((Ast::Minus, 1)));
+        }
+    }
+
+    #[test]
+    fn shared_prec_level() {
+        let sum = Prec::level(1);
+        let pow = Prec::level(2);
+
+        let int = || {
+            text::int::<_, _, Err<Simple<char>>>(10)
+                .from_str::<i64>()
+                .unwrapped()
+        };
+        let tuple = int().pratt((
+            infix(sum.left(), just('+'), |l, r| l + r),
+            infix(sum.left(), just('-'), |l, r| l - r),
+            infix(pow.right(), just('^'), |l: i64, r: i64| l.pow(r as u32)),
+        ));
+        let table = int().pratt(
+            PrattOps::new()
+                .infix(sum.left(), just('+'), |l, _, r| l + r)
+                .infix(sum.left(), just('-'), |l, _, r| l - r)
+                .infix(pow.right(), just('^'), |l: i64, _, r: i64| l.pow(r as u32)),
+        );
+
+        for expr in [Parser::boxed(tuple), Parser::boxed(table)] {
+            // `+` and `-` share a level, so they fold left to right
+            assert_eq!(expr.parse("1-2+3").into_result(), Ok(2));
+            assert_eq!(expr.parse("1+2-3").into_result(), Ok(0));
+            assert_eq!(expr.parse("2^3^2-1").into_result(), Ok(511));
+        }
+    }
+
+    #[test]
+    fn long_left_assoc_chain() {
+        let parser = |expr: &str| {
+            text::int::<_, _, Err<Rich<char>>>(10)
+                .from_str::<i64>()
+                .unwrapped()
+                .pratt((
+                    infix(left(1), just('-'), |l: i64, r: i64| l - r),
+                    infix(right(2), just('^'), |l: i64, r: i64| l.pow(r as u32)),
+                ))
+                // Chains of left-associative operators shouldn't need more than a single level of nesting
+                .with_max_depth(1)
+                .parse(expr)
+                .into_result()
+                .map_err(|errs| errs.iter().map(ToString::to_string).collect::<Vec<_>>())
+        };
+
+        let long = "1".to_string() + &"-1".repeat(99_999);
+        assert_eq!(parser(&long), Ok(-99_998));
+
+        // Right-associative chains nest, so they quickly exceed the limit
+        assert_eq!(parser("2^1"), Ok(2));
+        assert_eq!(parser("2^1^1"), Err(vec!["nesting too deep".to_string()]));
+    }
+
+    #[test]
+    fn max_depth() {
+        let deep = "-".repeat(10_000) + "1";
+
+        let parser = text::int::<_, _, Err<Rich<char>>>(10)
+            .from_str::<i64>()
+            .unwrapped()
+            .pratt((
+                prefix(2, just('-'), |x: i64| -x),
+                infix(right(1), just('^'), |l: i64, r: i64| l.pow(r as u32)),
+            ))
+            .with_max_depth(256);
+
+        assert_eq!(parser.parse("--1").into_result(), Ok(1));
+        assert_eq!(parser.parse("2^2^3").into_result(), Ok(256));
+
+        let errs = parser.parse(deep.as_str()).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].to_string(), "nesting too deep");
+        // Each operand nested within an operator is one level deeper
+        assert_eq!(errs[0].span(), &SimpleSpan::new(257, 258));
+    }
+
+    #[test]
+    fn ops_table() {
+        fn table_parser<'a>(allow_pow: bool) -> impl Parser<'a, &'a str, i64> {
+            let atom = text::int(10).padded().from_str::<i64>().unwrapped();
+
+            let mut ops = PrattOps::new()
+                .prefix(2, just('-'), |_, x: i64| -x)
+                .postfix(2, just('!'), |x, _| factorial(x))
+                .infix_left(0, just('+'), |l, _, r| l + r)
+                .infix_left(0, just('-'), |l, _, r| l - r)
+                .infix_left(1, just('*'), |l, _, r| l * r)
+                .infix(left(1), just('/'), |l, _, r| l / r);
+            if allow_pow {
+                ops = ops.infix_right(3, just('^'), |l, _, r: i64| l.pow(r as u32));
+            }
+            atom.pratt(ops)
+        }
+
+        // A table behaves the same as the equivalent tuple of operators
+        for input in [
+            "2 + 3 * 4",
+            "2 * 3 + 4",
+            "-2 + 3!",
+            "3! - -2 * 4 / 2",
+            "1 - 2 - 3",
+        ] {
+            assert_eq!(
+                table_parser(false).parse(input).into_result(),
+                parser().parse(input).into_result(),
+            );
+        }
+
+        assert_eq!(table_parser(true).parse("2 ^ 3 ^ 2").into_result(), Ok(512));
+        assert_eq!(table_parser(true).parse("2 * 3 ^ 2").into_result(), Ok(18));
+        assert!(table_parser(false).parse("2 ^ 3").has_errors());
+        assert!(!table_parser(true).check("-2 ^ 3!").has_errors());
+    }
+
+    #[test]
+    fn ops_table_operators() {
+        fn table_parser<'a>() -> impl Parser<'a, &'a str, String, Err<Rich<'a, char>>> {
+            let atom = text::ident().padded().map(str::to_string);
+            let op = |c| just(c).padded();
+
+            let ops = PrattOps::new()
+                .operator(try_infix(
+                    right(0),
+                    op('='),
+                    |(lhs, lhs_span): (String, SimpleSpan), _, (rhs, _), _| {
+                        if lhs.starts_with('(') {
+                            Err(Rich::custom(lhs_span, "invalid assignment target"))
+                        } else {
+                            Ok(format!("({lhs} = {rhs})"))
+                        }
+                    },
+                ))
+                .infix_left(1, op('+'), |l, _, r| format!("({l} + {r})"))
+                .operator(prefix(
+                    2,
+                    op('-'),
+                    |_, rhs, e: &mut MapExtra<'a, '_, &'a str, Err<Rich<'a, char>>>| {
+                        format!("(-{rhs} at {})", e.span())
+                    },
+                ));
+            atom.pratt(ops)
+        }
+
+        assert_eq!(
+            table_parser().parse("a = b + -c").into_result(),
+            Ok("(a = (b + (-c at 8..10)))".to_string()),
+        );
+
+        // Fallible operators in a table are checked like those in a tuple, even when output isn't required
+        let errs = table_parser().parse("a + b = c").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 6));
+        assert_eq!(errs[0].to_string(), "invalid assignment target");
+        assert_eq!(table_parser().check("a + b = c").errors().len(), 1);
+    }
+
+    #[test]
+    fn recover_operand() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Expr {
+            Num(i64),
+            Add(Box<Expr>, Box<Expr>),
+            Error,
+        }
+
+        fn add(l: Expr, r: Expr) -> Expr {
+            Expr::Add(Box::new(l), Box::new(r))
+        }
+
+        fn atom<'a>() -> impl Parser<'a, &'a str, Expr, Err<Simple<'a, char>>> + Clone {
+            text::int(10).padded().from_str().unwrapped().map(Expr::Num)
+        }
+
+        let recovery = || via_parser(none_of::<_, &str, _>("+").padded().to(Expr::Error));
+
+        let tuple = atom()
+            .pratt((infix(left(0), just('+'), add),))
+            .recover_operand_with(recovery());
+        let table = atom()
+            .pratt(PrattOps::new().infix_left(0, just('+'), |l, _, r| add(l, r)))
+            .recover_operand_with(recovery());
+
+        let expected = add(add(Expr::Num(1), Expr::Error), Expr::Num(2));
+        for (out, errs) in [
+            tuple.parse("1 + @ + 2").into_output_errors(),
+            table.parse("1 + @ + 2").into_output_errors(),
+        ] {
+            assert_eq!(out, Some(expected.clone()));
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[0].found(), Some(&'@'));
+            assert_eq!(errs[0].span(), &SimpleSpan::from(4..5));
+        }
+
+        // Valid input is unaffected, and inputs the strategy can't recover from are still rejected
+        assert_eq!(
+            tuple.parse("1 + 2").into_result(),
+            Ok(add(Expr::Num(1), Expr::Num(2))),
+        );
+        assert!(tuple.parse("1 +").has_errors());
+        assert!(tuple.parse("1 +").output().is_none());
+        // Without recovery, the bad operand stops the whole parse
+        assert!(atom()
+            .pratt((infix(left(0), just('+'), add),))
+            .parse("1 + @ + 2")
+            .output()
+            .is_none());
+    }
+
+    #[test]
+    fn prefix_only() {
+        let atom = || {
+            text::int::<_, _, Err<Simple<char>>>(10)
+                .from_str::<i64>()
+                .unwrapped()
+        };
+
+        let tuple = atom().pratt((prefix(0, just('-'), |x: i64| -x),));
+        let table = atom().pratt(PrattOps::new().prefix(0, just('-'), |_, x: i64| -x));
+
+        for expr in [Parser::boxed(tuple), Parser::boxed(table)] {
+            assert_eq!(expr.parse("1").into_result(), Ok(1));
+            assert_eq!(expr.parse("---1").into_result(), Ok(-1));
+            assert_eq!(expr.parse("----1").into_result(), Ok(1));
+            // There are no operators that can follow an operand
+            assert!(expr.parse("1-").has_errors());
+            assert_eq!(
+                expr.clone()
+                    .then(just("-").to_slice())
+                    .parse("-1-")
+                    .into_result(),
+                Ok((-1, "-")),
+            );
+        }
+    }
+
+    #[test]
+    fn padded_tokens() {
+        let atom = || text::int(10).from_str().unwrapped().map(Expr::Literal);
+
+        let tuple = atom()
+            .pratt((
+                prefix(2, just('-'), |r| u(Expr::Negate, r)),
+                postfix(2, just('!'), |l| u(Expr::Factorial, l)),
+                infix(left(0), just('+'), |l, r| i(Expr::Add, l, r)),
+                infix(left(1), just('*'), |l, r| i(Expr::Mul, l, r)),
+            ))
+            .padded_tokens()
+            .map(|x| x.to_string());
+        let table = atom()
+            .pratt(
+                PrattOps::new()
+                    .prefix(2, just('-'), |_, r| u(Expr::Negate, r))
+                    .postfix(2, just('!'), |l, _| u(Expr::Factorial, l))
+                    .infix_left(0, just('+'), |l, _, r| i(Expr::Add, l, r))
+                    .infix_left(1, just('*'), |l, _, r| i(Expr::Mul, l, r)),
+            )
+            .padded_tokens()
+            .map(|x| x.to_string());
+
+        for expr in [
+            Parser::<_, _, Err<Simple<char>>>::boxed(tuple),
+            Parser::boxed(table),
+        ] {
+            assert_eq!(
+                expr.parse("1 + 2 * 3").into_result(),
+                Ok("(1 + (2 * 3))".to_string()),
+            );
+            assert_eq!(
+                expr.parse("\t- 1 !+2*  3 \n").into_result(),
+                Ok("((-(1!)) + (2 * 3))".to_string()),
+            );
+            assert_eq!(expr.parse("1+2").into_result(), Ok("(1 + 2)".to_string()));
+            // Whitespace within an atom is still an error
+            assert!(expr.parse("1 + 2 3").has_errors());
+        }
+        // Without padding, whitespace isn't accepted
+        assert!(atom()
+            .pratt((infix(left(0), just('+'), |l, r| i(Expr::Add, l, r)),))
+            .parse("1 + 2")
+            .has_errors());
+    }
+
+    #[test]
+    fn operator_introspection() {
+        let add = infix(
+            left(2),
+            just::<_, &str, Err<Simple<char>>>('+'),
+            |l: i64, r| l + r,
+        );
+        let pow = infix(right(4), just('^'), |l: i64, r| l.pow(r as u32));
+        let eq = infix(non(1), just("=="), |l: i64, r| (l == r) as i64);
+        let neg = prefix(3, just('-'), |x: i64| -x);
+        let fact = postfix(5, just('!'), factorial);
+
+        assert_eq!(add.associativity(), Associativity::Left(2));
+        assert_eq!(add.binding_power(), 2);
+        assert_eq!(pow.associativity(), Associativity::Right(4));
+        assert_eq!(pow.binding_power(), 4);
+        assert_eq!(eq.associativity(), Associativity::Non(1));
+        assert_eq!(eq.binding_power(), 1);
+        assert_eq!(neg.binding_power(), 3);
+        assert_eq!(neg.associativity(), Associativity::Left(3));
+        assert_eq!(fact.binding_power(), 5);
+
+        // Enough to render a precedence table, tightest-binding first
+        let mut table = [
+            ("+", add.associativity()),
+            ("^", pow.associativity()),
+            ("==", eq.associativity()),
+        ];
+        table.sort_by_key(|(_, assoc)| core::cmp::Reverse(assoc.binding_power()));
+        assert_eq!(table.map(|(name, _)| name), ["^", "+", "=="]);
+
+        // Introspection doesn't consume the operators
+        let expr = any()
+            .filter(char::is_ascii_digit)
+            .map(|c: char| c.to_digit(10).unwrap() as i64)
+            .pratt((add, pow, eq, neg, fact));
+        assert_eq!(expr.parse("-2^2+3!==2").into_result(), Ok(1));
+    }
+
+    #[test]
+    fn with_prec() {
+        let atom = || text::int(10).from_str().unwrapped().map(Expr::Literal);
+
+        let tuple = atom()
+            .pratt_with_prec((
+                prefix(2, just('-'), |r| u(Expr::Negate, r)),
+                postfix(3, just('!'), |l| u(Expr::Factorial, l)),
+                infix(left(0), just('+'), |l, r| i(Expr::Add, l, r)),
+                infix(left(1), just('*'), |l, r| i(Expr::Mul, l, r)),
+            ))
+            .map(|(x, prec)| (x.to_string(), prec));
+        let table = atom()
+            .pratt_with_prec(
+                PrattOps::new()
+                    .prefix(2, just('-'), |_, r| u(Expr::Negate, r))
+                    .postfix(3, just('!'), |l, _| u(Expr::Factorial, l))
+                    .infix_left(0, just('+'), |l, _, r| i(Expr::Add, l, r))
+                    .infix_left(1, just('*'), |l, _, r| i(Expr::Mul, l, r)),
+            )
+            .map(|(x, prec)| (x.to_string(), prec));
+
+        for expr in [
+            Parser::<_, _, Err<Simple<char>>>::boxed(tuple),
+            Parser::boxed(table),
+        ] {
+            assert_eq!(
+                expr.parse("1+2*3").into_result(),
+                Ok(("(1 + (2 * 3))".to_string(), Some(left(0)))),
+            );
+            assert_eq!(
+                expr.parse("1*2+3").into_result(),
+                Ok(("((1 * 2) + 3)".to_string(), Some(left(0)))),
+            );
+            assert_eq!(
+                expr.parse("1*2*3").into_result(),
+                Ok(("((1 * 2) * 3)".to_string(), Some(left(1)))),
+            );
+            assert_eq!(
+                expr.parse("-1").into_result(),
+                Ok(("(-1)".to_string(), Some(Associativity::Left(2)))),
+            );
+            assert_eq!(
+                expr.parse("-1!").into_result(),
+                Ok(("(-(1!))".to_string(), Some(Associativity::Left(2)))),
+            );
+            assert_eq!(
+                expr.parse("1*2!").into_result(),
+                Ok(("(1 * (2!))".to_string(), Some(left(1)))),
+            );
+            assert_eq!(expr.parse("1").into_result(), Ok(("1".to_string(), None)));
+        }
+    }
 }
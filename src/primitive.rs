@@ -13,9 +13,11 @@
 //! - [`any`]: parses any single input
 //! - [`one_of`]: parses any one of a sequence of inputs
 //! - [`none_of`]: parses any input that does not appear in a sequence of inputs
+//! - [`range`]: parses any input within a range of inputs
 //! - [`end`]: parses the end of input (i.e: if there any more inputs, this parse fails)
 
 use super::*;
+use core::ops::RangeBounds;
 
 /// See [`end`].
 pub struct End<I, E>(EmptyPhantom<(E, I)>);
@@ -41,11 +43,17 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
-        let before = inp.offset();
+        let before = inp.save();
         match inp.next_maybe_inner() {
             (_, None) => Ok(M::bind(|| ())),
             (at, Some(tok)) => {
-                inp.add_alt(at, Some(None), Some(tok.into()), inp.span_since(before));
+                inp.add_alt(
+                    at,
+                    Some(None),
+                    Some(tok.into()),
+                    inp.span_since(before.offset()),
+                );
+                inp.rewind(before);
                 Err(())
             }
         }
@@ -59,7 +67,25 @@ pub struct Empty<I, E>(EmptyPhantom<(E, I)>);
 
 /// A parser that parses no inputs.
 ///
+/// This parser consumes nothing and never fails, which makes it useful as a neutral starting point when building
+/// parsers up programmatically, such as the base case of a fold or the last alternative of a [`choice`].
+///
 /// The output type of this parser is `()`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// // An optional `mut` modifier, where the absence of the modifier is represented by `empty`
+/// let modifier = choice((
+///     text::ascii::keyword::<_, _, _, extra::Err<Simple<char>>>("mut").padded().to(true),
+///     empty().to(false),
+/// ));
+/// let binding = modifier.then(text::ascii::ident());
+///
+/// assert_eq!(binding.parse("mut x").into_result(), Ok((true, "x")));
+/// assert_eq!(binding.parse("x").into_result(), Ok((false, "x")));
+/// ```
 pub const fn empty<I, E>() -> Empty<I, E> {
     Empty(EmptyPhantom::new())
 }
@@ -124,6 +150,10 @@ impl<T: Clone, I, E> Clone for Just<T, I, E> {
 
 /// A parser that accepts only the given input.
 ///
+/// The input may be a single token or any [`OrderedSeq`] of them, such as a [`&str`], or an array, slice, or [`Vec`]
+/// of tokens. A sequence is matched one token at a time, so if the input diverges from it part way through, the error
+/// is reported at the first token that doesn't match.
+///
 /// The output type of this parser is `C`, the input or sequence that was provided.
 ///
 /// # Examples
@@ -183,8 +213,10 @@ where
     ) -> PResult<M, T> {
         let seq = cfg.seq.as_ref().unwrap_or(&self.seq);
 
+        // Like every primitive, leave the input where it started on failure, even if part of the sequence matched
+        let start = inp.save();
         if let Some(()) = seq.seq_iter().find_map(|next| {
-            let before = inp.offset();
+            let before = inp.save();
             match inp.next_maybe_inner() {
                 (_, Some(tok)) if next.borrow() == tok.borrow() => None,
                 (at, found) => {
@@ -192,8 +224,9 @@ where
                         at,
                         Some(Some(T::to_maybe_ref(next))),
                         found.map(|f| f.into()),
-                        inp.span_since(before),
+                        inp.span_since(before.offset()),
                     );
+                    inp.rewind(start);
                     Some(())
                 }
             }
@@ -226,6 +259,9 @@ impl<T: Clone, I, E> Clone for OneOf<T, I, E> {
 
 /// A parser that accepts one of a sequence of specific inputs.
 ///
+/// The set of inputs may be anything that implements [`Seq`], such as a string, an array, or a range of tokens. If
+/// the input is not in the set, the error expects each input in the set.
+///
 /// The output type of this parser is `I`, the input that was found.
 ///
 /// # Examples
@@ -262,18 +298,19 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, I::Token> {
-        let before = inp.offset();
+        let before = inp.save();
         match inp.next_inner() {
             #[allow(suspicious_double_ref_op)] // Is this a clippy bug?
             (_, Some(tok)) if self.seq.contains(tok.borrow()) => Ok(M::bind(|| tok)),
             (at, found) => {
-                let err_span = inp.span_since(before);
+                let err_span = inp.span_since(before.offset());
                 inp.add_alt(
                     at,
                     self.seq.seq_iter().map(|e| Some(T::to_maybe_ref(e))),
                     found.map(|f| f.into()),
                     err_span,
                 );
+                inp.rewind(before);
                 Err(())
             }
         }
@@ -301,6 +338,8 @@ impl<T: Clone, I, E> Clone for NoneOf<T, I, E> {
 
 /// A parser that accepts any input that is *not* in a sequence of specific inputs.
 ///
+/// The set of inputs may be anything that implements [`Seq`], such as a string, an array, or a range of tokens.
+///
 /// The output type of this parser is `I`, the input that was found.
 ///
 /// # Examples
@@ -337,13 +376,14 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, I::Token> {
-        let before = inp.offset();
+        let before = inp.save();
         match inp.next_inner() {
             #[allow(suspicious_double_ref_op)] // Is this a clippy bug?
             (_, Some(tok)) if !self.seq.contains(tok.borrow()) => Ok(M::bind(|| tok)),
             (at, found) => {
-                let err_span = inp.span_since(before);
+                let err_span = inp.span_since(before.offset());
                 inp.add_alt(at, None, found.map(|f| f.into()), err_span);
+                inp.rewind(before);
                 Err(())
             }
         }
@@ -352,6 +392,153 @@ where
     go_extra!(I::Token);
 }
 
+/// See [`range`].
+pub struct TokenRange<R, I, E> {
+    range: R,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, I)>,
+}
+
+impl<R: Copy, I, E> Copy for TokenRange<R, I, E> {}
+impl<R: Clone, I, E> Clone for TokenRange<R, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            range: self.range.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// A parser that accepts any input within a range, such as `'a'..='z'`.
+///
+/// Unlike [`one_of`], this only needs the inputs to be ordered, so it works for any token type that implements
+/// [`PartialOrd`] and never iterates over the range. Both inclusive and exclusive ranges are supported, as are
+/// half-open ranges like `'0'..`. A range with no inputs in it, such as `'b'..'b'`, never matches anything.
+///
+/// Because a range may contain a great many inputs, errors produced by this parser do not list them as expected.
+/// Use [`Parser::labelled`] to describe the range instead.
+///
+/// The output type of this parser is `I`, the input that was found.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, error::Simple};
+/// let lowercase = range::<_, _, extra::Err<Simple<char>>>('a'..='z')
+///     .repeated()
+///     .at_least(1)
+///     .collect::<String>();
+///
+/// assert_eq!(lowercase.parse("hello").into_result(), Ok("hello".to_string()));
+/// assert!(lowercase.parse("Hello").has_errors());
+/// ```
+pub const fn range<'a, R, I, E>(range: R) -> TokenRange<R, I, E>
+where
+    I: ValueInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: PartialOrd,
+    R: RangeBounds<I::Token>,
+{
+    TokenRange {
+        range,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, I, E, R> ParserSealed<'a, I, I::Token, E> for TokenRange<R, I, E>
+where
+    I: ValueInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: PartialOrd,
+    R: RangeBounds<I::Token>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, I::Token> {
+        let before = inp.save();
+        match inp.next_inner() {
+            (_, Some(tok)) if self.range.contains(&tok) => Ok(M::bind(|| tok)),
+            (at, found) => {
+                let err_span = inp.span_since(before.offset());
+                inp.add_alt(at, None, found.map(|f| f.into()), err_span);
+                inp.rewind(before);
+                Err(())
+            }
+        }
+    }
+
+    go_extra!(I::Token);
+}
+
+/// See [`prev_token`].
+pub struct PrevToken<I, E> {
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, I)>,
+}
+
+impl<I, E> Copy for PrevToken<I, E> {}
+impl<I, E> Clone for PrevToken<I, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// A parser that consumes no input and produces the input that was most recently consumed, or [`None`] at the start
+/// of the input.
+///
+/// This is useful for context-sensitive lexing, where the meaning of an input depends on what came before it: for
+/// example, `/` might be a division operator after a number but the start of a regex literal elsewhere. Note that
+/// this is the last *input* consumed, so if whitespace was skipped before the current position then the previous
+/// token will be whitespace.
+///
+/// Backtracking restores the previous token along with the input position, so this always agrees with the input
+/// consumed by the parsers that succeeded before it.
+///
+/// The output type of this parser is `Option<I::Token>`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, primitive::prev_token, error::Simple};
+/// // A `-` is a minus sign at the start of the input or after another operator, but subtraction after a digit
+/// let minus = prev_token::<_, extra::Err<Simple<char>>>()
+///     .then_ignore(just('-'))
+///     .map(|prev: Option<char>| match prev {
+///         Some(c) if c.is_ascii_digit() => "sub",
+///         _ => "neg",
+///     });
+/// let token = minus.or(any().to_slice());
+/// let tokens = token.repeated().collect::<Vec<_>>();
+///
+/// assert_eq!(tokens.parse("-1-2").into_result(), Ok(vec!["neg", "1", "sub", "2"]));
+/// assert_eq!(tokens.parse("1*-2").into_result(), Ok(vec!["1", "*", "neg", "2"]));
+/// ```
+pub const fn prev_token<'a, I, E>() -> PrevToken<I, E>
+where
+    I: ValueInput<'a>,
+    E: ParserExtra<'a, I>,
+{
+    PrevToken {
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, I, E> ParserSealed<'a, I, Option<I::Token>, E> for PrevToken<I, E>
+where
+    I: ValueInput<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, Option<I::Token>> {
+        Ok(M::bind(|| {
+            // SAFETY: offset was generated by previous call to `Input::next`, and so is the offset of the token before
+            unsafe { inp.input.prev_token_offset(inp.offset) }
+                .and_then(|offset| unsafe { inp.input.next(offset) }.1)
+        }))
+    }
+
+    go_extra!(Option<I::Token>);
+}
+
 /// See [`custom`].
 pub struct Custom<F, I, O, E> {
     f: F,
@@ -456,17 +643,20 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-        let before = inp.offset();
+        let before = inp.save();
         let next = inp.next_inner();
-        let err_span = inp.span_since(before);
+        let err_span = inp.span_since(before.offset());
         let (at, found) = match next {
-            (at, Some(tok)) => match (self.filter)(tok.clone(), &mut MapExtra::new(before, inp)) {
-                Some(out) => return Ok(M::bind(|| out)),
-                None => (at, Some(tok.into())),
-            },
+            (at, Some(tok)) => {
+                match (self.filter)(tok.clone(), &mut MapExtra::new(before.offset(), inp)) {
+                    Some(out) => return Ok(M::bind(|| out)),
+                    None => (at, Some(tok.into())),
+                }
+            }
             (at, found) => (at, found.map(|f| f.into())),
         };
         inp.add_alt(at, None, found, err_span);
+        inp.rewind(before);
         Err(())
     }
 
@@ -513,17 +703,18 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-        let before = inp.offset();
+        let before = inp.save();
         let next = inp.next_ref_inner();
-        let err_span = inp.span_since(before);
+        let err_span = inp.span_since(before.offset());
         let (at, found) = match next {
-            (at, Some(tok)) => match (self.filter)(tok, &mut MapExtra::new(before, inp)) {
+            (at, Some(tok)) => match (self.filter)(tok, &mut MapExtra::new(before.offset(), inp)) {
                 Some(out) => return Ok(M::bind(|| out)),
                 None => (at, Some(tok.into())),
             },
             (at, found) => (at, found.map(|f| f.into())),
         };
         inp.add_alt(at, None, found, err_span);
+        inp.rewind(before);
         Err(())
     }
 
@@ -550,12 +741,13 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, I::Token> {
-        let before = inp.offset();
+        let before = inp.save();
         match inp.next_inner() {
             (_, Some(tok)) => Ok(M::bind(|| tok)),
             (at, found) => {
-                let err_span = inp.span_since(before);
+                let err_span = inp.span_since(before.offset());
                 inp.add_alt(at, None, found.map(|f| f.into()), err_span);
+                inp.rewind(before);
                 Err(())
             }
         }
@@ -605,12 +797,13 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, &'a I::Token> {
-        let before = inp.offset();
+        let before = inp.save();
         match inp.next_ref_inner() {
             (_, Some(tok)) => Ok(M::bind(|| tok)),
             (at, found) => {
-                let err_span = inp.span_since(before);
+                let err_span = inp.span_since(before.offset());
                 inp.add_alt(at, None, found.map(|f| f.into()), err_span);
+                inp.rewind(before);
                 Err(())
             }
         }
@@ -829,6 +1022,10 @@ pub struct Choice<T> {
 ///
 /// These qualities make this parser ideal for lexers.
 ///
+/// Unlike [`Parser::or`], which is committed to its first parser once that has consumed input, `choice` always
+/// backtracks: each parser is tried in turn from the same point in the input until one succeeds, like a chain of
+/// [`Parser::or`] using [`Or::or_rewinding`](crate::combinator::Or::or_rewinding) at every link.
+///
 /// The output type of this parser is the output type of the inner parsers.
 ///
 /// # Examples
@@ -982,6 +1179,358 @@ where
     go_extra!(O);
 }
 
+/// See [`choice_longest`].
+#[derive(Copy, Clone)]
+pub struct ChoiceLongest<T> {
+    parsers: T,
+}
+
+/// Parse using a tuple of many parsers, producing the output of the one that consumes the most input.
+///
+/// Every parser is tried from the same position. If several of them consume the same amount of input, the first of
+/// them wins, so [`choice`] and this parser agree whenever only one branch matches. This is useful for lexers, where
+/// rules such as `==` and `=` (or keywords and identifiers) overlap and the longest match is expected regardless of
+/// the order in which the rules are written. If the branches are all literals, [`choice_trie`] does the same job
+/// without trying each of them in turn.
+///
+/// The output type of this parser is the output type of the inner parsers.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Token<'a> {
+///     Eq,
+///     EqEq,
+///     Let,
+///     Ident(&'a str),
+/// }
+///
+/// let tokens = choice_longest((
+///     just::<_, _, extra::Err<Simple<char>>>('=').to(Token::Eq),
+///     just("==").to(Token::EqEq),
+///     just("let").to(Token::Let),
+///     text::ascii::ident().map(Token::Ident),
+/// ))
+///     .padded()
+///     .repeated()
+///     .collect::<Vec<_>>();
+///
+/// use Token::*;
+/// assert_eq!(
+///     tokens.parse("let letter = a == b").into_result(),
+///     Ok(vec![Let, Ident("letter"), Eq, Ident("a"), EqEq, Ident("b")]),
+/// );
+/// ```
+pub const fn choice_longest<T>(parsers: T) -> ChoiceLongest<T> {
+    ChoiceLongest { parsers }
+}
+
+// The output of the branch of a `ChoiceLongest` that has consumed the most input so far, along with the secondary
+// errors and recoveries it produced (these are set aside so that trying later branches doesn't discard them).
+struct Longest<'a, I: Input<'a>, E: ParserExtra<'a, I>, M: Mode, O> {
+    offset: I::Offset,
+    out: M::Output<O>,
+    errors: Vec<Located<I::Offset, E::Error>>,
+    recoveries: Vec<(Range<I::Offset>, &'static str)>,
+}
+
+impl<'a, I: Input<'a>, E: ParserExtra<'a, I>, M: Mode, O> Longest<'a, I, E, M, O> {
+    #[inline]
+    fn attempt<'parse, P: Parser<'a, I, O, E>>(
+        longest: &mut Option<Self>,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        before: input::Marker<'a, 'parse, I>,
+        parser: &P,
+    ) {
+        inp.rewind(before);
+        if let Ok(out) = parser.go::<M>(inp) {
+            if !matches!(longest, Some(longest) if inp.offset.into() <= longest.offset.into()) {
+                *longest = Some(Longest {
+                    offset: inp.offset,
+                    out,
+                    errors: inp.errors.secondary.split_off(before.err_count),
+                    recoveries: inp.errors.recoveries.split_off(before.recovery_count),
+                });
+            }
+        }
+    }
+
+    #[inline]
+    fn finish<'parse>(
+        longest: Option<Self>,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        before: input::Marker<'a, 'parse, I>,
+    ) -> PResult<M, O> {
+        inp.rewind(before);
+        let longest = longest.ok_or(())?;
+        inp.offset = longest.offset;
+        inp.errors.secondary.extend(longest.errors);
+        inp.errors.recoveries.extend(longest.recoveries);
+        Ok(longest.out)
+    }
+}
+
+macro_rules! impl_choice_longest_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_choice_longest_for_tuple!($($X)*);
+        impl_choice_longest_for_tuple!(~ $head $($X)*);
+    };
+    (~ $Head:ident $($X:ident)+) => {
+        #[allow(unused_variables, non_snake_case)]
+        impl<'a, I, E, $Head, $($X),*, O> ParserSealed<'a, I, O, E> for ChoiceLongest<($Head, $($X,)*)>
+        where
+            I: Input<'a>,
+            E: ParserExtra<'a, I>,
+            $Head: Parser<'a, I, O, E>,
+            $($X: Parser<'a, I, O, E>),*
+        {
+            #[inline]
+            fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+                let before = inp.save();
+
+                let ChoiceLongest { parsers: ($Head, $($X,)*), .. } = self;
+
+                let mut longest = None;
+                Longest::<I, E, M, O>::attempt(&mut longest, inp, before, $Head);
+                $(
+                    Longest::<I, E, M, O>::attempt(&mut longest, inp, before, $X);
+                )*
+                Longest::finish(longest, inp, before)
+            }
+
+            go_extra!(O);
+        }
+    };
+    (~ $Head:ident) => {
+        impl<'a, I, E, $Head, O> ParserSealed<'a, I, O, E> for ChoiceLongest<($Head,)>
+        where
+            I: Input<'a>,
+            E: ParserExtra<'a, I>,
+            $Head:  Parser<'a, I, O, E>,
+        {
+            #[inline]
+            fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+                self.parsers.0.go::<M>(inp)
+            }
+
+            go_extra!(O);
+        }
+    };
+}
+
+impl_choice_longest_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+
+impl<'a, A, I, O, E> ParserSealed<'a, I, O, E> for ChoiceLongest<&[A]>
+where
+    A: Parser<'a, I, O, E>,
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        if self.parsers.is_empty() {
+            let offs = inp.offset();
+            let err_span = inp.span_since(offs);
+            inp.add_alt(offs.offset, None, None, err_span);
+            Err(())
+        } else {
+            let before = inp.save();
+            let mut longest = None;
+            for parser in self.parsers {
+                Longest::<I, E, M, O>::attempt(&mut longest, inp, before, parser);
+            }
+            Longest::finish(longest, inp, before)
+        }
+    }
+
+    go_extra!(O);
+}
+
+impl<'a, A, I, O, E> ParserSealed<'a, I, O, E> for ChoiceLongest<Vec<A>>
+where
+    A: Parser<'a, I, O, E>,
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        choice_longest(&self.parsers[..]).go::<M>(inp)
+    }
+    go_extra!(O);
+}
+
+impl<'a, A, I, O, E, const N: usize> ParserSealed<'a, I, O, E> for ChoiceLongest<[A; N]>
+where
+    A: Parser<'a, I, O, E>,
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        choice_longest(&self.parsers[..]).go::<M>(inp)
+    }
+    go_extra!(O);
+}
+
+#[derive(Clone)]
+struct TrieNode<T, O> {
+    children: HashMap<T, usize>,
+    // The keys of `children` in the order they were added, so that errors list expected tokens deterministically
+    expected: Vec<T>,
+    value: Option<O>,
+}
+
+/// See [`choice_trie`].
+pub struct ChoiceTrie<T, O, I, E> {
+    nodes: Vec<TrieNode<T, O>>,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, I)>,
+}
+
+impl<T: Clone, O: Clone, I, E> Clone for ChoiceTrie<T, O, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Parse the longest of a set of literal sequences, producing the value associated with it.
+///
+/// The literals are arranged into a prefix trie when the parser is created, so matching takes time proportional to
+/// the length of the match rather than to the number of literals. This makes it a good fit for large sets of
+/// keywords or operators, where a [`choice`] over many [`just`] parsers would try every branch in turn.
+///
+/// Unlike [`choice`], the order of the literals does not matter: overlapping literals such as `"<"`, `"<="` and
+/// `"<<="` always resolve to the longest one present in the input. If the same literal appears more than once, the
+/// first value given for it is used.
+///
+/// The output type of this parser is `O`, the value associated with the literal that was matched.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Op { Lt, Le, Shl, ShlAssign }
+///
+/// let op = choice_trie::<_, _, _, _, extra::Err<Simple<char>>>([
+///     ("<", Op::Lt),
+///     ("<=", Op::Le),
+///     ("<<", Op::Shl),
+///     ("<<=", Op::ShlAssign),
+/// ]);
+///
+/// assert_eq!(op.parse("<").into_result(), Ok(Op::Lt));
+/// assert_eq!(op.parse("<=").into_result(), Ok(Op::Le));
+/// assert_eq!(op.parse("<<=").into_result(), Ok(Op::ShlAssign));
+/// assert!(op.parse(">").has_errors());
+/// ```
+pub fn choice_trie<'a, L, O, Lits, I, E>(literals: Lits) -> ChoiceTrie<I::Token, O, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: Hash + Eq + Clone,
+    L: OrderedSeq<'a, I::Token>,
+    O: Clone,
+    Lits: IntoIterator<Item = (L, O)>,
+{
+    let mut nodes = vec![TrieNode {
+        children: HashMap::default(),
+        expected: Vec::new(),
+        value: None,
+    }];
+
+    for (literal, value) in literals {
+        let mut node = 0;
+        for tok in literal.seq_iter() {
+            let tok = tok.borrow();
+            node = match nodes[node].children.get(tok) {
+                Some(&child) => child,
+                None => {
+                    let child = nodes.len();
+                    nodes.push(TrieNode {
+                        children: HashMap::default(),
+                        expected: Vec::new(),
+                        value: None,
+                    });
+                    nodes[node].children.insert(tok.clone(), child);
+                    nodes[node].expected.push(tok.clone());
+                    child
+                }
+            };
+        }
+        nodes[node].value.get_or_insert(value);
+    }
+
+    ChoiceTrie {
+        nodes,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, I, O, E> ParserSealed<'a, I, O, E> for ChoiceTrie<I::Token, O, I, E>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: Hash + Eq + Clone,
+    O: Clone,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let start = inp.save();
+        let mut node = &self.nodes[0];
+        let mut longest = node.value.as_ref().map(|value| (value, start));
+
+        loop {
+            let before = inp.offset();
+            let next = match inp.next_maybe_inner() {
+                (at, Some(tok)) => match node.children.get(tok.borrow()) {
+                    Some(&child) => Ok(child),
+                    None => Err((at, Some(tok))),
+                },
+                (at, None) => Err((at, None)),
+            };
+
+            match next {
+                Ok(child) => {
+                    node = &self.nodes[child];
+                    if let Some(value) = &node.value {
+                        longest = Some((value, inp.save()));
+                    }
+                }
+                Err((at, found)) => {
+                    return match longest {
+                        Some((value, marker)) => {
+                            inp.rewind(marker);
+                            Ok(M::bind(|| value.clone()))
+                        }
+                        None => {
+                            let err_span = inp.span_since(before);
+                            inp.add_alt(
+                                at,
+                                node.expected
+                                    .iter()
+                                    .map(|tok| Some(MaybeRef::Val(tok.clone()))),
+                                found.map(|f| f.into()),
+                                err_span,
+                            );
+                            inp.rewind(start);
+                            Err(())
+                        }
+                    };
+                }
+            }
+        }
+    }
+
+    go_extra!(O);
+}
+
 /// See [`group`].
 #[derive(Copy, Clone)]
 pub struct Group<T> {
@@ -992,6 +1541,20 @@ pub struct Group<T> {
 /// otherwise returning an error if any parsers fail.
 ///
 /// This parser is to [`Parser::then`] as [`choice`] is to [`Parser::or`]
+///
+/// Because the output is a flat tuple, this is the preferred way to sequence more than two parsers: chaining
+/// [`Parser::then`] instead produces nested tuples like `((a, b), c)` (see [`Parser::unnest2`] if you already have
+/// one of those).
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let digit = any::<_, extra::Err<Simple<char>>>().filter(char::is_ascii_digit);
+/// let date = group((digit, just('-'), digit, just('-'), digit));
+///
+/// assert_eq!(date.parse("1-2-3").into_result(), Ok(('1', '-', '2', '-', '3')));
+/// ```
 pub const fn group<T>(parsers: T) -> Group<T> {
     Group { parsers }
 }
@@ -1107,3 +1670,190 @@ impl_group_for_tuple! {
     Y_ OY
     Z_ OZ
 }
+
+/// See [`chainl1`].
+pub struct ChainL1<A, B, OB> {
+    atom: A,
+    op: B,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<OB>,
+}
+
+impl<A: Copy, B: Copy, OB> Copy for ChainL1<A, B, OB> {}
+impl<A: Clone, B: Clone, OB> Clone for ChainL1<A, B, OB> {
+    fn clone(&self) -> Self {
+        Self {
+            atom: self.atom.clone(),
+            op: self.op.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Parse one or more `atom`s separated by `op`s, combining them from the left with the functions that the `op`s
+/// produce.
+///
+/// This is the classic `chainl1` combinator, useful for chains of left-associative operators of the same precedence,
+/// such as `1 - 2 - 3`, which is combined as `(1 - 2) - 3`. For operators of several different precedences, see
+/// [`Parser::pratt`] (behind the `pratt` feature).
+///
+/// An `op` that is not followed by an `atom` is not consumed, and ends the chain.
+///
+/// The output type of this parser is `O`, the output of `atom`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let int = text::int::<_, _, extra::Err<Simple<char>>>(10).from_str::<i64>().unwrapped();
+/// let op = choice((
+///     just('+').to(i64::wrapping_add as fn(_, _) -> _),
+///     just('-').to(i64::wrapping_sub as fn(_, _) -> _),
+/// ));
+///
+/// let sum = chainl1(int, op);
+///
+/// assert_eq!(sum.parse("1-2-3").into_result(), Ok(-4));
+/// assert_eq!(sum.parse("10-2+3").into_result(), Ok(11));
+/// ```
+pub const fn chainl1<'a, A, B, O, OB, I, E>(atom: A, op: B) -> ChainL1<A, B, OB>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    B: Parser<'a, I, OB, E>,
+    OB: Fn(O, O) -> O,
+{
+    ChainL1 {
+        atom,
+        op,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, A, B, O, OB, I, E> ParserSealed<'a, I, O, E> for ChainL1<A, B, OB>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    B: Parser<'a, I, OB, E>,
+    OB: Fn(O, O) -> O,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let mut acc = self.atom.go::<M>(inp)?;
+        while let Some((f, rhs)) = chain_link::<M, _, _, _, _, _, _>(&self.op, &self.atom, inp)? {
+            acc = M::combine(M::combine(acc, f, |l, f| (l, f)), rhs, |(l, f), r| f(l, r));
+        }
+        Ok(acc)
+    }
+
+    go_extra!(O);
+}
+
+/// See [`chainr1`].
+pub struct ChainR1<A, B, OB> {
+    atom: A,
+    op: B,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<OB>,
+}
+
+impl<A: Copy, B: Copy, OB> Copy for ChainR1<A, B, OB> {}
+impl<A: Clone, B: Clone, OB> Clone for ChainR1<A, B, OB> {
+    fn clone(&self) -> Self {
+        Self {
+            atom: self.atom.clone(),
+            op: self.op.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+/// Parse one or more `atom`s separated by `op`s, combining them from the right with the functions that the `op`s
+/// produce.
+///
+/// This is the classic `chainr1` combinator, useful for chains of right-associative operators of the same precedence,
+/// such as `2 ^ 3 ^ 2`, which is combined as `2 ^ (3 ^ 2)`. For operators of several different precedences, see
+/// [`Parser::pratt`] (behind the `pratt` feature).
+///
+/// An `op` that is not followed by an `atom` is not consumed, and ends the chain.
+///
+/// The output type of this parser is `O`, the output of `atom`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let int = text::int::<_, _, extra::Err<Simple<char>>>(10).from_str::<u32>().unwrapped();
+/// let pow = just('^').to(|l: u32, r| l.pow(r));
+///
+/// let expr = chainr1(int, pow);
+///
+/// assert_eq!(expr.parse("2^3^2").into_result(), Ok(512));
+/// ```
+pub const fn chainr1<'a, A, B, O, OB, I, E>(atom: A, op: B) -> ChainR1<A, B, OB>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    B: Parser<'a, I, OB, E>,
+    OB: Fn(O, O) -> O,
+{
+    ChainR1 {
+        atom,
+        op,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<'a, A, B, O, OB, I, E> ParserSealed<'a, I, O, E> for ChainR1<A, B, OB>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    B: Parser<'a, I, OB, E>,
+    OB: Fn(O, O) -> O,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let mut last = self.atom.go::<M>(inp)?;
+        let mut lhs = Vec::new();
+        while let Some((f, rhs)) = chain_link::<M, _, _, _, _, _, _>(&self.op, &self.atom, inp)? {
+            lhs.push(M::combine(last, f, |l, f| (l, f)));
+            last = rhs;
+        }
+        Ok(lhs
+            .into_iter()
+            .rev()
+            .fold(last, |r, lf| M::combine(lf, r, |(l, f), r| f(l, r))))
+    }
+
+    go_extra!(O);
+}
+
+// Parse the next operator and atom of a chain, rewinding and producing `None` if either is missing
+#[allow(clippy::type_complexity)]
+fn chain_link<'a, M: Mode, A, B, O, OB, I, E>(
+    op: &B,
+    atom: &A,
+    inp: &mut InputRef<'a, '_, I, E>,
+) -> Result<Option<(M::Output<OB>, M::Output<O>)>, ()>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    B: Parser<'a, I, OB, E>,
+{
+    inp.step()?;
+    let before = inp.save();
+    let link = op.go::<M>(inp).and_then(|f| Ok((f, atom.go::<M>(inp)?)));
+    match link {
+        // A link that consumes nothing would repeat forever
+        Ok(link) if inp.offset() != before.offset() => Ok(Some(link)),
+        _ => {
+            inp.rewind(before);
+            Ok(None)
+        }
+    }
+}
@@ -54,6 +54,9 @@ pub trait Mode {
 
     fn get_or<T, F: FnOnce() -> T>(r: Self::Output<T>, f: F) -> T;
 
+    /// Given an [`Output`](Self::Output) containing a [`Result`], extract the error (if any) into the outer result.
+    fn transpose<T, E>(x: Self::Output<Result<T, E>>) -> Result<Self::Output<T>, E>;
+
     /// Invoke a parser user the current mode. This is normally equivalent to
     /// [`parser.go::<M>(inp)`](Parser::go), but it can be called on unsized values such as
     /// `dyn Parser`.
@@ -123,6 +126,10 @@ impl Mode for Emit {
     fn get_or<T, F: FnOnce() -> T>(r: Self::Output<T>, _f: F) -> T {
         r
     }
+    #[inline(always)]
+    fn transpose<T, E>(x: Self::Output<Result<T, E>>) -> Result<Self::Output<T>, E> {
+        x
+    }
 
     #[inline(always)]
     fn invoke<'a, I, O, E, P>(parser: &P, inp: &mut InputRef<'a, '_, I, E>) -> PResult<Self, O>
@@ -183,6 +190,10 @@ impl Mode for Check {
     fn get_or<T, F: FnOnce() -> T>(_r: Self::Output<T>, f: F) -> T {
         f()
     }
+    #[inline(always)]
+    fn transpose<T, E>(_x: Self::Output<Result<T, E>>) -> Result<Self::Output<T>, E> {
+        Ok(())
+    }
 
     #[inline(always)]
     fn invoke<'a, I, O, E, P>(parser: &P, inp: &mut InputRef<'a, '_, I, E>) -> PResult<Self, O>
@@ -280,6 +291,13 @@ where
         inp: &mut InputRef<'a, '_, I, E>,
         state: &mut Self::IterState<M>,
     ) -> IPResult<M, O>;
+
+    // The number of items that the iterator is expected to produce from the given state, used to pre-allocate
+    // containers when collecting
+    #[doc(hidden)]
+    fn size_hint<M: Mode>(&self, _state: &Self::IterState<M>) -> usize {
+        0
+    }
 }
 
 pub trait ConfigIterParserSealed<'a, I, O, E>: IterParserSealed<'a, I, O, E>
@@ -296,6 +314,11 @@ where
         state: &mut Self::IterState<M>,
         cfg: &Self::Config,
     ) -> IPResult<M, O>;
+
+    #[doc(hidden)]
+    fn size_hint_cfg<M: Mode>(&self, state: &Self::IterState<M>, _cfg: &Self::Config) -> usize {
+        self.size_hint(state)
+    }
 }
 
 // TODO: Remove this when MaybeUninit transforms to/from arrays stabilize in any form
@@ -16,6 +16,43 @@ pub trait Strategy<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         inp: &mut InputRef<'a, '_, I, E>,
         parser: &P,
     ) -> PResult<M, O>;
+
+    // The name of this strategy, as reported by [`Recovery::strategy`].
+    #[doc(hidden)]
+    fn name(&self) -> &'static str;
+
+    // Attempt to recover like `recover`, also producing the name of the strategy that recovered. Strategies made of
+    // several others report the one that succeeded.
+    #[doc(hidden)]
+    fn recover_named<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        parser: &P,
+    ) -> Result<(M::Output<O>, &'static str), ()> {
+        self.recover::<M, _>(inp, parser)
+            .map(|out| (out, self.name()))
+    }
+}
+
+/// A record of an error recovery strategy successfully recovering from an error. See
+/// [`Parser::parse_with_recoveries`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Recovery<S> {
+    pub(crate) span: S,
+    pub(crate) strategy: &'static str,
+}
+
+impl<S> Recovery<S> {
+    /// The span of input that was consumed while recovering.
+    pub fn span(&self) -> &S {
+        &self.span
+    }
+
+    /// The name of the strategy that performed the recovery (`"via_parser"`, `"insert"`, `"skip_then_retry_until"`,
+    /// `"skip_until"` or, for [`Parser::then_skip_garbage_until`], `"then_skip_garbage_until"`).
+    pub fn strategy(&self) -> &'static str {
+        self.strategy
+    }
 }
 
 /// See [`via_parser`].
@@ -50,6 +87,57 @@ where
         inp.emit(inp.offset, alt.err);
         Ok(out)
     }
+
+    fn name(&self) -> &'static str {
+        "via_parser"
+    }
+}
+
+/// See [`insert`].
+#[derive(Copy, Clone)]
+pub struct Insert<O>(O);
+
+/// Recover by pretending that the pattern was present, producing the given output without consuming any input.
+///
+/// This is useful for recovering from a missing token that is easy to infer, such as a closing delimiter or a
+/// statement terminator: an error is still emitted for it, but parsing continues as if it had been there.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let stmt = text::ascii::ident::<_, _, extra::Err<Simple<char>>>()
+///     .then(just(';').recover_with(insert(';')))
+///     .padded();
+///
+/// let (out, errs) = stmt.repeated().collect::<Vec<_>>().parse("a; b c;").into_output_errors();
+/// assert_eq!(out, Some(vec![("a", ';'), ("b", ';'), ("c", ';')]));
+/// assert_eq!(errs.len(), 1);
+/// ```
+pub fn insert<O>(output: O) -> Insert<O> {
+    Insert(output)
+}
+
+impl<O> Sealed for Insert<O> {}
+impl<'a, I, O, E> Strategy<'a, I, O, E> for Insert<O>
+where
+    I: Input<'a>,
+    O: Clone,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &P,
+    ) -> PResult<M, O> {
+        let alt = inp.errors.alt.take().expect("error but no alt?");
+        inp.emit(alt.pos, alt.err);
+        Ok(M::bind(|| self.0.clone()))
+    }
+
+    fn name(&self) -> &'static str {
+        "insert"
+    }
 }
 
 /// See [`Parser::recover_with`].
@@ -70,10 +158,14 @@ where
         let before = inp.save();
         match self.parser.go::<M>(inp) {
             Ok(out) => Ok(out),
+            Err(()) if !inp.can_recover() => Err(()),
             Err(()) => {
                 inp.rewind(before);
-                match self.strategy.recover::<M, _>(inp, &self.parser) {
-                    Ok(out) => Ok(out),
+                match self.strategy.recover_named::<M, _>(inp, &self.parser) {
+                    Ok((out, name)) => {
+                        inp.record_recovery(before.offset, name);
+                        Ok(out)
+                    }
                     Err(()) => {
                         // Reset to before fallback attempt
                         inp.rewind(before);
@@ -87,6 +179,130 @@ where
     go_extra!(O);
 }
 
+/// See [`Parser::then_skip_garbage_until`].
+pub struct ThenSkipGarbageUntil<A, U, OU> {
+    pub(crate) parser: A,
+    pub(crate) sync: U,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OU>,
+}
+
+impl<A: Copy, U: Copy, OU> Copy for ThenSkipGarbageUntil<A, U, OU> {}
+impl<A: Clone, U: Clone, OU> Clone for ThenSkipGarbageUntil<A, U, OU> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            sync: self.sync.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, I, O, E, A, U, OU> ParserSealed<'a, I, O, E> for ThenSkipGarbageUntil<A, U, OU>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+    U: Parser<'a, I, OU, E>,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let out = self.parser.go::<M>(inp)?;
+
+        let start = inp.save();
+        // Probing for the sync point shouldn't leave behind errors that would later be reported as expected input
+        let alt = inp.errors.alt.take();
+        let mut found = None;
+        loop {
+            let before = inp.save();
+            let synced = self.sync.go::<Check>(inp).is_ok();
+            inp.rewind(before);
+            if synced {
+                break;
+            }
+            match inp.next_maybe_inner() {
+                (_, Some(tok)) => {
+                    found.get_or_insert(tok);
+                }
+                (_, None) => break,
+            }
+        }
+        inp.errors.alt = alt;
+
+        if let Some(tok) = found {
+            let span = inp.span_since(start.offset());
+            let err = E::Error::expected_found(None, Some(tok.into()), span);
+            if !inp.can_recover() {
+                inp.rewind(start);
+                inp.add_alt_err(inp.offset, err);
+                return Err(());
+            }
+            inp.emit(inp.offset, err);
+            inp.record_recovery(start.offset, "then_skip_garbage_until");
+        }
+
+        Ok(out)
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::then_recover`].
+#[derive(Copy, Clone)]
+pub struct ThenRecover<A, B, OB> {
+    pub(crate) parser: A,
+    pub(crate) other: B,
+    pub(crate) default: OB,
+}
+
+impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, (OA, OB), E> for ThenRecover<A, B, OB>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+    OB: Clone,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, (OA, OB)> {
+        let a = self.parser.go::<M>(inp)?;
+
+        let before = inp.save();
+        // Set aside errors from earlier attempts so that only the second parser's own errors show how far it got
+        let alt = inp.errors.alt.take();
+        let b = match self.other.go::<M>(inp) {
+            Ok(b) => {
+                if let Some(alt) = alt {
+                    inp.add_alt_err(alt.pos, alt.err);
+                }
+                b
+            }
+            Err(()) => {
+                let err = inp.errors.alt.take().expect("error but no alt?");
+                if let Some(alt) = alt {
+                    inp.add_alt_err(alt.pos, alt.err);
+                }
+                if !inp.can_recover() {
+                    inp.add_alt_err(err.pos, err.err);
+                    return Err(());
+                }
+                // Skip whatever the second parser got through before failing
+                inp.rewind(before);
+                while inp.offset.into() < err.pos.into() {
+                    if inp.next_maybe_inner().1.is_none() {
+                        break;
+                    }
+                }
+                inp.emit(err.pos, err.err);
+                inp.record_recovery(before.offset, "then_recover");
+                M::bind(|| self.default.clone())
+            }
+        };
+
+        Ok(M::combine(a, b, |a, b| (a, b)))
+    }
+
+    go_extra!((OA, OB));
+}
+
 /// See [`skip_then_retry_until`].
 #[must_use]
 #[derive(Copy, Clone)]
@@ -138,6 +354,10 @@ where
             }
         }
     }
+
+    fn name(&self) -> &'static str {
+        "skip_then_retry_until"
+    }
 }
 
 /// TODO
@@ -183,6 +403,10 @@ where
             }
         }
     }
+
+    fn name(&self) -> &'static str {
+        "skip_until"
+    }
 }
 
 /// A recovery parser that skips input until one of several inputs is found.
@@ -197,6 +421,65 @@ pub fn skip_until<S, U, F>(skip: S, until: U, fallback: F) -> SkipUntil<S, U, F>
     }
 }
 
+macro_rules! impl_strategy_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_strategy_for_tuple!($($X)*);
+        impl_strategy_for_tuple!(~ $head $($X)*);
+    };
+    (~ $Head:ident $($X:ident)*) => {
+        impl<$Head, $($X),*> Sealed for ($Head, $($X,)*) {}
+
+        #[allow(non_snake_case)]
+        impl<'a, I, O, E, $Head, $($X),*> Strategy<'a, I, O, E> for ($Head, $($X,)*)
+        where
+            I: Input<'a>,
+            E: ParserExtra<'a, I>,
+            $Head: Strategy<'a, I, O, E>,
+            $($X: Strategy<'a, I, O, E>),*
+        {
+            fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+                &self,
+                inp: &mut InputRef<'a, '_, I, E>,
+                parser: &P,
+            ) -> PResult<M, O> {
+                self.recover_named::<M, _>(inp, parser).map(|(out, _)| out)
+            }
+
+            fn name(&self) -> &'static str {
+                self.0.name()
+            }
+
+            fn recover_named<M: Mode, P: Parser<'a, I, O, E>>(
+                &self,
+                inp: &mut InputRef<'a, '_, I, E>,
+                parser: &P,
+            ) -> Result<(M::Output<O>, &'static str), ()> {
+                let before = inp.save();
+
+                let ($Head, $($X,)*) = self;
+
+                // A strategy that fails puts back the error that it was recovering from, so the next one can try
+                match $Head.recover_named::<M, _>(inp, parser) {
+                    Ok(out) => return Ok(out),
+                    Err(()) => inp.rewind(before),
+                }
+
+                $(
+                    match $X.recover_named::<M, _>(inp, parser) {
+                        Ok(out) => return Ok(out),
+                        Err(()) => inp.rewind(before),
+                    }
+                )*
+
+                Err(())
+            }
+        }
+    };
+}
+
+impl_strategy_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_);
+
 /// A recovery parser that searches for a start and end delimiter, respecting nesting.
 ///
 /// It is possible to specify additional delimiter pairs that are valid in the pattern's context for better errors. For
@@ -204,6 +487,48 @@ pub fn skip_until<S, U, F>(skip: S, until: U, fallback: F) -> SkipUntil<S, U, F>
 /// this can aid in detecting delimiter mismatches.
 ///
 /// A function that generates a fallback output on recovery is also required.
+///
+/// This is a parser rather than a [`Strategy`], so use it with [`via_parser`]. Because the whole delimited region is
+/// skipped as a unit, the rest of the input following it can still be parsed as normal.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// enum Expr<'a> {
+///     Error,
+///     Num(u64),
+///     Call(&'a str, Vec<Expr<'a>>),
+///     Add(Box<Expr<'a>>, Box<Expr<'a>>),
+/// }
+///
+/// let expr = recursive::<_, _, extra::Err<Simple<char>>, _, _>(|expr| {
+///     let num = text::int(10).from_str().unwrapped().map(Expr::Num);
+///     let args = expr
+///         .separated_by(just(','))
+///         .collect()
+///         .delimited_by(just('('), just(')'))
+///         .recover_with(via_parser(nested_delimiters('(', ')', [], |_| vec![Expr::Error])));
+///     let call = text::ascii::ident().then(args).map(|(f, args)| Expr::Call(f, args));
+///
+///     let atom = num.or(call).padded();
+///
+///     atom.clone().foldl(just('+').ignore_then(atom).repeated(), |a, b| {
+///         Expr::Add(Box::new(a), Box::new(b))
+///     })
+/// });
+///
+/// let res = expr.parse("foo(@@@) + 1");
+/// assert_eq!(res.errors().len(), 1);
+/// assert_eq!(
+///     res.output(),
+///     Some(&Expr::Add(
+///         Box::new(Expr::Call("foo", vec![Expr::Error])),
+///         Box::new(Expr::Num(1)),
+///     )),
+/// );
+/// ```
 // TODO: Make this a strategy, add an unclosed_delimiter error
 pub fn nested_delimiters<'a, I, O, E, F, const N: usize>(
     start: I::Token,
@@ -217,7 +542,6 @@ where
     E: extra::ParserExtra<'a, I> + MaybeSync,
     F: Fn(I::Span) -> O + Clone,
 {
-    // TODO: Does this actually work? TESTS!
     #[allow(clippy::tuple_array_conversions)]
     // Clippy is overly eager to fine pointless non-problems
     recursive({
@@ -230,7 +554,9 @@ where
             );
             for (s, e) in &others {
                 many_block = Parser::boxed(
-                    many_block.or(block.clone().delimited_by(just(s.clone()), just(e.clone()))),
+                    many_block
+                        .or(block.clone().delimited_by(just(s.clone()), just(e.clone())))
+                        .or_rewinding(),
                 );
             }
 
@@ -241,6 +567,7 @@ where
 
             many_block
                 .or(any().and_is(none_of(skip)).ignored())
+                .or_rewinding()
                 .repeated()
         }
     })
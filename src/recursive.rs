@@ -187,14 +187,16 @@ where
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
         recurse(move || {
-            M::invoke(
-                self.parser()
-                    .inner
-                    .get()
-                    .expect("Recursive parser used before being defined")
-                    .as_ref(),
-                inp,
-            )
+            inp.nested(|inp| {
+                M::invoke(
+                    self.parser()
+                        .inner
+                        .get()
+                        .expect("Recursive parser used before being defined")
+                        .as_ref(),
+                    inp,
+                )
+            })
         })
     }
 
@@ -208,7 +210,7 @@ where
 {
     #[inline]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
-        recurse(move || M::invoke(&*self.parser(), inp))
+        recurse(move || inp.nested(|inp| M::invoke(&*self.parser(), inp)))
     }
 
     go_extra!(O);
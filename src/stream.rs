@@ -1,6 +1,6 @@
 use super::*;
 
-use core::cell::Cell;
+use core::cell::{Cell, RefCell};
 
 /// An input that dynamically pulls tokens from an [`Iterator`].
 ///
@@ -130,6 +130,107 @@ where
     }
 }
 
+/// An input that lazily pulls tokens from an [`Iterator`] one at a time, caching every token that has been read.
+///
+/// Unlike [`Stream`], which pulls tokens in batches, the iterator is only advanced as far as the parser actually
+/// looks. This is useful when producing each token is expensive or has side effects, such as a lexer that reads
+/// from an interactive source. Every token is kept, so parsers may backtrack arbitrarily far at the cost of holding
+/// the input read so far in memory.
+///
+/// # Example
+///
+/// ```
+/// # use chumsky::{prelude::*, input::CachedIterInput};
+/// // Trying the first pattern reads four tokens, all of which are read again by the second
+/// let parser = just::<_, _, extra::Err<Simple<_>>>([1, 2, 3, 4])
+///     .or(just([1, 2, 3, 5]))
+///     .or_rewinding();
+///
+/// let input = CachedIterInput::from_iter([1, 2, 3, 5]);
+/// assert_eq!(parser.parse(input).into_result(), Ok([1, 2, 3, 5]));
+/// ```
+pub struct CachedIterInput<I: Iterator> {
+    tokens: RefCell<(Vec<I::Item>, Option<I>)>,
+}
+
+impl<I: Iterator> CachedIterInput<I> {
+    /// Create a new `CachedIterInput` from an [`Iterator`].
+    pub fn from_iter<J: IntoIterator<IntoIter = I>>(iter: J) -> Self {
+        Self {
+            tokens: RefCell::new((Vec::new(), Some(iter.into_iter()))),
+        }
+    }
+}
+
+impl<I: Iterator> Sealed for CachedIterInput<I> {}
+impl<'a, I: Iterator + 'a> Input<'a> for CachedIterInput<I>
+where
+    I::Item: Clone,
+{
+    type Offset = usize;
+    type Token = I::Item;
+    type Span = SimpleSpan<usize>;
+
+    #[inline(always)]
+    fn start(&self) -> Self::Offset {
+        0
+    }
+
+    type TokenMaybe = I::Item;
+
+    #[inline(always)]
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        self.next(offset)
+    }
+
+    #[inline(always)]
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        range.into()
+    }
+
+    #[inline]
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        offs.saturating_sub(1)
+    }
+}
+
+impl<'a, I: ExactSizeIterator + 'a> ExactSizeInput<'a> for CachedIterInput<I>
+where
+    I::Item: Clone,
+{
+    #[inline(always)]
+    unsafe fn span_from(&self, range: RangeFrom<Self::Offset>) -> Self::Span {
+        let (vec, iter) = &*self.tokens.borrow();
+        let len = vec.len() + iter.as_ref().map_or(0, ExactSizeIterator::len);
+        (range.start..len).into()
+    }
+}
+
+impl<'a, I: Iterator + 'a> ValueInput<'a> for CachedIterInput<I>
+where
+    I::Item: Clone,
+{
+    #[inline]
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        let (vec, iter) = &mut *self.tokens.borrow_mut();
+
+        // Pull only as many items as are needed to reach the given offset
+        while vec.len() <= offset {
+            match iter.as_mut().and_then(Iterator::next) {
+                Some(tok) => vec.push(tok),
+                None => {
+                    // Don't call the iterator again once it has finished, since it might not be fused
+                    *iter = None;
+                    break;
+                }
+            }
+        }
+
+        let tok = vec.get(offset).cloned();
+        (offset + tok.is_some() as usize, tok)
+    }
+}
+
 #[test]
 fn spanned() {
     fn parser<'a>() -> impl Parser<
@@ -148,6 +148,28 @@ where
     go_extra!(O);
 }
 
+/// A parser that accepts (and ignores) any number of whitespace characters before another pattern. See
+/// [`Repeated::allow_whitespace`](crate::combinator::Repeated::allow_whitespace).
+#[derive(Copy, Clone)]
+pub struct PaddedBefore<A> {
+    pub(crate) parser: A,
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for PaddedBefore<A>
+where
+    I: ValueInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: Char,
+    A: Parser<'a, I, O, E>,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        inp.skip_while(|c| c.is_whitespace());
+        self.parser.go::<M>(inp)
+    }
+
+    go_extra!(O);
+}
+
 /// A parser that accepts (and ignores) any number of whitespace characters.
 ///
 /// This parser is a `Parser::Repeated` and so methods such as `at_least()` can be called on it.
@@ -253,9 +275,95 @@ where
             ]
             .contains(&(c as I::Token).to_char()) => c,
         })
+        .or_rewinding()
         .ignored()
 }
 
+/// A parser that accepts (and ignores) a C-style line continuation: a backslash immediately followed by a
+/// [`newline`].
+///
+/// Skipping line continuations between the characters of a token, or as part of the padding between tokens, makes
+/// the lines that they join look like a single line to the parsers around them. A backslash that is not followed by
+/// a newline is not a line continuation, and is not consumed.
+///
+/// The output type of this parser is `()`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let word = any::<_, extra::Err<Simple<char>>>()
+///     .filter(|c: &char| c.is_alphabetic())
+///     .padded_by(text::line_continuation().repeated())
+///     .repeated()
+///     .at_least(1)
+///     .collect::<String>();
+///
+/// assert_eq!(word.parse("hel\\\nlo").into_result(), Ok("hello".to_string()));
+/// assert_eq!(word.parse("hel\\\r\nlo").into_result(), Ok("hello".to_string()));
+/// // Not followed by a newline, so the backslash is left for the word to reject
+/// assert!(word.parse("hel\\lo").has_errors());
+/// ```
+#[must_use]
+pub fn line_continuation<'a, I: ValueInput<'a>, E: ParserExtra<'a, I>>(
+) -> impl Parser<'a, I, (), E> + Copy
+where
+    I::Token: Char,
+{
+    just(I::Token::from_ascii(b'\\'))
+        .ignore_then(newline())
+        .ignored()
+}
+
+/// A parser that accepts (and ignores) any number of whitespace characters and comments.
+///
+/// `line_comment` parses the start of a comment that runs until the end of the line, such as `//`, and
+/// `block_comment` parses the start and end of a comment that may span several lines, such as `/*` and `*/`. A line
+/// comment may also run until the end of the input. Block comments do not nest.
+///
+/// This is most useful with [`Parser::padded_by`], to skip layout between tokens like [`Parser::padded`] does
+/// while also skipping comments.
+///
+/// The output type of this parser is `()`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let layout = text::whitespace_and_comments(just("//"), (just("/*"), just("*/")));
+/// let num = text::int::<_, _, extra::Err<Simple<char>>>(10).padded_by(layout);
+/// let sum = num.separated_by(just('+')).collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     sum.parse("1 + /* two */ 2 // three\n + 3").into_result(),
+///     Ok(vec!["1", "2", "3"]),
+/// );
+/// ```
+pub fn whitespace_and_comments<'a, I, E, L, OL, B, OB, D, OD>(
+    line_comment: L,
+    block_comment: (B, D),
+) -> impl Parser<'a, I, (), E> + Clone
+where
+    I: ValueInput<'a>,
+    I::Token: Char,
+    E: ParserExtra<'a, I>,
+    L: Parser<'a, I, OL, E> + Clone,
+    B: Parser<'a, I, OB, E> + Clone,
+    D: Parser<'a, I, OD, E> + Clone,
+{
+    let whitespace = select! { c if (c as I::Token).is_whitespace() => () };
+    let line = line_comment
+        .then(any().and_is(newline().not()).repeated())
+        .ignored();
+    let (block_start, block_end) = block_comment;
+    let block = block_start
+        .then(any().and_is(block_end.clone().not()).repeated())
+        .then(block_end)
+        .ignored();
+
+    choice((whitespace, line, block)).repeated()
+}
+
 /// A parser that accepts one or more ASCII digits.
 ///
 /// The output type of this parser is `I::Slice` (i.e: [`&str`] when `I` is [`&str`], and [`&[u8]`]
@@ -347,6 +455,364 @@ pub fn int<'a, I: ValueInput<'a> + StrInput<'a, C>, C: Char, E: ParserExtra<'a,
         .to_slice()
 }
 
+/// A parser that accepts a non-negative integer, like [`int`], but rejects multi-digit integers with a leading zero.
+///
+/// This matches the integer grammar of formats like JSON, where `007` is invalid. A bare `0` is still accepted, but a
+/// `0` followed by further digits produces an [`Error::leading_zero`] error at the span of the `0`.
+///
+/// The output type of this parser is `I::Slice` (i.e: [`&str`] when `I` is [`&str`], and [`&[u8]`]
+/// when `I::Slice` is [`&[u8]`]).
+///
+/// The `radix` parameter functions identically to [`char::is_digit`]. If in doubt, choose `10`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let dec = text::int_no_leading_zero::<_, _, extra::Err<Rich<char>>>(10);
+///
+/// assert_eq!(dec.parse("0").into_result(), Ok("0"));
+/// assert_eq!(dec.parse("10").into_result(), Ok("10"));
+/// assert_eq!(
+///     dec.parse("007").into_result(),
+///     Err(vec![Rich::custom((0..1).into(), "leading zeros are not allowed")]),
+/// );
+/// ```
+#[must_use]
+pub fn int_no_leading_zero<
+    'a,
+    I: ValueInput<'a> + StrInput<'a, C>,
+    C: Char,
+    E: ParserExtra<'a, I>,
+>(
+    radix: u32,
+) -> impl Parser<'a, I, &'a C::Str, E> + Copy {
+    custom(move |inp| {
+        let before = inp.checkpoint();
+        if inp.peek() != Some(C::digit_zero()) {
+            return inp.parse(int(radix));
+        }
+        // Check for more digits directly, rather than by trying `int`, so that the only error is the one for the
+        // leading zero
+        inp.skip();
+        let zero_span = inp.span_since(before.offset());
+        match inp.peek() {
+            Some(c) if (c as C).is_digit(radix) => {
+                inp.rewind_to(before);
+                Err(Error::leading_zero(zero_span))
+            }
+            _ => Ok(inp.slice_since(before.offset()..)),
+        }
+    })
+}
+
+/// A parser that accepts a decimal literal, such as `12`, `-0.5`, or `+3.140`, without converting it to a float.
+///
+/// A decimal literal is an optional sign (`+` or `-`), one or more decimal digits, and optionally a `.` followed by
+/// one or more decimal digits. Leading and trailing zeroes are kept, so the literal can be converted exactly into a
+/// fixed-point or arbitrary-precision decimal type.
+///
+/// The output type of this parser is `(bool, I::Slice, I::Slice)`: whether the literal is negative, the digits before
+/// the `.`, and the digits after it. The last of these is empty if the literal has no `.`, and its length is the
+/// literal's scale (the number of digits after the `.`).
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let decimal = text::decimal::<_, _, extra::Err<Simple<char>>>();
+///
+/// assert_eq!(decimal.parse("-12.340").into_result(), Ok((true, "12", "340")));
+/// assert_eq!(decimal.parse("+007").into_result(), Ok((false, "007", "")));
+/// // Digits are required on both sides of the `.`
+/// assert!(decimal.parse(".5").has_errors());
+/// assert!(decimal.parse("5.").has_errors());
+/// ```
+#[must_use]
+pub fn decimal<'a, I: ValueInput<'a> + StrInput<'a, C>, C: Char, E: ParserExtra<'a, I>>(
+) -> impl Parser<'a, I, (bool, &'a C::Str, &'a C::Str), E> + Copy {
+    let negative = just(C::from_ascii(b'-'))
+        .to(true)
+        .or(just(C::from_ascii(b'+')).to(false))
+        .or_not()
+        .map(|sign| sign.unwrap_or(false));
+    let frac = just(C::from_ascii(b'.'))
+        .ignore_then(digits(10).to_slice())
+        .or(empty().to_slice())
+        .or_rewinding();
+
+    negative
+        .then(digits(10).to_slice())
+        .then(frac)
+        .map(|((negative, int), frac)| (negative, int, frac))
+}
+
+/// A parser that accepts a quoted string in which the quote character is escaped by doubling it, as in CSV fields and
+/// SQL string literals (`'it''s'`).
+///
+/// The output type of this parser is [`String`], the contents of the string between its quotes with every doubled
+/// quote replaced by a single one. No other escape sequences are recognised.
+///
+/// A string that is not closed before the end of the input produces an error at the span of its opening quote.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let field = text::doubled_quote_string::<_, extra::Err<Simple<char>>>('"');
+///
+/// assert_eq!(field.parse(r#""hello""#).into_result(), Ok("hello".to_string()));
+/// assert_eq!(
+///     field.parse(r#""he said ""hi""""#).into_result(),
+///     Ok(r#"he said "hi""#.to_string()),
+/// );
+/// assert_eq!(field.parse(r#""""#).into_result(), Ok(String::new()));
+/// // The string must be terminated
+/// assert!(field.parse(r#""hello"#).has_errors());
+/// ```
+#[must_use]
+pub fn doubled_quote_string<'a, I, E>(quote: char) -> DoubledQuoteString<I, E>
+where
+    I: ValueInput<'a> + Input<'a, Token = char>,
+    E: ParserExtra<'a, I>,
+{
+    DoubledQuoteString {
+        quote,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+/// See [`doubled_quote_string`].
+pub struct DoubledQuoteString<I, E> {
+    pub(crate) quote: char,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(I, E)>,
+}
+
+impl<I, E> Copy for DoubledQuoteString<I, E> {}
+impl<I, E> Clone for DoubledQuoteString<I, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, I, E> ParserSealed<'a, I, String, E> for DoubledQuoteString<I, E>
+where
+    I: ValueInput<'a> + Input<'a, Token = char>,
+    E: ParserExtra<'a, I>,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, String> {
+        let quote = self.quote;
+        let start = inp.offset();
+        match inp.peek_maybe() {
+            Some(c) if *c == quote => inp.skip(),
+            found => {
+                inp.skip();
+                let err_span = inp.span_since(start);
+                inp.add_alt(
+                    start.offset,
+                    Some(Some(MaybeRef::Val(quote))),
+                    found,
+                    err_span,
+                );
+                return Err(());
+            }
+        }
+        let open_span = inp.span_since(start);
+
+        let mut out = M::bind(String::new);
+        loop {
+            let c = match inp.peek() {
+                Some(c) if c == quote => {
+                    inp.skip();
+                    if inp.peek() != Some(quote) {
+                        break;
+                    }
+                    inp.skip();
+                    c
+                }
+                Some(c) => {
+                    inp.skip();
+                    c
+                }
+                None => {
+                    // Nothing can be parsed beyond the end of the input, so this error takes precedence over any
+                    // others
+                    let at = inp.offset().offset;
+                    inp.errors.alt = Some(Located::at(
+                        at,
+                        E::Error::expected_found(Some(Some(MaybeRef::Val(quote))), None, open_span),
+                    ));
+                    return Err(());
+                }
+            };
+            M::combine_mut(&mut out, M::bind(|| c), |out, c| out.push(c));
+        }
+        Ok(out)
+    }
+
+    go_extra!(String);
+}
+
+/// An iterable parser that parses `element` any number of times, separated by the character `separator`.
+///
+/// This behaves exactly like `element.separated_by(just(separator))`, including the errors it produces, but compares
+/// each token with the separator directly rather than running a parser for it. Splitting on a single character is
+/// common (for lists, CSV fields, paths, and so on). Use [`Parser::separated_by`] for anything more elaborate, such as
+/// leading or trailing separators.
+///
+/// The output type of this iterable parser is `O`, the output of `element`.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let fields = text::split_on::<_, _, _, _, extra::Err<Simple<char>>>(',', text::int(10))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(fields.parse("1,22,333").into_result(), Ok(vec!["1", "22", "333"]));
+/// assert_eq!(fields.parse("").into_result(), Ok(vec![]));
+/// assert!(fields.parse("1,22,").has_errors());
+/// ```
+#[must_use]
+pub fn split_on<'a, C, A, O, I, E>(separator: C, element: A) -> SplitOn<A, C>
+where
+    C: Char,
+    A: Parser<'a, I, O, E>,
+    I: ValueInput<'a> + StrInput<'a, C>,
+    E: ParserExtra<'a, I>,
+{
+    SplitOn { separator, element }
+}
+
+/// See [`split_on`].
+#[derive(Copy, Clone)]
+pub struct SplitOn<A, C> {
+    pub(crate) separator: C,
+    pub(crate) element: A,
+}
+
+impl<'a, C, A, O, I, E> IterParserSealed<'a, I, O, E> for SplitOn<A, C>
+where
+    C: Char,
+    A: Parser<'a, I, O, E>,
+    I: ValueInput<'a> + StrInput<'a, C>,
+    E: ParserExtra<'a, I>,
+{
+    type IterState<M: Mode> = usize
+    where
+        I: 'a;
+
+    #[inline(always)]
+    fn make_iter<M: Mode>(
+        &self,
+        _inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<Emit, Self::IterState<M>> {
+        Ok(0)
+    }
+
+    #[inline(always)]
+    fn next<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        count: &mut Self::IterState<M>,
+    ) -> IPResult<M, O> {
+        inp.step()?;
+        // Peek at the separator so that reaching the end of the list doesn't need anything undoing
+        if *count > 0 && inp.peek() != Some(self.separator) {
+            let before = inp.save();
+            let (_, found) = inp.next_inner();
+            let err_span = inp.span_since(before.offset());
+            inp.rewind_input(before);
+            inp.add_alt(
+                before.offset,
+                Some(Some(MaybeRef::Val(self.separator))),
+                found.map(MaybeRef::Val),
+                err_span,
+            );
+            return Ok(None);
+        }
+
+        // The separator has been seen, but the element may still fail after consuming input
+        let before_separator = inp.save();
+        if *count > 0 {
+            inp.skip();
+        }
+        match self.element.go::<M>(inp) {
+            Ok(out) => {
+                *count += 1;
+                Ok(Some(out))
+            }
+            Err(()) => {
+                inp.rewind(before_separator);
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// A parser that accepts a unicode escape sequence of the form `\u{XXXX}`, with between 1 and 6 hexadecimal digits,
+/// as found in the string and character literals of languages like Rust and JavaScript.
+///
+/// The output type of this parser is [`char`], the character with the given code point.
+///
+/// A code point that is not a valid [`char`], because it lies beyond `U+10FFFF` or is a surrogate
+/// (`U+D800`-`U+DFFF`), produces an [`Error::invalid_literal`] error at the span of its digits. Parsing then
+/// continues with [`char::REPLACEMENT_CHARACTER`] in place of the character.
+///
+/// This parser only matches the escape sequence itself, so it can be combined with other escapes to build up
+/// literals of different kinds.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// let escape = text::unicode_escape::<_, extra::Err<Simple<char>>>();
+///
+/// assert_eq!(escape.parse("\\u{41}").into_result(), Ok('A'));
+/// assert_eq!(escape.parse("\\u{1F600}").into_result(), Ok('😀'));
+/// // At least one digit is required
+/// assert!(escape.parse("\\u{}").has_errors());
+/// // Surrogates are not characters
+/// assert!(escape.parse("\\u{D800}").has_errors());
+/// assert_eq!(escape.parse("\\u{110000}").into_output(), Some('\u{FFFD}'));
+///
+/// let string = escape
+///     .or(none_of("\\\""))
+///     .repeated()
+///     .collect::<String>()
+///     .delimited_by(just('"'), just('"'));
+///
+/// assert_eq!(string.parse("\"caf\\u{e9}\"").into_result(), Ok("café".to_string()));
+/// ```
+#[must_use]
+pub fn unicode_escape<'a, I, E>() -> impl Parser<'a, I, char, E> + Copy
+where
+    I: ValueInput<'a> + StrInput<'a, char>,
+    E: ParserExtra<'a, I>,
+{
+    just("\\u{")
+        .ignore_then(
+            digits(16)
+                .at_most(6)
+                .to_slice()
+                .validate(|digits: &str, e, emitter| {
+                    // At most 6 hex digits always fit in a `u32`
+                    let code = u32::from_str_radix(digits, 16).unwrap();
+                    char::from_u32(code).unwrap_or_else(|| {
+                        let reason = if (0xD800..=0xDFFF).contains(&code) {
+                            "surrogate code point in unicode escape"
+                        } else {
+                            "unicode escape out of range"
+                        };
+                        emitter.emit(Error::invalid_literal(reason, e.span()));
+                        char::REPLACEMENT_CHARACTER
+                    })
+                }),
+        )
+        .then_ignore(just('}'))
+}
+
 /// Parsers and utilities for working with ASCII inputs.
 pub mod ascii {
     use super::*;
@@ -431,6 +897,69 @@ pub mod ascii {
             })
             .to_slice()
     }
+
+    /// Like [`keyword`], but ASCII letters match regardless of case, as in SQL.
+    ///
+    /// Only ASCII letters are case-folded: other characters, including non-ASCII letters, must match exactly. Like
+    /// [`keyword`], a match that is followed by further identifier characters is rejected.
+    ///
+    /// The output type of this parser is the matched slice, with its original casing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let select = text::ascii::keyword_ignore_case::<_, _, _, extra::Err<Simple<char>>>("select");
+    ///
+    /// assert_eq!(select.parse("SELECT").into_result(), Ok("SELECT"));
+    /// assert_eq!(select.parse("Select").into_result(), Ok("Select"));
+    /// assert!(select.parse("selects").has_errors());
+    /// ```
+    #[track_caller]
+    pub fn keyword_ignore_case<
+        'a,
+        I: ValueInput<'a> + StrInput<'a, C>,
+        C: Char + 'a,
+        Str: AsRef<C::Str> + 'a + Clone,
+        E: ParserExtra<'a, I> + 'a,
+    >(
+        keyword: Str,
+    ) -> impl Parser<'a, I, &'a C::Str, E> + Clone + 'a {
+        #[cfg(debug_assertions)]
+        {
+            let mut cs = C::str_to_chars(keyword.as_ref());
+            if let Some(c) = cs.next() {
+                assert!(c.to_char().is_ascii_alphabetic() || c.to_char() == '_', "The first character of a keyword must be ASCII alphabetic or an underscore, not {:?}", c);
+            } else {
+                panic!("Keyword must have at least one character");
+            }
+            for c in cs {
+                assert!(c.to_char().is_ascii_alphanumeric() || c.to_char() == '_', "Trailing characters of a keyword must be ASCII alphanumeric or an underscore, not {:?}", c);
+            }
+        }
+        ident()
+            .try_map(move |s: &C::Str, span| {
+                if super::eq_ignore_ascii_case::<C>(s, keyword.as_ref()) {
+                    Ok(())
+                } else {
+                    Err(Error::expected_found(None, None, span))
+                }
+            })
+            .to_slice()
+    }
+}
+
+// Whether two strings are equal when ASCII letters are compared without regard to case
+fn eq_ignore_ascii_case<C: Char>(a: &C::Str, b: &C::Str) -> bool {
+    let mut a = C::str_to_chars(a);
+    let mut b = C::str_to_chars(b);
+    loop {
+        match (a.next(), b.next()) {
+            (Some(a), Some(b)) if a.to_char().eq_ignore_ascii_case(&b.to_char()) => {}
+            (None, None) => break true,
+            _ => break false,
+        }
+    }
 }
 
 // Unicode is the default
@@ -520,6 +1049,234 @@ pub mod unicode {
             })
             .to_slice()
     }
+
+    /// Like [`keyword`], but ASCII letters match regardless of case, as in SQL.
+    ///
+    /// Only ASCII letters are case-folded: other characters, including non-ASCII letters, must match exactly. Like
+    /// [`keyword`], a match that is followed by further identifier characters is rejected.
+    ///
+    /// The output type of this parser is the matched slice, with its original casing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let select = text::unicode::keyword_ignore_case::<_, _, _, extra::Err<Simple<char>>>("select");
+    ///
+    /// assert_eq!(select.parse("SELECT").into_result(), Ok("SELECT"));
+    /// assert_eq!(select.parse("Select").into_result(), Ok("Select"));
+    /// assert!(select.parse("selects").has_errors());
+    /// ```
+    #[track_caller]
+    pub fn keyword_ignore_case<
+        'a,
+        I: ValueInput<'a> + StrInput<'a, C>,
+        C: Char + 'a,
+        Str: AsRef<C::Str> + 'a + Clone,
+        E: ParserExtra<'a, I> + 'a,
+    >(
+        keyword: Str,
+    ) -> impl Parser<'a, I, &'a C::Str, E> + Clone + 'a {
+        #[cfg(debug_assertions)]
+        {
+            let mut cs = C::str_to_chars(keyword.as_ref());
+            if let Some(c) = cs.next() {
+                assert!(
+                    c.is_ident_start(),
+                    "The first character of a keyword must be a valid unicode XID_START, not {:?}",
+                    c
+                );
+            } else {
+                panic!("Keyword must have at least one character");
+            }
+            for c in cs {
+                assert!(c.is_ident_continue(), "Trailing characters of a keyword must be valid as unicode XID_CONTINUE, not {:?}", c);
+            }
+        }
+        ident()
+            .try_map(move |s: &C::Str, span| {
+                if super::eq_ignore_ascii_case::<C>(s, keyword.as_ref()) {
+                    Ok(())
+                } else {
+                    Err(Error::expected_found(None, None, span))
+                }
+            })
+            .to_slice()
+    }
+}
+
+/// A line and the lines indented beneath it, as produced by [`semantic_indentation`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Block<T> {
+    /// The output of the line parser for the line that opens this block.
+    pub line: T,
+    /// The blocks formed by the lines that are indented beneath this one, in order.
+    pub children: Vec<Block<T>>,
+}
+
+/// See [`semantic_indentation`].
+#[derive(Copy, Clone)]
+pub struct SemanticIndentation<A> {
+    pub(crate) line: A,
+}
+
+/// A parser that groups lines into a tree of [`Block`]s according to their indentation (the 'off-side rule').
+///
+/// Each line is made up of indentation (spaces or tabs), followed by a pattern accepted by `line`, followed by a newline
+/// or the end of the input. Blank lines are ignored. A line that is indented further than the line before it opens a
+/// new block nested beneath that line, and a line that is indented less closes blocks until it lines up with an
+/// enclosing one.
+///
+/// The indentation of the first line is taken as the base level. Parsing stops, without consuming it, at the first
+/// line that is indented less than the base level, so this parser can itself be nested within a larger grammar.
+///
+/// Parsing fails if:
+///
+/// - Indentation mixes tabs and spaces
+/// - A line is dedented to a level that does not match any enclosing block
+///
+/// The output type of this parser is `Vec<Block<O>>`, where `O` is the output of the line parser.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, text::Block};
+/// let stmts = text::semantic_indentation::<_, _, &str, extra::Err<Simple<char>>>(
+///     text::ascii::ident(),
+/// );
+///
+/// let leaf = |line| Block { line, children: Vec::new() };
+///
+/// assert_eq!(
+///     stmts.parse("loop\n    a\n    b\nc\n").into_result(),
+///     Ok(vec![
+///         Block { line: "loop", children: vec![leaf("a"), leaf("b")] },
+///         leaf("c"),
+///     ]),
+/// );
+/// // Indentation must be consistent
+/// assert!(stmts.parse("loop\n    a\n\tb\n").has_errors());
+/// assert!(stmts.parse("loop\n    a\n  b\n").has_errors());
+/// ```
+pub fn semantic_indentation<'a, A, O, I, E>(line: A) -> SemanticIndentation<A>
+where
+    I: ValueInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: Char,
+    A: Parser<'a, I, O, E>,
+{
+    SemanticIndentation { line }
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, Vec<Block<O>>, E> for SemanticIndentation<A>
+where
+    I: ValueInput<'a>,
+    E: ParserExtra<'a, I>,
+    I::Token: Char,
+    A: Parser<'a, I, O, E>,
+{
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, Vec<Block<O>>> {
+        fn close<O>(blocks: &mut Vec<Vec<Block<O>>>) {
+            let children = blocks.pop().expect("blocks are never empty");
+            if let Some(parent) = blocks.last_mut().and_then(|b| b.last_mut()) {
+                parent.children = children;
+            }
+        }
+
+        let (space, tab) = (I::Token::from_ascii(b' '), I::Token::from_ascii(b'\t'));
+        let mut indent_char = None;
+        // The indentation width of each open block, and the blocks that have been parsed within it
+        let mut levels = Vec::<usize>::new();
+        let mut blocks = vec![Vec::new()];
+
+        loop {
+            let line_start = inp.save();
+            let indent_start = inp.offset();
+
+            let mut width = 0;
+            loop {
+                let before = inp.offset();
+                match inp.peek() {
+                    Some(c) if c == space || c == tab => {
+                        inp.skip();
+                        if *indent_char.get_or_insert(c) != c {
+                            let err_span = inp.span_since(before);
+                            inp.add_alt(
+                                before.offset,
+                                indent_char.map(|c| Some(MaybeRef::Val(c))),
+                                Some(MaybeRef::Val(c)),
+                                err_span,
+                            );
+                            return Err(());
+                        }
+                        width += 1;
+                    }
+                    _ => break,
+                }
+            }
+
+            if inp.peek().is_none() {
+                inp.rewind(line_start);
+                break;
+            }
+            // Skip blank lines, without letting the attempt to find one contribute to any later error
+            let before_newline = inp.save();
+            let alt = inp.errors.alt.take();
+            let blank = newline().go::<Check>(inp).is_ok();
+            inp.errors.alt = alt;
+            if blank {
+                continue;
+            }
+            inp.rewind(before_newline);
+
+            match levels.last() {
+                None => levels.push(width),
+                Some(&level) if width > level => {
+                    levels.push(width);
+                    blocks.push(Vec::new());
+                }
+                Some(&level) if width < level => {
+                    if width < levels[0] {
+                        inp.rewind(line_start);
+                        break;
+                    }
+                    while matches!(levels.last(), Some(&level) if width < level) {
+                        levels.pop();
+                        close(&mut blocks);
+                    }
+                    if levels.last() != Some(&width) {
+                        let before = inp.offset();
+                        let found = inp.peek_maybe();
+                        let err_span = inp.span(indent_start..before);
+                        inp.add_alt(before.offset, None, found, err_span);
+                        return Err(());
+                    }
+                }
+                Some(_) => {}
+            }
+
+            let line = self.line.go::<Emit>(inp)?;
+            blocks
+                .last_mut()
+                .expect("blocks are never empty")
+                .push(Block {
+                    line,
+                    children: Vec::new(),
+                });
+
+            if inp.peek().is_some() {
+                newline().go::<Check>(inp)?;
+            }
+        }
+
+        while blocks.len() > 1 {
+            close(&mut blocks);
+        }
+        let blocks = blocks.pop().expect("blocks are never empty");
+        Ok(M::bind(|| blocks))
+    }
+
+    go_extra!(Vec<Block<O>>);
 }
 
 // TODO: Better native form of semantic indentation that uses the context system?
@@ -579,4 +1336,331 @@ mod tests {
     fn keyword_unicode_in_ascii() {
         make_ascii_kw_parser::<char, &str>("שלום");
     }
+
+    #[test]
+    fn keyword_ignore_case() {
+        let select = text::keyword_ignore_case::<_, _, _, extra::Err<Simple<char>>>("select");
+
+        for src in ["select", "SELECT", "Select", "sElEcT"] {
+            assert_eq!(select.parse(src).into_result(), Ok(src));
+        }
+        // Identifier boundaries are respected
+        assert!(select.parse("selects").has_errors());
+        assert!(select.parse("select_all").has_errors());
+        assert!(select.parse("selec").has_errors());
+        assert_eq!(select.lazy().parse("SELECT(x)").into_result(), Ok("SELECT"));
+
+        // Only ASCII letters are case-folded
+        let strasse = text::keyword_ignore_case::<_, _, _, extra::Err<Simple<char>>>("straße");
+        assert_eq!(strasse.parse("STRAßE").into_result(), Ok("STRAßE"));
+        assert!(strasse.parse("STRASSE").has_errors());
+
+        let from = text::ascii::keyword_ignore_case::<_, _, _, extra::Err<Simple<u8>>>(b"from");
+        assert_eq!(from.parse(&b"From"[..]).into_result(), Ok(&b"From"[..]));
+        assert!(from.parse(&b"fromage"[..]).has_errors());
+    }
+
+    #[test]
+    fn whitespace_and_comments() {
+        let layout = text::whitespace_and_comments(just("//"), (just("/*"), just("*/")));
+        let num = text::int::<_, _, extra::Err<Simple<char>>>(10)
+            .from_str::<u32>()
+            .unwrapped()
+            .padded_by(layout.clone());
+
+        assert_eq!(num.parse("42 // note").into_result(), Ok(42));
+        assert_eq!(num.parse("42 //").into_result(), Ok(42));
+        assert_eq!(num.parse("/* a */ 42 /* b */").into_result(), Ok(42));
+        assert_eq!(
+            num.parse("// first\n/* multi\nline */\n  42\n// last\n")
+                .into_result(),
+            Ok(42)
+        );
+
+        // Comments end at the end of the line, so what follows is parsed as normal
+        let nums = num.clone().repeated().collect::<Vec<_>>();
+        assert_eq!(nums.parse("1 // 2\n3").into_result(), Ok(vec![1, 3]));
+        assert_eq!(nums.parse("1 /* 2 */ 3").into_result(), Ok(vec![1, 3]));
+
+        // An unterminated block comment isn't skipped
+        assert!(num.parse("42 /* note").has_errors());
+        assert!(layout.parse("/* a */ */").has_errors());
+    }
+
+    #[test]
+    fn semantic_indentation() {
+        use text::Block;
+
+        let stmts = text::semantic_indentation::<_, _, _, extra::Err<Rich<char>>>(
+            text::ascii::ident().then_ignore(just(':').or_not()),
+        );
+        let block = |line, children| Block { line, children };
+        let leaf = |line| block(line, Vec::new());
+
+        assert_eq!(
+            stmts
+                .parse("fn:\n  loop:\n    a\n\n    b\n  c\nd")
+                .into_result(),
+            Ok(vec![
+                block(
+                    "fn",
+                    vec![block("loop", vec![leaf("a"), leaf("b")]), leaf("c")]
+                ),
+                leaf("d"),
+            ]),
+        );
+
+        // The base level is set by the first line, and a dedent below it ends the blocks
+        let item = just("item\n").ignore_then(stmts).then_ignore(just("end"));
+        assert_eq!(
+            item.parse("item\n  a:\n    b\n  c\nend").into_result(),
+            Ok(vec![block("a", vec![leaf("b")]), leaf("c")]),
+        );
+
+        // Mixed tabs and spaces
+        let errs = stmts.parse("a:\n  b\n \tc\n").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(8, 9));
+
+        // Dedent to a level that was never opened
+        let errs = stmts.parse("a:\n    b\n  c\n").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(9, 11));
+    }
+
+    #[test]
+    fn int_no_leading_zero() {
+        let dec = text::int_no_leading_zero::<_, _, extra::Err<Rich<char>>>(10);
+        assert_eq!(dec.parse("0").into_result(), Ok("0"));
+        assert_eq!(dec.parse("10").into_result(), Ok("10"));
+
+        // The error spans the leading zero
+        let errs = dec.parse("007").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 1));
+        assert_eq!(errs[0].to_string(), "leading zeros are not allowed");
+
+        // A lone zero is fine, so the error belongs to whatever follows it
+        let errs = dec.parse("0 ").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&' '));
+        assert_eq!(errs[0].span(), &SimpleSpan::new(1, 2));
+
+        let hex = text::int_no_leading_zero::<_, _, extra::Err<Rich<char>>>(16);
+        assert_eq!(hex.parse("0").into_result(), Ok("0"));
+        assert_eq!(hex.parse("a0").into_result(), Ok("a0"));
+        let errs = hex.parse("0f").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 1));
+
+        // `int` itself stops after a leading zero without an error, leaving the rest to whatever comes next
+        let ints = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .repeated()
+            .collect::<Vec<_>>();
+        assert_eq!(ints.parse("007").into_result(), Ok(vec!["0", "0", "7"]));
+        let zero_then = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .then(just("07"))
+            .to_slice();
+        assert_eq!(zero_then.parse("007").into_result(), Ok("007"));
+    }
+
+    #[test]
+    fn doubled_quote_string() {
+        let field = text::doubled_quote_string::<_, extra::Err<Simple<char>>>('"');
+        assert_eq!(field.parse(r#""abc""#).into_result(), Ok("abc".to_string()));
+        assert_eq!(
+            field.parse(r#""he said ""hi""""#).into_result(),
+            Ok(r#"he said "hi""#.to_string()),
+        );
+        assert_eq!(field.parse(r#""""""#).into_result(), Ok(r#"""#.to_string()));
+        assert_eq!(field.parse(r#""""#).into_result(), Ok(String::new()));
+
+        let sql = text::doubled_quote_string::<_, extra::Err<Simple<char>>>('\'');
+        assert_eq!(sql.parse("'it''s'").into_result(), Ok("it's".to_string()));
+
+        // A quote that isn't doubled ends the string
+        assert!(field.parse(r#""a"b""#).has_errors());
+        assert_eq!(
+            field.padded().then(field).parse(r#""a" "b""#).into_result(),
+            Ok(("a".to_string(), "b".to_string())),
+        );
+
+        // An unterminated string is reported at its opening quote
+        for input in [r#""abc"#, r#"""#, r#""ab""c"#] {
+            let errs = field.parse(input).into_errors();
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[0].found(), None);
+            assert_eq!(errs[0].span(), &SimpleSpan::new(0, 1));
+        }
+
+        let errs = field
+            .padded()
+            .repeated()
+            .collect::<Vec<_>>()
+            .parse(r#""a" "b "#)
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(4, 5));
+    }
+
+    #[test]
+    fn unicode_escape() {
+        let escape = text::unicode_escape::<_, extra::Err<Rich<char>>>();
+
+        assert_eq!(escape.parse("\\u{0}").into_result(), Ok('\0'));
+        assert_eq!(escape.parse("\\u{7A}").into_result(), Ok('z'));
+        assert_eq!(escape.parse("\\u{00e9}").into_result(), Ok('é'));
+        assert_eq!(escape.parse("\\u{FFFD}").into_result(), Ok('\u{FFFD}'));
+        assert_eq!(escape.parse("\\u{1F600}").into_result(), Ok('😀'));
+        assert_eq!(escape.parse("\\u{10FFFF}").into_result(), Ok('\u{10FFFF}'));
+        assert_eq!(escape.parse("\\u{00000A}").into_result(), Ok('\n'));
+
+        // Malformed escapes
+        for input in [
+            "\\u{}",
+            "\\u{",
+            "\\u{12",
+            "\\u{0000000}",
+            "\\u{g}",
+            "\\u41",
+            "u{41}",
+        ] {
+            assert!(escape.parse(input).has_errors(), "{input:?}");
+        }
+
+        // Code points that aren't characters are reported at their digits
+        for (input, span, reason) in [
+            ("\\u{D800}", 3..7, "surrogate code point in unicode escape"),
+            ("\\u{dfff}", 3..7, "surrogate code point in unicode escape"),
+            ("\\u{110000}", 3..9, "unicode escape out of range"),
+            ("\\u{FFFFFF}", 3..9, "unicode escape out of range"),
+        ] {
+            let (out, errs) = escape.parse(input).into_output_errors();
+            assert_eq!(out, Some(char::REPLACEMENT_CHARACTER));
+            assert_eq!(errs.len(), 1, "{input:?}");
+            assert_eq!(errs[0].span(), &SimpleSpan::from(span));
+            assert_eq!(errs[0].to_string(), reason);
+        }
+
+        let string = just('"')
+            .ignore_then(escape.or(none_of("\\\"")).repeated().collect::<String>())
+            .then_ignore(just('"'));
+        assert_eq!(
+            string.parse("\"a\\u{62}c\\u{1F980}\"").into_result(),
+            Ok("abc🦀".to_string()),
+        );
+        let (out, errs) = string.parse("\"x\\u{d83d}y\"").into_output_errors();
+        assert_eq!(out.as_deref(), Some("x\u{FFFD}y"));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(5, 9));
+    }
+
+    #[test]
+    fn decimal_is_exact() {
+        let decimal = text::decimal::<_, _, extra::Err<Rich<char>>>();
+
+        let (negative, int, frac) = decimal.parse("-12.340").into_result().unwrap();
+        assert!(negative);
+        assert_eq!((int, frac), ("12", "340"));
+        // The trailing zero is kept, so the scale is 3 rather than 2
+        assert_eq!(frac.len(), 3);
+
+        assert_eq!(decimal.parse("0").into_result(), Ok((false, "0", "")));
+        assert_eq!(decimal.parse("+0.00").into_result(), Ok((false, "0", "00")));
+        // More digits than any float can represent exactly
+        assert_eq!(
+            decimal
+                .parse("12345678901234567890.000000000000000000001")
+                .into_result(),
+            Ok((false, "12345678901234567890", "000000000000000000001"))
+        );
+
+        // A `.` without digits after it is not part of the literal
+        assert_eq!(
+            decimal.then(just('.')).parse("1.").into_result(),
+            Ok(((false, "1", ""), '.'))
+        );
+        for input in ["", "-", ".5", "-.5", "1.2.3", "1e5", "--1"] {
+            assert!(decimal.parse(input).has_errors(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn line_continuation_joins_lines() {
+        let joined = || text::line_continuation::<_, extra::Err<Simple<char>>>().repeated();
+        let ident = any()
+            .filter(|c: &char| c.is_alphanumeric())
+            .then_ignore(joined())
+            .repeated()
+            .at_least(1)
+            .collect::<String>();
+        let idents = joined()
+            .ignore_then(ident)
+            .padded()
+            .repeated()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            idents.parse("a\\\nb").into_result(),
+            Ok(vec!["ab".to_string()])
+        );
+        assert_eq!(
+            idents
+                .parse("\\\r\nfoo\\\n\\\nbar baz\\\u{2028}")
+                .into_result(),
+            Ok(vec!["foobar".to_string(), "baz".to_string()])
+        );
+        // A newline without a backslash still separates lines
+        assert_eq!(
+            idents.parse("a\nb").into_result(),
+            Ok(vec!["a".to_string(), "b".to_string()])
+        );
+        // A backslash that isn't followed by a newline is left in place
+        assert_eq!(
+            ident
+                .then(just('\\'))
+                .then(ident)
+                .parse("a\\b")
+                .into_result(),
+            Ok((("a".to_string(), '\\'), "b".to_string()))
+        );
+    }
+
+    #[test]
+    fn split_on_matches_separated_by() {
+        let item = || text::int::<_, _, extra::Err<Rich<char>>>(10);
+        let general = item()
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .then_ignore(just(';'));
+        let fast = text::split_on(',', item())
+            .collect::<Vec<_>>()
+            .then_ignore(just(';'));
+
+        for input in [
+            "1,2,3;",
+            "",
+            ";",
+            "1;",
+            "1,;",
+            ",1;",
+            "1,2,x;",
+            "1 2;",
+            "1,2",
+            "12,345,6789;",
+        ] {
+            assert_eq!(
+                fast.parse(input).into_output_errors(),
+                general.parse(input).into_output_errors(),
+                "{input:?}",
+            );
+        }
+
+        let bytes = text::split_on::<_, _, _, &[u8], extra::Default>(b':', text::int(10))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            bytes.parse(b"8:80:8080").into_result(),
+            Ok(vec![&b"8"[..], b"80", b"8080"])
+        );
+    }
 }
@@ -174,3 +174,27 @@ impl<'de, T: Deserialize<'de>, R: Deref<Target = T>> Deserialize<'de> for Maybe<
         deserializer.deserialize_newtype_struct("Maybe", MaybeVisitor(PhantomData))
     }
 }
+
+/// A left-nested tuple, as produced by chaining [`Parser::then`], that can be flattened into `Flat`.
+///
+/// This trait is used by [`Parser::unnest2`] and [`Parser::unnest3`] and is not generally useful on its own.
+pub trait Nested<Flat> {
+    /// Flatten this nested tuple.
+    fn unnest(self) -> Flat;
+}
+
+impl<A, B, C> Nested<(A, B, C)> for ((A, B), C) {
+    #[inline]
+    fn unnest(self) -> (A, B, C) {
+        let ((a, b), c) = self;
+        (a, b, c)
+    }
+}
+
+impl<A, B, C, D> Nested<(A, B, C, D)> for (((A, B), C), D) {
+    #[inline]
+    fn unnest(self) -> (A, B, C, D) {
+        let (((a, b), c), d) = self;
+        (a, b, c, d)
+    }
+}